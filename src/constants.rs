@@ -1,3 +1,7 @@
 pub const TIMESHIFT_COMMENT: &str = "arch-update-manager";
 pub const APP_ID: &str = "com.destbg.arch-update-manager";
 pub const AUR_NAME: &str = "aur";
+pub const AUR_RPC_URL: &str = "https://aur.archlinux.org/rpc/v5";
+pub const AUR_GIT_URL: &str = "https://aur.archlinux.org";
+pub const BUILTIN_AUR_BUILDER: &str = "builtin";
+pub const BTRFS_SNAPSHOT_DIR: &str = "/.arch-update-manager-snapshots";
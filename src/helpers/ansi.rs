@@ -0,0 +1,27 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+static ANSI_REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn strip_ansi_codes(text: &str) -> String {
+    let re = ANSI_REGEX.get_or_init(|| {
+        Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[()][0-9A-Za-z]|\x1b[=>78]")
+            .unwrap()
+    });
+
+    let stripped = re.replace_all(text, "");
+    let mut result = String::with_capacity(stripped.len());
+
+    for line in stripped.split('\n') {
+        let line = line.trim_end_matches('\r');
+        let visible = line.rsplit('\r').next().unwrap_or(line);
+        result.push_str(visible);
+        result.push('\n');
+    }
+
+    if !text.ends_with('\n') {
+        result.pop();
+    }
+
+    return result;
+}
@@ -1,35 +1,55 @@
 use crate::{
-    constants::AUR_NAME,
-    helpers::settings::{get_effective_aur_helper, load_settings},
-    models::{aur_managers::AurManagers, package_update::PackageUpdate},
+    constants::{AUR_NAME, BUILTIN_AUR_BUILDER},
+    helpers::{
+        ansi::strip_ansi_codes,
+        aur_rpc::{get_aur_rpc_url, list_foreign_packages, query_aur_info},
+        devel_packages::{
+            DEVEL_UPDATE_VERSION, has_devel_update, is_devel_check_enabled, is_devel_package,
+        },
+        settings::{get_available_aur_helpers, get_effective_aur_helper, load_settings},
+        vercmp::vercmp,
+    },
+    models::{
+        aur_helper_definition::AurHelperDefinition, aur_update_format::AurUpdateFormat,
+        package_update::PackageUpdate, update_check::UpdateCheck,
+    },
 };
 use anyhow::{Context, Result};
+use regex::Regex;
+use std::cmp::Ordering;
 use std::process::Command;
 
-pub fn detect_aur_helper() -> Option<AurManagers> {
+// `pamac checkupdates` exits with this code when it found updates.
+const PAMAC_UPDATES_AVAILABLE: i32 = 100;
+
+enum HelperLine<'a> {
+    Update {
+        name: &'a str,
+        current: &'a str,
+        new: &'a str,
+    },
+    // Headers, progress messages, descriptions and updates from the official repositories.
+    Skip,
+    Unknown,
+}
+
+pub fn detect_aur_helper() -> Option<AurHelperDefinition> {
     let settings = load_settings();
 
-    if let Some(helper_name) = get_effective_aur_helper(&settings) {
-        if let Some(helper) = AurManagers::from_command(&helper_name) {
-            return Some(helper);
-        }
+    if settings.preferred_aur_helper.as_deref() == Some(BUILTIN_AUR_BUILDER) {
+        return None;
     }
 
-    let helpers = [
-        AurManagers::Yay,
-        AurManagers::Paru,
-        AurManagers::Trizen,
-        AurManagers::Pikaur,
-        AurManagers::PamacCli,
-    ];
-
-    for helper in &helpers {
-        if is_command_available(helper.command()) {
-            return Some(helper.clone());
-        }
+    if let Some(helper) = get_effective_aur_helper(&settings) {
+        return Some(helper);
     }
 
-    return None;
+    return get_available_aur_helpers(&settings).into_iter().next();
+}
+
+// Without a helper, AUR packages are built with makepkg directly.
+pub fn uses_builtin_aur_builder() -> bool {
+    return detect_aur_helper().is_none();
 }
 
 pub fn is_command_available(command: &str) -> bool {
@@ -40,26 +60,106 @@ pub fn is_command_available(command: &str) -> bool {
         .unwrap_or(false);
 }
 
-pub fn get_aur_updates() -> Result<Vec<PackageUpdate>> {
+pub fn get_aur_updates() -> Result<UpdateCheck> {
+    let check = match get_rpc_aur_updates() {
+        Ok(check) => check,
+        Err(e) => {
+            eprintln!(
+                "AUR RPC unavailable, falling back to the AUR helper: {:#}",
+                e
+            );
+            get_helper_aur_updates()?
+        }
+    };
+
+    return Ok(check);
+}
+
+fn get_rpc_aur_updates() -> Result<UpdateCheck> {
+    let foreign = list_foreign_packages()?;
+    if foreign.is_empty() {
+        return Ok(UpdateCheck::default());
+    }
+
+    let settings = load_settings();
+    let names: Vec<String> = foreign.iter().map(|(name, _)| name.clone()).collect();
+    let packages = query_aur_info(&get_aur_rpc_url(&settings), &names)?;
+
+    let mut updates = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (name, current_version) in foreign {
+        let Some(package) = packages.iter().find(|package| package.name == name) else {
+            continue;
+        };
+
+        let new_version = if vercmp(&package.version, &current_version) == Ordering::Greater {
+            package.version.clone()
+        } else if is_devel_package(&name) && is_devel_check_enabled(&settings, &name) {
+            match has_devel_update(&name, &package.package_base, &current_version) {
+                Ok(Some(true)) => DEVEL_UPDATE_VERSION.to_string(),
+                Ok(Some(false)) => continue,
+                Ok(None) => {
+                    warnings.push(format!(
+                        "Upstream state of {} is unknown: its version names no commit. Reinstall it once to start tracking it.",
+                        name
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    warnings.push(format!("Failed to check upstream of {}: {:#}", name, e));
+                    continue;
+                }
+            }
+        } else {
+            continue;
+        };
+
+        updates.push(PackageUpdate {
+            repository: AUR_NAME.to_string(),
+            selected: true,
+            name: name.clone(),
+            description: package
+                .description
+                .clone()
+                .unwrap_or_else(|| format!("AUR package: {}", name)),
+            current_version,
+            new_version,
+            size: 0,
+            aur_info: Some(package.clone()),
+            pkgbuild_findings: Vec::new(),
+            rebuild_reason: None,
+            rebuild_for: Vec::new(),
+        });
+    }
+
+    return Ok(UpdateCheck { updates, warnings });
+}
+
+fn get_helper_aur_updates() -> Result<UpdateCheck> {
     let Some(helper) = detect_aur_helper() else {
-        return Ok(Vec::new());
+        return Ok(UpdateCheck::default());
     };
 
-    let output = Command::new(helper.command())
-        .args(helper.update_check_args())
+    let mut args = helper.check_args.clone();
+    args.extend(helper.no_color_args.iter().cloned());
+
+    let parts = build_helper_command(&helper, &args);
+    let output = Command::new(&parts[0])
+        .args(&parts[1..])
         .output()
-        .context(format!(
-            "Failed to run {} for AUR updates",
-            helper.command()
-        ))?;
+        .context(format!("Failed to run {} for AUR updates", helper.binary))?;
+
+    let updates_available = helper.update_format == AurUpdateFormat::Pamac
+        && output.status.code() == Some(PAMAC_UPDATES_AVAILABLE);
 
-    if !output.status.success() {
+    if !output.status.success() && !updates_available {
         let stderr = String::from_utf8_lossy(&output.stderr);
         if stderr.contains("nothing to do")
             || stderr.contains("no packages")
             || output.stdout.is_empty()
         {
-            return Ok(Vec::new());
+            return Ok(UpdateCheck::default());
         }
         return Err(anyhow::anyhow!("AUR helper failed: {}", stderr));
     }
@@ -68,69 +168,151 @@ pub fn get_aur_updates() -> Result<Vec<PackageUpdate>> {
     return parse_aur_updates(&stdout, &helper);
 }
 
-fn parse_aur_updates(output: &str, helper: &AurManagers) -> Result<Vec<PackageUpdate>> {
-    let mut updates = Vec::new();
+pub fn parse_aur_updates(output: &str, helper: &AurHelperDefinition) -> Result<UpdateCheck> {
+    let update_re = if helper.update_format == AurUpdateFormat::Pattern {
+        Some(
+            Regex::new(&helper.update_pattern)
+                .with_context(|| format!("Invalid update pattern for {}", helper.id))?,
+        )
+    } else {
+        None
+    };
+
+    // Not every helper has a flag to turn colours off, so escape codes are removed regardless.
+    let output = strip_ansi_codes(output);
+    let mut check = UpdateCheck::default();
 
     for line in output.lines() {
-        if line.trim().is_empty() {
+        // Lines starting with `::` are progress messages, like pacman prints them.
+        if line.trim().is_empty() || line.trim_start().starts_with("::") {
             continue;
         }
 
-        let package_update = match helper {
-            AurManagers::PamacCli => parse_pamac_line(line)?,
-            _ => parse_standard_aur_line(line)?,
+        let parsed = match &update_re {
+            Some(update_re) => parse_pattern_line(update_re, line, helper)?,
+            None => match helper.update_format {
+                AurUpdateFormat::Pikaur => parse_pikaur_line(line),
+                AurUpdateFormat::Pamac => parse_pamac_line(line),
+                AurUpdateFormat::Aura => parse_aura_line(line),
+                AurUpdateFormat::Pacman | AurUpdateFormat::Pattern => parse_pacman_line(line),
+            },
         };
 
-        if let Some(update) = package_update {
-            updates.push(update);
-        }
+        let (name, current_version, new_version) = match parsed {
+            HelperLine::Update { name, current, new } => (name, current, new),
+            HelperLine::Skip => continue,
+            HelperLine::Unknown => {
+                check.warnings.push(format!(
+                    "Could not parse {} output: {}",
+                    helper.id,
+                    line.trim()
+                ));
+                continue;
+            }
+        };
+
+        check.updates.push(PackageUpdate {
+            repository: AUR_NAME.to_string(),
+            selected: !line.contains("[ignored]"),
+            name: name.to_string(),
+            description: format!("AUR package: {}", name),
+            current_version: current_version.to_string(),
+            new_version: new_version.to_string(),
+            size: 0,
+            aur_info: None,
+            pkgbuild_findings: Vec::new(),
+            rebuild_reason: None,
+            rebuild_for: Vec::new(),
+        });
     }
 
-    return Ok(updates);
+    return Ok(check);
 }
 
-fn parse_standard_aur_line(line: &str) -> Result<Option<PackageUpdate>> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
+fn parse_pattern_line<'a>(
+    update_re: &Regex,
+    line: &'a str,
+    helper: &AurHelperDefinition,
+) -> Result<HelperLine<'a>> {
+    let Some(caps) = update_re.captures(line) else {
+        return Ok(HelperLine::Unknown);
+    };
+    let (Some(name), Some(current), Some(new)) =
+        (caps.name("name"), caps.name("current"), caps.name("new"))
+    else {
+        return Err(anyhow::anyhow!(
+            "The update pattern of {} needs the groups name, current and new",
+            helper.id
+        ));
+    };
 
-    if parts.len() >= 4 && parts[parts.len() - 2] == "->" {
-        let package_name = parts[0].to_string();
-        let current_version = parts[1].to_string();
-        let new_version = parts[parts.len() - 1].to_string();
+    return Ok(HelperLine::Update {
+        name: name.as_str(),
+        current: current.as_str(),
+        new: new.as_str(),
+    });
+}
 
-        return Ok(Some(PackageUpdate {
-            repository: AUR_NAME.to_string(),
-            selected: true,
-            name: package_name.clone(),
-            description: format!("AUR package: {}", package_name),
-            current_version,
-            new_version,
-            size: 0,
-        }));
+// `name 1.0-1 -> 1.1-1 [ignored]` as pacman -Qu prints it. pacaur prefixes `aur/`.
+fn parse_pacman_line(line: &str) -> HelperLine<'_> {
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    let [name, current, "->", new, rest @ ..] = columns.as_slice() else {
+        return HelperLine::Unknown;
+    };
+    if !rest.iter().all(|column| *column == "[ignored]") {
+        return HelperLine::Unknown;
     }
 
-    return Ok(None);
+    return HelperLine::Update {
+        name: name.rsplit('/').next().unwrap_or(name),
+        current,
+        new,
+    };
 }
 
-fn parse_pamac_line(line: &str) -> Result<Option<PackageUpdate>> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
+// pikaur pads ` aur/name  1.0-1  -> 1.1-1` into columns and, when verbose, puts the description
+// on an indented line below.
+fn parse_pikaur_line(line: &str) -> HelperLine<'_> {
+    if !line.contains("->") && line.starts_with("    ") {
+        return HelperLine::Skip;
+    }
 
-    if parts.len() >= 3 {
-        let package_name = parts[0].to_string();
-        let current_version = parts[1].to_string();
-        let new_version = parts[2].to_string();
+    return parse_pacman_line(line);
+}
 
-        return Ok(Some(PackageUpdate {
-            repository: AUR_NAME.to_string(),
-            selected: true,
-            name: package_name.clone(),
-            description: format!("AUR package: {}", package_name),
-            current_version,
-            new_version,
-            size: 0,
-        }));
+// `name  1.0-1 -> 1.1-1  AUR`. With -a pamac also lists repository updates, which pacman installs.
+fn parse_pamac_line(line: &str) -> HelperLine<'_> {
+    let trimmed = line.trim();
+    if trimmed.ends_with(':')
+        || trimmed.starts_with("Checking for updates")
+        || trimmed == "Your system is up to date."
+    {
+        return HelperLine::Skip;
+    }
+
+    let columns: Vec<&str> = trimmed.split_whitespace().collect();
+    let [name, current, "->", new, repository] = columns.as_slice() else {
+        return HelperLine::Unknown;
+    };
+    if *repository != "AUR" {
+        return HelperLine::Skip;
     }
 
-    return Ok(None);
+    return HelperLine::Update { name, current, new };
+}
+
+// `name : 1.0-1 => 1.1-1`, between messages prefixed with `aura >>=`.
+fn parse_aura_line(line: &str) -> HelperLine<'_> {
+    if line.trim_start().starts_with("aura >>=") {
+        return HelperLine::Skip;
+    }
+
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    let [name, ":", current, "=>", new] = columns.as_slice() else {
+        return HelperLine::Unknown;
+    };
+
+    return HelperLine::Update { name, current, new };
 }
 
 pub fn install_aur_packages(packages: Vec<String>) -> Result<Vec<String>> {
@@ -138,31 +320,28 @@ pub fn install_aur_packages(packages: Vec<String>) -> Result<Vec<String>> {
         return Err(anyhow::anyhow!("No AUR helper available for installation"));
     };
 
-    let mut args = helper.install_args().to_vec();
+    let mut args = helper.install_args.clone();
+    args.extend(packages);
 
-    for package in &packages {
-        args.push(package);
-    }
+    return Ok(build_helper_command(&helper, &args));
+}
 
-    let original_user = get_original_user();
+fn build_helper_command(helper: &AurHelperDefinition, args: &[String]) -> Vec<String> {
+    let mut command_parts = Vec::new();
 
-    if let Some(user) = original_user {
-        let mut command_parts = vec![
-            "sudo".to_string(),
-            "-u".to_string(),
-            user,
-            helper.command().to_string(),
-        ];
-        command_parts.extend(args.into_iter().map(|s| s.to_string()));
-        return Ok(command_parts);
-    } else {
-        let mut command_parts = vec![helper.command().to_string()];
-        command_parts.extend(args.into_iter().map(|s| s.to_string()));
-        return Ok(command_parts);
+    if helper.run_as_user {
+        if let Some(user) = get_original_user() {
+            command_parts.extend(["sudo".to_string(), "-u".to_string(), user]);
+        }
     }
+
+    command_parts.push(helper.binary.clone());
+    command_parts.extend(args.iter().cloned());
+
+    return command_parts;
 }
 
-fn get_original_user() -> Option<String> {
+pub fn get_original_user() -> Option<String> {
     if let Ok(user) = std::env::var("SUDO_USER") {
         if !user.is_empty() && user != "root" {
             return Some(user);
@@ -198,3 +377,175 @@ fn get_original_user() -> Option<String> {
 
     return None;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::aur_helpers::aur_helper_presets;
+
+    fn preset(id: &str) -> AurHelperDefinition {
+        return aur_helper_presets()
+            .into_iter()
+            .find(|helper| helper.id == id)
+            .unwrap();
+    }
+
+    fn summarize(check: &UpdateCheck) -> Vec<(&str, &str, &str, bool)> {
+        return check
+            .updates
+            .iter()
+            .map(|update| {
+                (
+                    update.name.as_str(),
+                    update.current_version.as_str(),
+                    update.new_version.as_str(),
+                    update.selected,
+                )
+            })
+            .collect();
+    }
+
+    fn assert_parses(id: &str, plain: &str, color: &str, expected: &[(&str, &str, &str, bool)]) {
+        let helper = preset(id);
+
+        for output in [plain, color] {
+            let check = parse_aur_updates(output, &helper).unwrap();
+            assert_eq!(check.warnings, Vec::<String>::new(), "{}", id);
+            assert_eq!(summarize(&check), expected, "{}", id);
+            assert!(check.updates.iter().all(|u| u.repository == AUR_NAME));
+        }
+    }
+
+    #[test]
+    fn parses_yay_output() {
+        assert_parses(
+            "yay",
+            include_str!("../../tests/fixtures/aur_helpers/yay.txt"),
+            include_str!("../../tests/fixtures/aur_helpers/yay_color.txt"),
+            &[
+                ("google-chrome", "126.0.6478.55-1", "126.0.6478.114-1", true),
+                ("visual-studio-code-bin", "1.90.0-1", "1.90.2-1", true),
+                ("yay-bin", "12.3.5-1", "12.4.1-1", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_paru_output() {
+        assert_parses(
+            "paru",
+            include_str!("../../tests/fixtures/aur_helpers/paru.txt"),
+            include_str!("../../tests/fixtures/aur_helpers/paru_color.txt"),
+            &[
+                ("google-chrome", "126.0.6478.55-1", "126.0.6478.114-1", true),
+                ("paru", "2.0.3-1", "2.0.4-1", false),
+                ("visual-studio-code-bin", "1.90.0-1", "1.90.2-1", true),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_trizen_output() {
+        assert_parses(
+            "trizen",
+            include_str!("../../tests/fixtures/aur_helpers/trizen.txt"),
+            include_str!("../../tests/fixtures/aur_helpers/trizen_color.txt"),
+            &[
+                ("google-chrome", "126.0.6478.55-1", "126.0.6478.114-1", true),
+                ("trizen", "1:1.68-1", "1:1.69-1", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_pikaur_columns_and_skips_descriptions() {
+        assert_parses(
+            "pikaur",
+            include_str!("../../tests/fixtures/aur_helpers/pikaur.txt"),
+            include_str!("../../tests/fixtures/aur_helpers/pikaur_color.txt"),
+            &[
+                ("google-chrome", "126.0.6478.55-1", "126.0.6478.114-1", true),
+                ("pikaur", "1.20-1", "1.21-1", true),
+                ("zoom", "6.0.12-1", "6.1.0-1", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_only_aur_rows_of_pamac_output() {
+        assert_parses(
+            "pamac",
+            include_str!("../../tests/fixtures/aur_helpers/pamac.txt"),
+            include_str!("../../tests/fixtures/aur_helpers/pamac_color.txt"),
+            &[
+                ("google-chrome", "126.0.6478.55-1", "126.0.6478.114-1", true),
+                ("pamac-aur", "11.6.4-1", "11.7.0-1", true),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_aura_output() {
+        assert_parses(
+            "aura",
+            include_str!("../../tests/fixtures/aur_helpers/aura.txt"),
+            include_str!("../../tests/fixtures/aur_helpers/aura_color.txt"),
+            &[
+                ("aura-bin", "3.2.9-1", "4.0.0-1", true),
+                ("google-chrome", "126.0.6478.55-1", "126.0.6478.114-1", true),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_pacaur_output() {
+        assert_parses(
+            "pacaur",
+            include_str!("../../tests/fixtures/aur_helpers/pacaur.txt"),
+            include_str!("../../tests/fixtures/aur_helpers/pacaur_color.txt"),
+            &[
+                ("google-chrome", "126.0.6478.55-1", "126.0.6478.114-1", true),
+                ("pacaur", "4.8.6-1", "4.8.7-1", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn warns_about_lines_it_cannot_parse() {
+        let check = parse_aur_updates(
+            "yay-bin 12.3.5-1 -> 12.4.1-1\nerror: could not connect to aur.archlinux.org\n",
+            &preset("yay"),
+        )
+        .unwrap();
+
+        assert_eq!(check.updates.len(), 1);
+        assert_eq!(
+            check.warnings,
+            ["Could not parse yay output: error: could not connect to aur.archlinux.org"]
+        );
+    }
+
+    #[test]
+    fn reads_custom_helpers_with_their_pattern() {
+        let mut helper = preset("yay");
+        helper.id = "custom".to_string();
+        helper.update_format = AurUpdateFormat::Pattern;
+        helper.update_pattern = r"^(?P<name>\S+) \[(?P<current>\S+) to (?P<new>\S+)\]".to_string();
+
+        let check = parse_aur_updates("yay-bin [12.3.5-1 to 12.4.1-1]\nDone\n", &helper).unwrap();
+        assert_eq!(
+            summarize(&check),
+            [("yay-bin", "12.3.5-1", "12.4.1-1", true)]
+        );
+        assert_eq!(check.warnings, ["Could not parse custom output: Done"]);
+
+        helper.update_pattern = r"^(?P<name>\S+)".to_string();
+        assert!(parse_aur_updates("yay-bin\n", &helper).is_err());
+
+        let definition: AurHelperDefinition = serde_json::from_str(
+            r#"{"id": "old", "binary": "old", "check_args": [], "install_args": [], "update_pattern": ""}"#,
+        )
+        .unwrap();
+        assert_eq!(definition.update_format, AurUpdateFormat::Pattern);
+    }
+}
@@ -0,0 +1,485 @@
+use anyhow::{Result, anyhow};
+use shlex::try_quote as quote;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::helpers::aur::{get_original_user, is_command_available};
+use crate::helpers::aur_review::{get_aur_clone_dir, load_reviewed_commits};
+use crate::helpers::aur_rpc::{get_aur_rpc_url, query_aur_info, search_aur_providers};
+use crate::helpers::cache_dir::get_cache_dir;
+use crate::helpers::settings::load_settings;
+use crate::models::aur_build::AurBuild;
+use crate::models::aur_build_plan::AurBuildPlan;
+use crate::models::aur_build_status::AurBuildStatus;
+use crate::models::aur_build_step::AurBuildStep;
+use crate::models::aur_package::AurPackage;
+
+// The script runs as root, so it builds in a fresh directory that no other user can replace.
+const BUILD_ROOT_TEMPLATE: &str = "/var/tmp/arch-update-manager-build.XXXXXXXXXX";
+const CHROOT_DIR: &str = "/var/cache/arch-update-manager/chroot";
+
+// The clone belongs to whoever ran the app, and git refuses to read repositories owned by someone
+// else, so the reviewed commit is extracted as root and handed to the build user afterwards.
+const EXTRACT_SOURCE_FUNCTION: &str = r#"extract_source() {
+    local source=$1 commit=$2 dir=$3
+    mkdir "$dir" || return 1
+    git -c safe.directory="$source" -C "$source" archive "$commit" |
+        tar -x --no-same-owner -C "$dir" || return 1
+    chown -R "$build_user": "$dir" || return 1
+}"#;
+
+pub fn resolve_aur_build_plan(targets: &[String]) -> Result<AurBuildPlan> {
+    let base_url = get_aur_rpc_url(&load_settings());
+
+    let mut known: BTreeMap<String, AurPackage> = BTreeMap::new();
+    let mut checked: BTreeSet<String> = targets.iter().cloned().collect();
+    let mut repo_dependencies = BTreeSet::new();
+    let mut pending: Vec<String> = targets.to_vec();
+
+    while !pending.is_empty() {
+        let found = query_aur_info(&base_url, &pending)?;
+        let mut next = Vec::new();
+
+        for name in std::mem::take(&mut pending) {
+            if found.iter().any(|package| package.name == name) {
+                continue;
+            }
+            if targets.contains(&name) {
+                return Err(anyhow!("{} was not found in the AUR", name));
+            }
+
+            // Dependencies such as `foo` are often only provided by `foo-git` or `foo-bin`.
+            let provider = search_aur_providers(&base_url, &name)?
+                .into_iter()
+                .max_by_key(|package| (package.out_of_date.is_none(), package.num_votes))
+                .ok_or_else(|| anyhow!("Neither the repositories nor the AUR provide {}", name))?;
+            if !known.contains_key(&provider.name) && !next.contains(&provider.name) {
+                next.push(provider.name);
+            }
+        }
+
+        let mut dependencies = Vec::new();
+        for package in found {
+            for dependency in package
+                .depends
+                .iter()
+                .chain(&package.make_depends)
+                .chain(&package.check_depends)
+            {
+                let dependency = strip_version_constraint(dependency).to_string();
+                if checked.insert(dependency.clone()) {
+                    dependencies.push(dependency);
+                }
+            }
+            known.insert(package.name.clone(), package);
+        }
+
+        dependencies.retain(|dependency| {
+            !known.values().any(|package| {
+                package.name == *dependency
+                    || package
+                        .provides
+                        .iter()
+                        .any(|provided| strip_version_constraint(provided) == dependency)
+            })
+        });
+
+        for dependency in find_unsatisfied_dependencies(&dependencies)? {
+            if is_repo_dependency(&dependency) {
+                repo_dependencies.insert(dependency);
+            } else if !next.contains(&dependency) {
+                next.push(dependency);
+            }
+        }
+
+        pending = next;
+    }
+
+    let packages: Vec<AurPackage> = known.into_values().collect();
+
+    return Ok(AurBuildPlan {
+        steps: order_aur_builds(&packages, targets)?,
+        repo_dependencies: repo_dependencies.into_iter().collect(),
+    });
+}
+
+// Orders package bases so that every AUR dependency is built and installed before the packages
+// that need it. Bases without a dependency between them keep alphabetical order.
+pub fn order_aur_builds(packages: &[AurPackage], targets: &[String]) -> Result<Vec<AurBuildStep>> {
+    let mut bases: BTreeMap<&str, Vec<&AurPackage>> = BTreeMap::new();
+    for package in packages {
+        bases
+            .entry(&package.package_base)
+            .or_default()
+            .push(package);
+    }
+
+    let mut provided_by: BTreeMap<&str, &str> = BTreeMap::new();
+    for package in packages {
+        provided_by.insert(&package.name, &package.package_base);
+        for provided in &package.provides {
+            provided_by
+                .entry(strip_version_constraint(provided))
+                .or_insert(&package.package_base);
+        }
+    }
+
+    let mut requires: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (base, members) in &bases {
+        let needed = members
+            .iter()
+            .flat_map(|package| {
+                package
+                    .depends
+                    .iter()
+                    .chain(&package.make_depends)
+                    .chain(&package.check_depends)
+            })
+            .filter_map(|dependency| provided_by.get(strip_version_constraint(dependency)))
+            .filter(|dependency_base| *dependency_base != base)
+            .copied()
+            .collect();
+        requires.insert(base, needed);
+    }
+
+    let mut steps = Vec::new();
+    let mut built: BTreeSet<&str> = BTreeSet::new();
+
+    while built.len() < bases.len() {
+        let ready: Vec<&str> = requires
+            .iter()
+            .filter(|(base, needed)| !built.contains(*base) && needed.is_subset(&built))
+            .map(|(base, _)| *base)
+            .collect();
+
+        if ready.is_empty() {
+            let remaining: Vec<&str> = requires
+                .keys()
+                .filter(|base| !built.contains(*base))
+                .copied()
+                .collect();
+            return Err(anyhow!(
+                "These AUR packages depend on each other in a cycle: {}",
+                remaining.join(", ")
+            ));
+        }
+
+        for base in ready {
+            let mut names: Vec<String> = bases[base].iter().map(|p| p.name.clone()).collect();
+            names.sort();
+
+            steps.push(AurBuildStep {
+                package_base: base.to_string(),
+                as_dependency: !names.iter().any(|name| targets.contains(name)),
+                packages: names,
+            });
+            built.insert(base);
+        }
+    }
+
+    return Ok(steps);
+}
+
+pub fn strip_version_constraint(dependency: &str) -> &str {
+    return dependency
+        .split(['<', '>', '=', ':'])
+        .next()
+        .unwrap_or(dependency)
+        .trim();
+}
+
+fn find_unsatisfied_dependencies(dependencies: &[String]) -> Result<Vec<String>> {
+    if dependencies.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // pacman -T prints the dependencies that no installed package satisfies.
+    let output = Command::new("pacman")
+        .arg("-T")
+        .args(dependencies)
+        .output()?;
+
+    return Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| strip_version_constraint(line).to_string())
+        .filter(|line| !line.is_empty())
+        .collect());
+}
+
+fn is_repo_dependency(dependency: &str) -> bool {
+    return Command::new("pacman")
+        .args(["-Sp", "--print-format", "%n", dependency])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+}
+
+pub fn prepare_aur_build(targets: &[String]) -> Result<AurBuild> {
+    if !is_command_available("git") {
+        return Err(anyhow!("git is needed to build AUR packages"));
+    }
+
+    let Some(user) = get_original_user() else {
+        return Err(anyhow!(
+            "makepkg cannot run as root and no regular user started this session"
+        ));
+    };
+
+    let plan = resolve_aur_build_plan(targets)?;
+
+    // Only the commit the user reviewed is built, even if the AUR has moved on since.
+    let reviewed = load_reviewed_commits();
+    let mut sources = Vec::new();
+    for step in &plan.steps {
+        let Some(commit) = reviewed.get(&step.package_base) else {
+            return Err(anyhow!(
+                "The build scripts of {} have not been reviewed",
+                step.package_base
+            ));
+        };
+        sources.push((get_aur_clone_dir(&step.package_base)?, commit.clone()));
+    }
+
+    let settings = load_settings();
+    let chroot = if settings.aur_clean_chroot
+        && is_command_available("makechrootpkg")
+        && is_command_available("mkarchroot")
+    {
+        Some(PathBuf::from(CHROOT_DIR))
+    } else {
+        None
+    };
+
+    let build_dir = get_cache_dir("build")?;
+    let status_file = build_dir.join("status");
+    let script = build_dir.join("build.sh");
+
+    fs::write(&status_file, "")?;
+    fs::write(
+        &script,
+        generate_build_script(&plan, &sources, &user, chroot.as_deref(), &status_file)?,
+    )?;
+
+    return Ok(AurBuild {
+        plan,
+        script,
+        status_file,
+    });
+}
+
+pub fn generate_build_script(
+    plan: &AurBuildPlan,
+    sources: &[(PathBuf, String)],
+    user: &str,
+    chroot: Option<&Path>,
+    status_file: &Path,
+) -> Result<String> {
+    let q = |value: &str| -> Result<String> { Ok(quote(value)?.into_owned()) };
+
+    let mut script = vec![
+        "#!/bin/bash".to_string(),
+        "set -uo pipefail".to_string(),
+        format!("status_file={}", q(&status_file.to_string_lossy())?),
+        format!("build_user={}", q(user)?),
+        "built_files=()".to_string(),
+        String::new(),
+        format!(
+            "build_root=$(mktemp -d {}) || exit 1",
+            q(BUILD_ROOT_TEMPLATE)?
+        ),
+        r#"trap 'rm -rf -- "$build_root"' EXIT"#.to_string(),
+        r#"chmod 755 "$build_root" || exit 1"#.to_string(),
+        String::new(),
+        r#"mark() { printf '%s %s\n' "$1" "$2" >> "$status_file"; }"#.to_string(),
+        r#"pkg_name() { local f=${1##*/}; f=${f%-*}; f=${f%-*}; printf '%s\n' "${f%-*}"; }"#
+            .to_string(),
+        EXTRACT_SOURCE_FUNCTION.to_string(),
+        String::new(),
+    ];
+
+    let build_command = match chroot {
+        Some(chroot) => {
+            script.push(format!("chroot_dir={}", q(&chroot.to_string_lossy())?));
+            script.push(
+                r#"mkdir -p "$chroot_dir" || exit 1
+if [[ -d $chroot_dir/root ]]; then
+    arch-nspawn "$chroot_dir/root" pacman -Syu --noconfirm || exit 1
+else
+    mkarchroot "$chroot_dir/root" base-devel || exit 1
+fi"#
+                .to_string(),
+            );
+            r#"local install_args=() file
+    for file in "${built_files[@]}"; do install_args+=(-I "$file"); done
+    (cd "$dir" && PKGDEST="$dir" makechrootpkg -c -r "$chroot_dir" -U "$build_user" "${install_args[@]}") || return 1"#
+        }
+        None => {
+            if !plan.repo_dependencies.is_empty() {
+                let dependencies = plan
+                    .repo_dependencies
+                    .iter()
+                    .map(|dependency| q(dependency))
+                    .collect::<Result<Vec<_>>>()?;
+                script.push(format!(
+                    "pacman -S --needed --asdeps {} || exit 1",
+                    dependencies.join(" ")
+                ));
+            }
+            r#"(cd "$dir" && sudo -u "$build_user" env PKGDEST="$dir" makepkg --cleanbuild --force --noconfirm) || return 1"#
+        }
+    };
+
+    script.push(format!(
+        r#"
+build_package() {{
+    local base=$1 source=$2 commit=$3 reason=$4
+    shift 4
+    local dir="$build_root/$base"
+
+    mark building "$base"
+    echo "==> Building $base"
+    extract_source "$source" "$commit" "$dir" || return 1
+    {}
+
+    local files=() file name
+    while read -r file; do
+        for name in "$@"; do
+            [[ $(pkg_name "$file") == "$name" ]] && files+=("$file")
+        done
+    done < <(cd "$dir" && sudo -u "$build_user" env PKGDEST="$dir" makepkg --packagelist)
+    ((${{#files[@]}})) || return 1
+
+    pacman -U $reason "${{files[@]}}" || return 1
+    built_files+=("${{files[@]}}")
+    mark built "$base"
+}}
+"#,
+        build_command
+    ));
+
+    for (step, (source, commit)) in plan.steps.iter().zip(sources) {
+        let packages = step
+            .packages
+            .iter()
+            .map(|package| q(package))
+            .collect::<Result<Vec<_>>>()?;
+
+        script.push(format!(
+            "build_package {} {} {} {} {} || {{ mark failed {}; exit 1; }}",
+            q(&step.package_base)?,
+            q(&source.to_string_lossy())?,
+            q(commit)?,
+            if step.as_dependency { "--asdeps" } else { "''" },
+            packages.join(" "),
+            q(&step.package_base)?,
+        ));
+    }
+
+    script.push(String::new());
+    script.push(r#"echo "==> All AUR packages were built and installed""#.to_string());
+
+    return Ok(script.join("\n") + "\n");
+}
+
+pub fn read_build_statuses(build: &AurBuild) -> Vec<(String, AurBuildStatus)> {
+    let content = fs::read_to_string(&build.status_file).unwrap_or_default();
+    return parse_build_statuses(&content, &build.plan.steps);
+}
+
+pub fn parse_build_statuses(
+    content: &str,
+    steps: &[AurBuildStep],
+) -> Vec<(String, AurBuildStatus)> {
+    let mut statuses: Vec<(String, AurBuildStatus)> = steps
+        .iter()
+        .map(|step| (step.package_base.clone(), AurBuildStatus::Waiting))
+        .collect();
+
+    for line in content.lines() {
+        let Some((marker, base)) = line.split_once(' ') else {
+            continue;
+        };
+        let Some(status) = AurBuildStatus::from_marker(marker) else {
+            continue;
+        };
+
+        if let Some(entry) = statuses.iter_mut().find(|(name, _)| name == base) {
+            entry.1 = status;
+        }
+    }
+
+    return statuses;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::MetadataExt;
+
+    fn git(dir: &Path, args: &[&str]) -> std::process::Output {
+        return Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.org"])
+            .args(args)
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn extracts_sources_from_a_clone_owned_by_another_user() {
+        // Handing a repository to another user needs root.
+        if !Command::new("id")
+            .arg("-u")
+            .output()
+            .is_ok_and(|output| output.stdout == b"0\n")
+        {
+            return;
+        }
+
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("clone");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("PKGBUILD"), "pkgname=example\n").unwrap();
+        assert!(git(&source, &["init", "--quiet"]).status.success());
+        assert!(git(&source, &["add", "PKGBUILD"]).status.success());
+        assert!(
+            git(&source, &["commit", "--quiet", "-m", "first"])
+                .status
+                .success()
+        );
+        let commit = String::from_utf8(git(&source, &["rev-parse", "HEAD"]).stdout).unwrap();
+
+        let chown = Command::new("chown")
+            .args(["-R", "nobody:"])
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(chown.success());
+        assert!(!git(&source, &["archive", commit.trim()]).status.success());
+
+        let dir = temp.path().join("build");
+        let status = Command::new("bash")
+            .arg("-c")
+            .arg(format!(
+                "build_user=nobody\n{}\nextract_source \"$@\"",
+                EXTRACT_SOURCE_FUNCTION
+            ))
+            .arg("bash")
+            .arg(&source)
+            .arg(commit.trim())
+            .arg(&dir)
+            .status()
+            .unwrap();
+
+        assert!(status.success());
+        assert_eq!(
+            fs::read_to_string(dir.join("PKGBUILD")).unwrap(),
+            "pkgname=example\n"
+        );
+        assert_eq!(fs::metadata(&dir).unwrap().uid(), 65534);
+        assert_eq!(fs::metadata(dir.join("PKGBUILD")).unwrap().uid(), 65534);
+    }
+}
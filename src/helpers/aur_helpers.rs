@@ -0,0 +1,110 @@
+use crate::models::app_settings::AppSettings;
+use crate::models::aur_helper_definition::AurHelperDefinition;
+use crate::models::aur_update_format::AurUpdateFormat;
+
+// Presets are read by their update format. The pattern is kept as a starting point for custom
+// helpers: it matches `name 1.0-1 -> 1.1-1`, optionally prefixed with the repository.
+const STANDARD_UPDATE_PATTERN: &str =
+    r"^\s*(?:\S+/)?(?P<name>\S+)\s+(?P<current>\S+)\s+->\s+(?P<new>\S+)";
+
+pub fn aur_helper_presets() -> Vec<AurHelperDefinition> {
+    return vec![
+        preset(
+            "yay",
+            &["-Qua"],
+            &["--color", "never"],
+            &["-S"],
+            AurUpdateFormat::Pacman,
+            true,
+        ),
+        preset(
+            "paru",
+            &["-Qua"],
+            &["--color", "never"],
+            &["-S"],
+            AurUpdateFormat::Pacman,
+            true,
+        ),
+        preset(
+            "trizen",
+            &["-Qua"],
+            &["--nocolors"],
+            &["-S"],
+            AurUpdateFormat::Pacman,
+            true,
+        ),
+        preset(
+            "pikaur",
+            &["-Qua"],
+            &["--color", "never"],
+            &["-S"],
+            AurUpdateFormat::Pikaur,
+            true,
+        ),
+        // pamac and aura have no flag to turn colours off, so their output is only stripped.
+        preset(
+            "pamac",
+            &["checkupdates", "-a"],
+            &[],
+            &["install"],
+            AurUpdateFormat::Pamac,
+            true,
+        ),
+        // aura expects to be started as root and drops privileges for makepkg itself.
+        preset(
+            "aura",
+            &["-Au", "--dryrun"],
+            &[],
+            &["-A"],
+            AurUpdateFormat::Aura,
+            false,
+        ),
+        preset(
+            "pacaur",
+            &["-Qua"],
+            &["--color", "never"],
+            &["-S"],
+            AurUpdateFormat::Pacman,
+            true,
+        ),
+    ];
+}
+
+// Custom definitions from the settings replace presets with the same id.
+pub fn get_aur_helper_definitions(settings: &AppSettings) -> Vec<AurHelperDefinition> {
+    let mut definitions = aur_helper_presets();
+
+    for custom in &settings.custom_aur_helpers {
+        definitions.retain(|definition| definition.id != custom.id);
+        definitions.push(custom.clone());
+    }
+
+    return definitions;
+}
+
+pub fn is_custom_aur_helper(settings: &AppSettings, id: &str) -> bool {
+    return settings
+        .custom_aur_helpers
+        .iter()
+        .any(|custom| custom.id == id);
+}
+
+fn preset(
+    binary: &str,
+    check_args: &[&str],
+    no_color_args: &[&str],
+    install_args: &[&str],
+    update_format: AurUpdateFormat,
+    run_as_user: bool,
+) -> AurHelperDefinition {
+    return AurHelperDefinition {
+        id: binary.to_string(),
+        binary: binary.to_string(),
+        check_args: check_args.iter().map(|arg| arg.to_string()).collect(),
+        no_color_args: no_color_args.iter().map(|arg| arg.to_string()).collect(),
+        install_args: install_args.iter().map(|arg| arg.to_string()).collect(),
+        update_pattern: STANDARD_UPDATE_PATTERN.to_string(),
+        update_format,
+        run_as_user,
+    };
+}
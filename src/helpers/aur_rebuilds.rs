@@ -0,0 +1,389 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+use crate::constants::AUR_NAME;
+use crate::helpers::aur::is_command_available;
+use crate::helpers::aur_rpc::{get_aur_rpc_url, list_foreign_packages, query_aur_info};
+use crate::helpers::pacman_info::{query_package_info, split_list_field};
+use crate::helpers::settings::load_settings;
+use crate::models::package_update::PackageUpdate;
+
+const ELF_DIRS: [&str; 5] = [
+    "/usr/bin/",
+    "/usr/lib/",
+    "/usr/lib32/",
+    "/usr/libexec/",
+    "/opt/",
+];
+
+const READELF_BATCH_SIZE: usize = 200;
+
+// Foreign packages keep working only as long as the libraries and interpreters they were built
+// against stay in place, so this looks for the ones an official update is about to break.
+pub fn find_aur_rebuilds(updates: &[PackageUpdate]) -> Result<Vec<PackageUpdate>> {
+    let official: Vec<&PackageUpdate> = updates
+        .iter()
+        .filter(|update| update.repository != AUR_NAME)
+        .collect();
+    if official.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let old_python_dir = official
+        .iter()
+        .find(|update| update.name == "python")
+        .and_then(|update| {
+            let current = python_minor(&update.current_version)?;
+            (Some(current.as_str()) != python_minor(&update.new_version).as_deref())
+                .then(|| format!("/usr/lib/python{}/", current))
+        });
+
+    let mut installed_args = vec!["-Qi"];
+    installed_args.extend(official.iter().map(|update| update.name.as_str()));
+    let mut available_args = vec!["-Si"];
+    available_args.extend(official.iter().map(|update| update.name.as_str()));
+
+    let old_sonames = find_changed_sonames(
+        &collect_provides(&query_package_info(&installed_args)?),
+        &collect_provides(&query_package_info(&available_args)?),
+    );
+
+    if old_python_dir.is_none() && old_sonames.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let candidates: Vec<(String, String)> = list_foreign_packages()?
+        .into_iter()
+        .filter(|(name, _)| !updates.iter().any(|update| update.name == *name))
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let candidate_names: Vec<&str> = candidates.iter().map(|(name, _)| name.as_str()).collect();
+    let files = list_package_files(&candidate_names)?;
+    let can_read_elf = is_command_available("readelf");
+
+    // Each rebuild remembers the updates that cause it, so it can be dropped along with them.
+    let mut reasons: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+    for (name, paths) in &files {
+        if let Some(python_dir) = &old_python_dir {
+            if paths
+                .iter()
+                .any(|path| path.starts_with(python_dir.as_str()))
+            {
+                reasons.insert(
+                    name.clone(),
+                    (
+                        format!("Installs files under {}", python_dir.trim_end_matches('/')),
+                        vec!["python".to_string()],
+                    ),
+                );
+                continue;
+            }
+        }
+
+        if old_sonames.is_empty() || !can_read_elf {
+            continue;
+        }
+
+        let needed = find_needed_libraries(paths);
+        let broken: Vec<(&String, &String)> = old_sonames
+            .iter()
+            .filter(|(soname, _)| needed.contains(*soname))
+            .collect();
+        if !broken.is_empty() {
+            let mut triggers: Vec<String> = broken
+                .iter()
+                .map(|(_, package)| (*package).clone())
+                .collect();
+            triggers.sort();
+            triggers.dedup();
+
+            reasons.insert(
+                name.clone(),
+                (
+                    format!(
+                        "Links against {}",
+                        broken
+                            .iter()
+                            .map(|(soname, _)| soname.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    triggers,
+                ),
+            );
+        }
+    }
+
+    if reasons.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let reason_names: Vec<String> = reasons.keys().cloned().collect();
+    let aur_packages = query_aur_info(&get_aur_rpc_url(&load_settings()), &reason_names)?;
+
+    let mut rebuilds = Vec::new();
+    for (name, version) in candidates {
+        let Some((reason, triggers)) = reasons.remove(&name) else {
+            continue;
+        };
+        let Some(package) = aur_packages.iter().find(|package| package.name == name) else {
+            eprintln!("{} needs a rebuild but is not in the AUR: {}", name, reason);
+            continue;
+        };
+
+        rebuilds.push(PackageUpdate {
+            repository: AUR_NAME.to_string(),
+            selected: true,
+            description: package
+                .description
+                .clone()
+                .unwrap_or_else(|| format!("AUR package: {}", name)),
+            name,
+            new_version: version.clone(),
+            current_version: version,
+            size: 0,
+            aur_info: Some(package.clone()),
+            pkgbuild_findings: Vec::new(),
+            rebuild_reason: Some(reason),
+            rebuild_for: triggers,
+        });
+    }
+
+    return Ok(rebuilds);
+}
+
+pub fn python_minor(version: &str) -> Option<String> {
+    let version = version
+        .split_once(':')
+        .map(|(_, version)| version)
+        .unwrap_or(version);
+    let mut parts = version.split(['.', '-']);
+    let major = parts.next()?;
+    let minor = parts.next()?;
+
+    return Some(format!("{}.{}", major, minor));
+}
+
+// The first repository wins when `pacman -Si` lists a package twice.
+pub fn collect_provides(info: &[HashMap<String, String>]) -> BTreeMap<String, Vec<String>> {
+    let mut provides: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for package in info {
+        let Some(name) = package.get("Name") else {
+            continue;
+        };
+        if !provides.contains_key(name) {
+            provides.insert(name.clone(), split_list_field(package.get("Provides")));
+        }
+    }
+
+    return provides;
+}
+
+// makepkg adds a `libfoo.so=MAJOR-64` provide for every shared library, so a provide whose major
+// version changes or disappears means `libfoo.so.MAJOR` is about to be removed. Each soname maps
+// to the package whose update removes it.
+pub fn find_changed_sonames(
+    installed: &BTreeMap<String, Vec<String>>,
+    available: &BTreeMap<String, Vec<String>>,
+) -> BTreeMap<String, String> {
+    let mut changed = BTreeMap::new();
+
+    for (package, provides) in installed {
+        let new_sonames: Vec<(&str, &str)> = available
+            .get(package)
+            .map(|provides| provides.iter().filter_map(|p| parse_soname(p)).collect())
+            .unwrap_or_default();
+
+        for (library, major) in provides.iter().filter_map(|p| parse_soname(p)) {
+            let still_provided = new_sonames
+                .iter()
+                .any(|(new_library, new_major)| *new_library == library && *new_major == major);
+            if !still_provided {
+                changed.insert(format!("{}.{}", library, major), package.clone());
+            }
+        }
+    }
+
+    return changed;
+}
+
+fn parse_soname(provide: &str) -> Option<(&str, &str)> {
+    let (library, version) = provide.split_once('=')?;
+    if !library.ends_with(".so") {
+        return None;
+    }
+
+    return Some((library, version.split('-').next()?));
+}
+
+pub fn parse_needed_libraries(readelf_output: &str) -> BTreeSet<String> {
+    let needed_re = Regex::new(r"\(NEEDED\)\s+Shared library: \[([^\]]+)\]").unwrap();
+
+    return needed_re
+        .captures_iter(readelf_output)
+        .map(|caps| caps[1].to_string())
+        .collect();
+}
+
+fn find_needed_libraries(paths: &[String]) -> BTreeSet<String> {
+    let elf_files: Vec<&String> = paths
+        .iter()
+        .filter(|path| ELF_DIRS.iter().any(|dir| path.starts_with(dir)))
+        .filter(|path| is_elf_file(Path::new(path)))
+        .collect();
+
+    let mut needed = BTreeSet::new();
+    for batch in elf_files.chunks(READELF_BATCH_SIZE) {
+        match Command::new("readelf")
+            .args(["-d", "-W"])
+            .args(batch)
+            .output()
+        {
+            Ok(output) => needed.extend(parse_needed_libraries(&String::from_utf8_lossy(
+                &output.stdout,
+            ))),
+            Err(e) => eprintln!("Failed to run readelf: {}", e),
+        }
+    }
+
+    return needed;
+}
+
+fn is_elf_file(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+
+    let mut magic = [0u8; 4];
+    return File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok()
+        && magic == *b"\x7fELF";
+}
+
+fn list_package_files(names: &[&str]) -> Result<BTreeMap<String, Vec<String>>> {
+    let output = Command::new("pacman")
+        .arg("-Ql")
+        .args(names)
+        .output()
+        .context("Failed to list package files")?;
+
+    let mut files: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((name, path)) = line.split_once(' ') else {
+            continue;
+        };
+        if !path.ends_with('/') {
+            files
+                .entry(name.to_string())
+                .or_default()
+                .push(path.to_string());
+        }
+    }
+
+    return Ok(files);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::pacman_info::parse_package_info;
+
+    fn fixture_provides(output: &str) -> BTreeMap<String, Vec<String>> {
+        return collect_provides(&parse_package_info(output));
+    }
+
+    #[test]
+    fn reads_the_python_minor_version() {
+        assert_eq!(python_minor("3.12.4-1").as_deref(), Some("3.12"));
+        assert_eq!(python_minor("3.13.0rc1-1").as_deref(), Some("3.13"));
+        assert_eq!(python_minor("1:3.11.9-2").as_deref(), Some("3.11"));
+        assert_eq!(python_minor("3"), None);
+
+        assert_eq!(python_minor("3.12.4-1"), python_minor("3.12.5-1"));
+        assert_ne!(python_minor("3.12.4-1"), python_minor("3.13.0-1"));
+    }
+
+    #[test]
+    fn collects_provides_from_the_first_repository() {
+        let available = fixture_provides(include_str!(
+            "../../tests/fixtures/aur_rebuilds/available.Si"
+        ));
+
+        assert_eq!(available.len(), 3);
+        assert_eq!(available["icu"].len(), 6);
+        assert!(available["icu"].contains(&"libicuuc.so=75-64".to_string()));
+        assert_eq!(
+            available["python"],
+            vec!["python3", "python-externally-managed"]
+        );
+    }
+
+    #[test]
+    fn finds_sonames_removed_by_an_update() {
+        let installed = fixture_provides(include_str!(
+            "../../tests/fixtures/aur_rebuilds/installed.Qi"
+        ));
+        let available = fixture_provides(include_str!(
+            "../../tests/fixtures/aur_rebuilds/available.Si"
+        ));
+
+        let changed = find_changed_sonames(&installed, &available);
+
+        let sonames: Vec<&str> = changed.keys().map(|soname| soname.as_str()).collect();
+        assert_eq!(
+            sonames,
+            vec![
+                "libicudata.so.74",
+                "libicui18n.so.74",
+                "libicuio.so.74",
+                "libicutest.so.74",
+                "libicutu.so.74",
+                "libicuuc.so.74",
+            ]
+        );
+        assert!(changed.values().all(|package| package == "icu"));
+    }
+
+    #[test]
+    fn treats_a_dropped_library_as_removed() {
+        let installed = BTreeMap::from([(
+            "libfoo".to_string(),
+            vec!["libfoo.so=2-64".to_string(), "libbar.so=1-64".to_string()],
+        )]);
+        let available =
+            BTreeMap::from([("libfoo".to_string(), vec!["libfoo.so=2-64".to_string()])]);
+
+        assert_eq!(
+            find_changed_sonames(&installed, &available),
+            BTreeMap::from([("libbar.so.1".to_string(), "libfoo".to_string())])
+        );
+    }
+
+    #[test]
+    fn parses_needed_libraries_from_readelf() {
+        let output = "\
+File: /usr/bin/foo
+
+Dynamic section at offset 0x2d80 contains 28 entries:
+  Tag        Type                         Name/Value
+ 0x0000000000000001 (NEEDED)             Shared library: [libicuuc.so.74]
+ 0x0000000000000001 (NEEDED)             Shared library: [libc.so.6]
+ 0x000000000000000e (SONAME)             Library soname: [libfoo.so.1]
+";
+
+        assert_eq!(
+            parse_needed_libraries(output),
+            BTreeSet::from(["libc.so.6".to_string(), "libicuuc.so.74".to_string()])
+        );
+    }
+}
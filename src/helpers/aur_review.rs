@@ -0,0 +1,194 @@
+use anyhow::{Context, Result, anyhow};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+
+use crate::constants::AUR_GIT_URL;
+use crate::helpers::cache_dir::get_cache_dir;
+use crate::helpers::pkgbuild_scanner::scan_pkgbuild_update;
+use crate::helpers::state_dir::get_state_dir;
+use crate::models::aur_review::AurReview;
+use crate::models::pkgbuild_finding::PkgbuildFinding;
+
+// How many AUR repositories are cloned and scanned at the same time.
+const SCAN_JOBS: usize = 4;
+
+// The hash of git's empty tree, so a first review diffs every file against nothing.
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+pub fn get_aur_clone_dir(package_base: &str) -> Result<PathBuf> {
+    return Ok(get_cache_dir("aur")?.join(package_base));
+}
+
+pub fn sync_aur_clone(package_base: &str) -> Result<PathBuf> {
+    let dir = get_aur_clone_dir(package_base)?;
+
+    if dir.join(".git").exists() {
+        run_git(&dir, &["fetch", "--quiet", "origin"])?;
+        // makepkg rewrites pkgver in the PKGBUILD of VCS packages, so local edits are dropped.
+        run_git(&dir, &["reset", "--hard", "--quiet", "origin/HEAD"])?;
+    } else {
+        let url = format!("{}/{}.git", AUR_GIT_URL, package_base);
+        let output = Command::new("git")
+            .args(["clone", "--quiet", &url])
+            .arg(&dir)
+            .output()
+            .context("Failed to run git clone")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Could not clone {}: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+    }
+
+    return Ok(dir);
+}
+
+pub fn prepare_aur_review(package: &str, package_base: &str) -> Result<AurReview> {
+    let dir = sync_aur_clone(package_base)?;
+    let commit = run_git(&dir, &["rev-parse", "HEAD"])?.trim().to_string();
+    let reviewed_commit = find_reviewed_commit(&dir, package_base);
+
+    let diff = if reviewed_commit.as_deref() == Some(commit.as_str()) {
+        String::new()
+    } else {
+        run_git(
+            &dir,
+            &[
+                "diff",
+                "--no-color",
+                reviewed_commit.as_deref().unwrap_or(EMPTY_TREE),
+                &commit,
+            ],
+        )?
+    };
+
+    let findings = scan_clone(&dir, reviewed_commit.as_deref())?;
+
+    return Ok(AurReview {
+        package: package.to_string(),
+        package_base: package_base.to_string(),
+        commit,
+        reviewed_commit,
+        diff,
+        findings,
+    });
+}
+
+pub fn scan_aur_build_scripts(package_base: &str) -> Result<Vec<PkgbuildFinding>> {
+    let dir = sync_aur_clone(package_base)?;
+    let reviewed_commit = find_reviewed_commit(&dir, package_base);
+
+    return scan_clone(&dir, reviewed_commit.as_deref());
+}
+
+// Scans several packages at once; a package that cannot be fetched is left out of the result.
+pub fn scan_all_aur_build_scripts(
+    package_bases: &[String],
+) -> BTreeMap<String, Vec<PkgbuildFinding>> {
+    let queue = Mutex::new(package_bases.iter().collect::<BTreeSet<_>>().into_iter());
+    let results = Mutex::new(BTreeMap::new());
+
+    thread::scope(|scope| {
+        for _ in 0..SCAN_JOBS {
+            scope.spawn(|| {
+                loop {
+                    let Some(package_base) = queue.lock().unwrap().next() else {
+                        return;
+                    };
+
+                    match scan_aur_build_scripts(package_base) {
+                        Ok(findings) => {
+                            results
+                                .lock()
+                                .unwrap()
+                                .insert(package_base.clone(), findings);
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to scan the PKGBUILD of {}: {:#}", package_base, e)
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    return results.into_inner().unwrap();
+}
+
+fn scan_clone(dir: &Path, reviewed_commit: Option<&str>) -> Result<Vec<PkgbuildFinding>> {
+    let pkgbuild = run_git(dir, &["show", "HEAD:PKGBUILD"])?;
+    let previous = reviewed_commit
+        .and_then(|commit| run_git(dir, &["show", &format!("{}:PKGBUILD", commit)]).ok());
+
+    return Ok(scan_pkgbuild_update(previous.as_deref(), &pkgbuild));
+}
+
+// A reviewed commit that vanished from the history (a force push) counts as never reviewed.
+fn find_reviewed_commit(dir: &Path, package_base: &str) -> Option<String> {
+    return load_reviewed_commits()
+        .remove(package_base)
+        .filter(|reviewed| {
+            run_git(
+                dir,
+                &["cat-file", "-e", &format!("{}^{{commit}}", reviewed)],
+            )
+            .is_ok()
+        });
+}
+
+pub fn load_reviewed_commits() -> BTreeMap<String, String> {
+    let Ok(path) = reviewed_commits_path() else {
+        return BTreeMap::new();
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+
+    return match serde_json::from_str(&content) {
+        Ok(reviewed) => reviewed,
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            BTreeMap::new()
+        }
+    };
+}
+
+pub fn mark_aur_reviewed(package_base: &str, commit: &str) -> Result<()> {
+    let mut reviewed = load_reviewed_commits();
+    reviewed.insert(package_base.to_string(), commit.to_string());
+
+    let content =
+        serde_json::to_string_pretty(&reviewed).context("Failed to serialize reviewed commits")?;
+    fs::write(reviewed_commits_path()?, content).context("Failed to write reviewed commits")?;
+
+    return Ok(());
+}
+
+fn reviewed_commits_path() -> Result<PathBuf> {
+    return Ok(get_state_dir("aur")?.join("reviewed.json"));
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+}
@@ -0,0 +1,218 @@
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::process::Command;
+
+use crate::constants::AUR_RPC_URL;
+use crate::models::app_settings::AppSettings;
+use crate::models::aur_package::AurPackage;
+
+// Keeps each request well below the AUR's limits on arguments and request size.
+const INFO_BATCH_SIZE: usize = 100;
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    results: Vec<AurPackage>,
+}
+
+pub fn get_aur_rpc_url(settings: &AppSettings) -> String {
+    return settings
+        .aur_rpc_url
+        .as_deref()
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .unwrap_or(AUR_RPC_URL)
+        .to_string();
+}
+
+pub fn list_foreign_packages() -> Result<Vec<(String, String)>> {
+    let output = Command::new("pacman")
+        .arg("-Qm")
+        .output()
+        .context("Failed to list foreign packages")?;
+
+    // pacman exits with 1 and prints nothing when there are no foreign packages at all.
+    let no_foreign_packages = output.stdout.is_empty() && output.stderr.is_empty();
+    if !output.status.success() && !no_foreign_packages {
+        return Err(anyhow!(
+            "pacman -Qm failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    return Ok(parse_foreign_packages(&String::from_utf8_lossy(
+        &output.stdout,
+    )));
+}
+
+pub fn parse_foreign_packages(output: &str) -> Vec<(String, String)> {
+    return output
+        .lines()
+        .filter_map(|line| {
+            let (name, version) = line.trim().split_once(' ')?;
+            Some((name.to_string(), version.trim().to_string()))
+        })
+        .collect();
+}
+
+pub fn query_aur_info(base_url: &str, names: &[String]) -> Result<Vec<AurPackage>> {
+    let mut packages = Vec::new();
+    let url = format!("{}/info", base_url.trim_end_matches('/'));
+
+    for batch in names.chunks(INFO_BATCH_SIZE) {
+        let mut form = Vec::new();
+        for name in batch {
+            form.push("--data-urlencode".to_string());
+            form.push(format!("arg[]={}", name));
+        }
+
+        packages.extend(fetch_rpc(&url, &form)?);
+    }
+
+    return Ok(packages);
+}
+
+pub fn search_aur_providers(base_url: &str, name: &str) -> Result<Vec<AurPackage>> {
+    let url = format!("{}/search/{}", base_url.trim_end_matches('/'), name);
+    let form = [
+        "--get".to_string(),
+        "--data-urlencode".to_string(),
+        "by=provides".to_string(),
+    ];
+
+    return fetch_rpc(&url, &form);
+}
+
+fn fetch_rpc(url: &str, form: &[String]) -> Result<Vec<AurPackage>> {
+    let output = Command::new("curl")
+        .args(["-fsSL", "--max-time", "20", url])
+        .args(form)
+        .output()
+        .context("Failed to run curl")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Could not query {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    return parse_aur_info_response(&String::from_utf8_lossy(&output.stdout));
+}
+
+pub fn parse_aur_info_response(body: &str) -> Result<Vec<AurPackage>> {
+    let response: RpcResponse =
+        serde_json::from_str(body).context("Failed to parse AUR RPC response")?;
+
+    if response.kind == "error" {
+        return Err(anyhow!(
+            "AUR RPC error: {}",
+            response
+                .error
+                .unwrap_or_else(|| "unknown error".to_string())
+        ));
+    }
+
+    return Ok(response.results);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // Answers a single request with `body` and hands back the form data it received.
+    fn serve_once(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/rpc/v5", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+
+            let mut form = vec![0; content_length];
+            reader.read_exact(&mut form).unwrap();
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+
+            return String::from_utf8(form).unwrap();
+        });
+
+        return (base_url, server);
+    }
+
+    #[test]
+    fn queries_the_info_endpoint() {
+        let (base_url, server) = serve_once(include_str!("../../tests/fixtures/aur_rpc/info.json"));
+
+        let packages = query_aur_info(
+            &base_url,
+            &["google-chrome".to_string(), "yay-bin".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(server.join().unwrap(), "arg[]=google-chrome&arg[]=yay-bin");
+
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "google-chrome");
+        assert_eq!(packages[0].version, "126.0.6478.55-1");
+        assert_eq!(packages[0].maintainer.as_deref(), Some("luzifer"));
+        assert_eq!(packages[0].depends, ["gtk3", "nss", "alsa-lib"]);
+        assert_eq!(packages[0].check_depends, ["python-pytest"]);
+        assert_eq!(packages[0].out_of_date, None);
+
+        assert_eq!(packages[1].package_base, "yay-bin");
+        assert_eq!(packages[1].maintainer, None);
+        assert_eq!(packages[1].out_of_date, Some(1719000000));
+        assert_eq!(packages[1].provides, ["yay"]);
+    }
+
+    #[test]
+    fn reports_rpc_errors() {
+        let (base_url, server) =
+            serve_once(include_str!("../../tests/fixtures/aur_rpc/error.json"));
+
+        let error = query_aur_info(&base_url, &["a".to_string()]).unwrap_err();
+        server.join().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "AUR RPC error: Too many package results."
+        );
+    }
+
+    #[test]
+    fn uses_the_configured_base_url() {
+        let mut settings = AppSettings::default();
+        assert_eq!(get_aur_rpc_url(&settings), AUR_RPC_URL);
+
+        settings.aur_rpc_url = Some(" http://localhost:8080/rpc/v5/ ".to_string());
+        assert_eq!(get_aur_rpc_url(&settings), "http://localhost:8080/rpc/v5");
+    }
+}
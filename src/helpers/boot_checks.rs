@@ -0,0 +1,429 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use glib::format_size;
+use regex::Regex;
+
+use crate::helpers::disk_space::get_available_space;
+
+const INITRAMFS_PACKAGES: &[&str] = &[
+    "mkinitcpio",
+    "dracut",
+    "booster",
+    "linux-firmware",
+    "intel-ucode",
+    "amd-ucode",
+];
+
+// Used when a kernel is installed for the first time and there are no old images to measure.
+const DEFAULT_KERNEL_IMAGE_SIZE: u64 = 100 * 1024 * 1024;
+
+// New images replace the old ones, so only their growth needs room. They are assumed to grow by
+// up to a tenth of their current size.
+const IMAGE_GROWTH_DIVISOR: u64 = 10;
+
+// Kernels from the official repositories, recognised even before they are first installed.
+const KNOWN_KERNEL_PACKAGES: &[&str] = &[
+    "linux",
+    "linux-lts",
+    "linux-zen",
+    "linux-hardened",
+    "linux-rt",
+    "linux-rt-lts",
+];
+
+pub fn is_kernel_package(root: &Path, package: &str) -> bool {
+    return KNOWN_KERNEL_PACKAGES.contains(&package)
+        || find_installed_kernel_packages(root)
+            .iter()
+            .any(|kernel| kernel == package);
+}
+
+pub fn is_boot_related_package(root: &Path, package: &str) -> bool {
+    return is_kernel_package(root, package)
+        || INITRAMFS_PACKAGES
+            .iter()
+            .any(|name| package == *name || package.starts_with(&format!("{}-", name)));
+}
+
+pub fn check_boot_partition(root: &Path, packages: &[String]) -> Vec<String> {
+    return check_boot_partition_with(root, packages, get_available_space);
+}
+
+fn check_boot_partition_with(
+    root: &Path,
+    packages: &[String],
+    available_space: impl Fn(&Path) -> Result<u64>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    let boot_dir = root.join("boot");
+
+    if let Some(problem) = check_boot_mounted(root) {
+        problems.push(problem);
+        return problems;
+    }
+
+    let kernels = find_affected_kernels(root, packages);
+    let required = estimate_boot_space(root, packages);
+
+    match available_space(&boot_dir) {
+        Ok(available) if available < required => problems.push(format!(
+            "/boot has {} free, but about {} more is needed for the new kernel and initramfs images.",
+            format_size(available),
+            format_size(required)
+        )),
+        Ok(_) => {}
+        Err(e) => problems.push(format!("Could not determine free space on /boot: {}", e)),
+    }
+
+    problems.extend(check_bootloader_entries(root, &kernels));
+
+    return problems;
+}
+
+// The net growth of /boot: new images minus the ones they replace.
+pub fn estimate_boot_space(root: &Path, packages: &[String]) -> u64 {
+    let boot_dir = root.join("boot");
+
+    return find_affected_kernels(root, packages)
+        .iter()
+        .map(|kernel| estimate_image_growth(&boot_dir, kernel))
+        .sum();
+}
+
+fn find_affected_kernels(root: &Path, packages: &[String]) -> Vec<String> {
+    let packages: Vec<&String> = packages
+        .iter()
+        .filter(|p| is_boot_related_package(root, p))
+        .collect();
+
+    let mut kernels: Vec<String> = packages
+        .iter()
+        .filter(|p| is_kernel_package(root, p))
+        .map(|p| p.to_string())
+        .collect();
+
+    // Rebuilding the initramfs touches every installed kernel's images.
+    if packages.iter().any(|p| !is_kernel_package(root, p)) {
+        kernels.extend(find_installed_kernels(&root.join("boot")));
+    }
+    kernels.sort();
+    kernels.dedup();
+
+    return kernels;
+}
+
+fn check_boot_mounted(root: &Path) -> Option<String> {
+    let fstab = fs::read_to_string(root.join("etc/fstab")).unwrap_or_default();
+    let mounts = fs::read_to_string(root.join("proc/mounts")).unwrap_or_default();
+
+    for mount_point in ["/boot", "/efi"] {
+        if mount_point_listed(&fstab, mount_point) && !mount_point_listed(&mounts, mount_point) {
+            return Some(format!(
+                "{} is listed in /etc/fstab but is not mounted. Mount it before updating the kernel, otherwise the new kernel is written to the wrong place and the system will not boot.",
+                mount_point
+            ));
+        }
+    }
+
+    return None;
+}
+
+fn mount_point_listed(table: &str, mount_point: &str) -> bool {
+    return table.lines().any(|line| {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return false;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let options = fields.get(3).copied().unwrap_or("");
+
+        // Automount entries only show up in the mount table once they are accessed.
+        fields.get(1) == Some(&mount_point) && !options.contains("x-systemd.automount")
+    });
+}
+
+// Every kernel package ships its image as /usr/lib/modules/<version>/vmlinuz, next to a
+// pkgbase file that names the package. Headers, tools and firmware packages don't.
+fn find_installed_kernel_packages(root: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(root.join("usr/lib/modules")) else {
+        return Vec::new();
+    };
+
+    return entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| dir.join("vmlinuz").is_file())
+        .filter_map(|dir| fs::read_to_string(dir.join("pkgbase")).ok())
+        .map(|pkgbase| pkgbase.trim().to_string())
+        .collect();
+}
+
+fn find_installed_kernels(boot_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(boot_dir) else {
+        return Vec::new();
+    };
+
+    return entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            file_name.strip_prefix("vmlinuz-").map(|k| k.to_string())
+        })
+        .collect();
+}
+
+fn estimate_image_growth(boot_dir: &Path, kernel: &str) -> u64 {
+    let images = [
+        format!("vmlinuz-{}", kernel),
+        format!("initramfs-{}.img", kernel),
+        format!("initramfs-{}-fallback.img", kernel),
+    ];
+
+    let size: u64 = images
+        .iter()
+        .filter_map(|image| fs::metadata(boot_dir.join(image)).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    if size == 0 {
+        return DEFAULT_KERNEL_IMAGE_SIZE;
+    }
+
+    return size / IMAGE_GROWTH_DIVISOR;
+}
+
+fn check_bootloader_entries(root: &Path, kernels: &[String]) -> Vec<String> {
+    // Unified kernel images carry their own entries, there is nothing to cross-check.
+    let uki_dirs = ["boot/EFI/Linux", "efi/EFI/Linux"];
+    if uki_dirs
+        .iter()
+        .any(|dir| fs::read_dir(root.join(dir)).is_ok_and(|mut entries| entries.next().is_some()))
+    {
+        return Vec::new();
+    }
+
+    let configs = read_bootloader_configs(root);
+    if configs.is_empty() {
+        return Vec::new();
+    }
+
+    let boot_dir = root.join("boot");
+
+    return kernels
+        .iter()
+        .filter(|kernel| boot_dir.join(format!("vmlinuz-{}", kernel)).exists())
+        .filter(|kernel| {
+            let image_re = Regex::new(&format!(
+                r"vmlinuz-{}([^a-z0-9-]|$)",
+                regex::escape(kernel)
+            ))
+            .unwrap();
+            !configs.iter().any(|config| image_re.is_match(config))
+        })
+        .map(|kernel| {
+            format!(
+                "No bootloader entry references vmlinuz-{}. Update your bootloader configuration before installing the new kernel.",
+                kernel
+            )
+        })
+        .collect();
+}
+
+fn read_bootloader_configs(root: &Path) -> Vec<String> {
+    let mut paths: Vec<PathBuf> = [
+        "boot/grub/grub.cfg",
+        "boot/limine.conf",
+        "boot/limine.cfg",
+        "boot/syslinux/syslinux.cfg",
+    ]
+    .iter()
+    .map(|path| root.join(path))
+    .collect();
+
+    for entries_dir in ["boot/loader/entries", "efi/loader/entries"] {
+        if let Ok(entries) = fs::read_dir(root.join(entries_dir)) {
+            paths.extend(entries.flatten().map(|entry| entry.path()));
+        }
+    }
+
+    return paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn fake_root() -> TempDir {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            root.path(),
+            "etc/fstab",
+            "UUID=1234 / ext4 rw,relatime 0 1\n",
+        );
+        write(
+            root.path(),
+            "proc/mounts",
+            "/dev/vda1 / ext4 rw,relatime 0 0\n",
+        );
+        write(root.path(), "boot/vmlinuz-linux", "kernel");
+        write(
+            root.path(),
+            "usr/lib/modules/6.9.1-arch1-1/vmlinuz",
+            "kernel",
+        );
+        write(
+            root.path(),
+            "usr/lib/modules/6.9.1-arch1-1/pkgbase",
+            "linux\n",
+        );
+        return root;
+    }
+
+    fn packages(names: &[&str]) -> Vec<String> {
+        return names.iter().map(|name| name.to_string()).collect();
+    }
+
+    #[test]
+    fn recognises_kernels_by_their_module_directory() {
+        let root = fake_root();
+        write(
+            root.path(),
+            "usr/lib/modules/6.9.2-custom/vmlinuz",
+            "kernel",
+        );
+        write(
+            root.path(),
+            "usr/lib/modules/6.9.2-custom/pkgbase",
+            "linux-custom\n",
+        );
+        write(
+            root.path(),
+            "usr/lib/modules/extramodules-6.9-arch/pkgbase",
+            "nvidia\n",
+        );
+
+        assert!(is_kernel_package(root.path(), "linux"));
+        assert!(is_kernel_package(root.path(), "linux-lts"));
+        assert!(is_kernel_package(root.path(), "linux-custom"));
+        assert!(!is_kernel_package(root.path(), "nvidia"));
+        assert!(!is_kernel_package(root.path(), "linux-tools"));
+        assert!(!is_kernel_package(root.path(), "linux-api-headers"));
+        assert!(!is_kernel_package(root.path(), "linux-atm"));
+        assert!(!is_kernel_package(root.path(), "linux-headers"));
+        assert!(!is_kernel_package(root.path(), "linux-firmware"));
+        assert!(is_boot_related_package(root.path(), "linux-firmware"));
+    }
+
+    #[test]
+    fn reports_an_unmounted_boot_partition() {
+        let root = fake_root();
+        write(
+            root.path(),
+            "etc/fstab",
+            "UUID=1234 / ext4 rw,relatime 0 1\nUUID=ABCD /boot vfat rw 0 2\n",
+        );
+
+        let problems = check_boot_partition(root.path(), &packages(&["linux"]));
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("/boot is listed in /etc/fstab but is not mounted"));
+    }
+
+    #[test]
+    fn ignores_automounted_boot_partitions() {
+        let root = fake_root();
+        write(
+            root.path(),
+            "etc/fstab",
+            "UUID=ABCD /boot vfat rw,noauto,x-systemd.automount 0 2\n",
+        );
+
+        assert!(check_boot_partition(root.path(), &packages(&["linux"])).is_empty());
+    }
+
+    #[test]
+    fn estimates_the_growth_of_replaced_images() {
+        let root = fake_root();
+        File::create(root.path().join("boot/vmlinuz-linux"))
+            .unwrap()
+            .set_len(15_000)
+            .unwrap();
+        File::create(root.path().join("boot/initramfs-linux.img"))
+            .unwrap()
+            .set_len(25_000)
+            .unwrap();
+
+        assert_eq!(
+            estimate_boot_space(root.path(), &packages(&["linux"])),
+            4_000
+        );
+        assert_eq!(
+            estimate_boot_space(root.path(), &packages(&["linux", "linux-lts"])),
+            4_000 + DEFAULT_KERNEL_IMAGE_SIZE
+        );
+        assert_eq!(estimate_boot_space(root.path(), &packages(&["vim"])), 0);
+    }
+
+    #[test]
+    fn reports_a_full_boot_partition() {
+        let root = fake_root();
+        let new_kernel = packages(&["linux-lts"]);
+
+        let problems = check_boot_partition_with(root.path(), &new_kernel, |_| Ok(1024 * 1024));
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("/boot has 1.0"));
+        assert!(problems[0].contains("more is needed for the new kernel and initramfs images"));
+
+        assert!(
+            check_boot_partition_with(root.path(), &new_kernel, |_| Ok(DEFAULT_KERNEL_IMAGE_SIZE))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn reports_kernels_missing_from_the_bootloader_entries() {
+        let root = fake_root();
+        write(root.path(), "boot/vmlinuz-linux-lts", "kernel");
+        write(
+            root.path(),
+            "boot/loader/entries/arch.conf",
+            "title Arch Linux\nlinux /vmlinuz-linux\ninitrd /initramfs-linux.img\n",
+        );
+
+        let problems = check_boot_partition(root.path(), &packages(&["linux", "linux-lts"]));
+
+        assert_eq!(
+            problems,
+            [
+                "No bootloader entry references vmlinuz-linux-lts. Update your bootloader configuration before installing the new kernel."
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_kernels_with_bootloader_entries() {
+        let root = fake_root();
+        write(
+            root.path(),
+            "boot/grub/grub.cfg",
+            "menuentry 'Arch Linux' {\n\tlinux /vmlinuz-linux root=UUID=1234 rw\n}\n",
+        );
+
+        assert!(check_boot_partition(root.path(), &packages(&["linux", "mkinitcpio"])).is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::{Context, Result, anyhow};
+use chrono::Local;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::constants::BTRFS_SNAPSHOT_DIR;
+use crate::helpers::aur::is_command_available;
+use crate::helpers::snapshot_provider::{
+    MIN_SNAPSHOT_FREE_SPACE, SnapshotProvider, ensure_free_space,
+};
+use crate::helpers::timeshift::parse_snapshot_timestamp;
+use crate::models::system_snapshot::SystemSnapshot;
+
+pub struct BtrfsProvider;
+
+impl SnapshotProvider for BtrfsProvider {
+    fn name(&self) -> &'static str {
+        return "Btrfs";
+    }
+
+    fn is_available(&self) -> bool {
+        if !is_command_available("btrfs") {
+            return false;
+        }
+
+        return Command::new("findmnt")
+            .args(["-n", "-o", "FSTYPE", "/"])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "btrfs")
+            .unwrap_or(false);
+    }
+
+    fn preflight(&self) -> Result<()> {
+        return ensure_free_space(Path::new("/"), MIN_SNAPSHOT_FREE_SPACE);
+    }
+
+    fn create(
+        &self,
+        description: &str,
+        on_output: &mut dyn FnMut(&str),
+        cancelled: &AtomicBool,
+    ) -> Result<SystemSnapshot> {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(anyhow!("Snapshot cancelled"));
+        }
+
+        fs::create_dir_all(BTRFS_SNAPSHOT_DIR).context("Failed to create snapshot directory")?;
+
+        let created_at = Local::now();
+        let name = created_at.format("%Y-%m-%d_%H-%M-%S").to_string();
+        let target = snapshot_path(&name);
+        on_output(&format!(
+            "Creating read-only snapshot of / at {}",
+            target.display()
+        ));
+
+        let output = Command::new("btrfs")
+            .args(["subvolume", "snapshot", "-r", "/"])
+            .arg(&target)
+            .output()
+            .context("Failed to run btrfs")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "btrfs subvolume snapshot failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        if let Err(e) = fs::write(description_path(&name), description) {
+            eprintln!("Failed to save snapshot description: {}", e);
+        }
+
+        return Ok(SystemSnapshot {
+            id: name,
+            created_at: Some(created_at),
+            description: description.to_string(),
+            labels: vec!["Read-only".to_string()],
+            post_id: None,
+        });
+    }
+
+    fn list(&self) -> Result<Vec<SystemSnapshot>> {
+        let Ok(entries) = fs::read_dir(BTRFS_SNAPSHOT_DIR) else {
+            return Ok(Vec::new());
+        };
+
+        let mut snapshots = Vec::new();
+
+        for entry in entries.flatten() {
+            if !entry.path().is_dir() {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Ok(created_at) = parse_snapshot_timestamp(&name) else {
+                continue;
+            };
+
+            snapshots.push(SystemSnapshot {
+                description: fs::read_to_string(description_path(&name))
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+                id: name,
+                created_at: Some(created_at),
+                labels: vec!["Read-only".to_string()],
+                post_id: None,
+            });
+        }
+
+        return Ok(snapshots);
+    }
+
+    fn delete(&self, snapshot: &SystemSnapshot) -> Result<()> {
+        let output = Command::new("btrfs")
+            .args(["subvolume", "delete"])
+            .arg(snapshot_path(&snapshot.id))
+            .output()
+            .context("Failed to run btrfs")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "failed to delete snapshot {}: {}",
+                snapshot.id,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let _ = fs::remove_file(description_path(&snapshot.id));
+
+        return Ok(());
+    }
+
+    fn restore_command(&self, snapshot: &SystemSnapshot) -> Result<Vec<String>> {
+        return Err(anyhow!(
+            "Plain Btrfs snapshots cannot be restored while the system is running. Boot a live system and replace the root subvolume with {}",
+            snapshot_path(&snapshot.id).display()
+        ));
+    }
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    return Path::new(BTRFS_SNAPSHOT_DIR).join(name);
+}
+
+fn description_path(name: &str) -> PathBuf {
+    return Path::new(BTRFS_SNAPSHOT_DIR).join(format!("{}.description", name));
+}
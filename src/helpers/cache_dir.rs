@@ -0,0 +1,21 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+pub fn get_cache_dir(name: &str) -> Result<PathBuf> {
+    let cache_home = if let Ok(cache_home) = std::env::var("XDG_CACHE_HOME") {
+        PathBuf::from(cache_home)
+    } else if let Ok(home) = std::env::var("HOME") {
+        PathBuf::from(home).join(".cache")
+    } else {
+        return Err(anyhow::anyhow!("Could not determine cache directory"));
+    };
+
+    let dir = cache_home.join("arch-update-manager").join(name);
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir).context("Failed to create cache directory")?;
+    }
+
+    return Ok(dir);
+}
@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::helpers::downgrade::parse_package_filename;
+use crate::helpers::pacman_conf::get_cache_dirs;
+use crate::helpers::pacman_info::get_installed_packages;
+use crate::helpers::state_dir::get_state_dir;
+use crate::models::checkpoint_rollback::CheckpointRollback;
+use crate::models::package_checkpoint::PackageCheckpoint;
+
+// Every checkpoint pins its package files in the cache, so only the newest ones are kept.
+pub const MAX_CHECKPOINTS: usize = 5;
+
+pub fn create_package_checkpoint() -> Result<PackageCheckpoint> {
+    let created_at = Local::now();
+    let packages = get_installed_packages()?;
+    let cache = index_package_cache();
+
+    let mut cached_files = BTreeMap::new();
+    let mut missing_from_cache = Vec::new();
+
+    for (name, version) in &packages {
+        match cache.get(&(name.clone(), version.clone())) {
+            Some(path) => {
+                cached_files.insert(name.clone(), path.to_string_lossy().to_string());
+            }
+            None => missing_from_cache.push(name.clone()),
+        }
+    }
+
+    let checkpoint = PackageCheckpoint {
+        id: created_at.format("%Y-%m-%d_%H-%M-%S").to_string(),
+        created_at,
+        packages,
+        cached_files,
+        missing_from_cache,
+    };
+
+    let content =
+        serde_json::to_string_pretty(&checkpoint).context("Failed to serialize checkpoint")?;
+    fs::write(checkpoint_path(&checkpoint.id)?, content).context("Failed to write checkpoint")?;
+
+    if let Err(e) = prune_package_checkpoints() {
+        eprintln!("Failed to remove old checkpoints: {:#}", e);
+    }
+
+    return Ok(checkpoint);
+}
+
+pub fn load_package_checkpoint(id: &str) -> Result<PackageCheckpoint> {
+    let content = fs::read_to_string(checkpoint_path(id)?).context("Failed to read checkpoint")?;
+    return serde_json::from_str(&content).context("Failed to parse checkpoint");
+}
+
+// History sessions keep the id of a checkpoint after it was pruned.
+pub fn package_checkpoint_exists(id: &str) -> bool {
+    return checkpoint_path(id).is_ok_and(|path| path.exists());
+}
+
+pub fn list_package_checkpoints() -> Result<Vec<PackageCheckpoint>> {
+    let mut checkpoints = Vec::new();
+
+    let entries =
+        fs::read_dir(get_state_dir("checkpoints")?).context("Failed to read checkpoints")?;
+    for entry in entries.flatten() {
+        let content = match fs::read_to_string(entry.path()) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", entry.path().display(), e);
+                continue;
+            }
+        };
+
+        match serde_json::from_str::<PackageCheckpoint>(&content) {
+            Ok(checkpoint) => checkpoints.push(checkpoint),
+            Err(e) => eprintln!("Failed to parse {}: {}", entry.path().display(), e),
+        }
+    }
+
+    checkpoints.sort_by_key(|checkpoint| std::cmp::Reverse(checkpoint.created_at));
+
+    return Ok(checkpoints);
+}
+
+// Cache cleanup must never remove these files, or rolling back to a checkpoint stops working.
+// Only the cleanup on the maintenance page knows about them: paccache, `pacman -Sc` and pacman
+// hooks that run them still delete these files, and a rollback then reports them as unavailable.
+pub fn get_pinned_cache_files() -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = list_package_checkpoints()?
+        .iter()
+        .flat_map(|checkpoint| checkpoint.cached_files.values().map(PathBuf::from))
+        .collect();
+    files.sort();
+    files.dedup();
+
+    return Ok(files);
+}
+
+pub fn plan_checkpoint_rollback(checkpoint: &PackageCheckpoint) -> Result<CheckpointRollback> {
+    let installed = get_installed_packages()?;
+
+    let mut files = Vec::new();
+    let mut unavailable = Vec::new();
+
+    for (name, version) in &checkpoint.packages {
+        if installed.get(name) == Some(version) {
+            continue;
+        }
+
+        match checkpoint.cached_files.get(name) {
+            Some(file) if PathBuf::from(file).exists() => files.push(file.clone()),
+            _ => unavailable.push(format!("{} {}", name, version)),
+        }
+    }
+
+    let installed_since = installed
+        .keys()
+        .filter(|name| !checkpoint.packages.contains_key(*name))
+        .cloned()
+        .collect();
+
+    return Ok(CheckpointRollback {
+        files,
+        unavailable,
+        installed_since,
+    });
+}
+
+fn prune_package_checkpoints() -> Result<()> {
+    for checkpoint in list_package_checkpoints()?.iter().skip(MAX_CHECKPOINTS) {
+        fs::remove_file(checkpoint_path(&checkpoint.id)?)
+            .with_context(|| format!("Failed to remove checkpoint {}", checkpoint.id))?;
+    }
+
+    return Ok(());
+}
+
+fn index_package_cache() -> HashMap<(String, String), PathBuf> {
+    let mut index = HashMap::new();
+
+    for cache_dir in get_cache_dirs() {
+        let Ok(entries) = fs::read_dir(&cache_dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if let Some((name, version, _arch)) = parse_package_filename(&file_name) {
+                index.entry((name, version)).or_insert_with(|| entry.path());
+            }
+        }
+    }
+
+    return index;
+}
+
+fn checkpoint_path(id: &str) -> Result<PathBuf> {
+    return Ok(get_state_dir("checkpoints")?.join(format!("{}.json", id)));
+}
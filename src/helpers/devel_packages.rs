@@ -0,0 +1,400 @@
+use anyhow::{Context, Result, anyhow};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::helpers::aur_review::{get_aur_clone_dir, sync_aur_clone};
+use crate::helpers::aur_rpc::list_foreign_packages;
+use crate::helpers::settings::load_settings;
+use crate::helpers::state_dir::get_state_dir;
+use crate::models::app_settings::AppSettings;
+use crate::models::devel_package::DevelPackage;
+use crate::models::devel_source::DevelSource;
+
+pub const DEVEL_UPDATE_VERSION: &str = "latest-commit";
+
+// Upstream changes are only looked up for git sources. Packages of these version control systems
+// are listed as devel packages but only update when their AUR version changes.
+const UNCHECKED_VCS_SUFFIXES: &[&str] = &["-svn", "-hg", "-bzr", "-fossil", "-darcs", "-cvs"];
+
+// Upstream commits are looked up with git ls-remote, which only works for -git packages.
+pub fn is_devel_package(name: &str) -> bool {
+    return name.ends_with("-git");
+}
+
+pub fn is_unchecked_vcs_package(name: &str) -> bool {
+    return UNCHECKED_VCS_SUFFIXES
+        .iter()
+        .any(|suffix| name.ends_with(suffix));
+}
+
+pub fn is_devel_check_enabled(settings: &AppSettings, name: &str) -> bool {
+    return settings
+        .devel_check_overrides
+        .get(name)
+        .copied()
+        .unwrap_or(settings.check_devel_updates);
+}
+
+// Returns None when nothing tells which commit the installed package was built from.
+pub fn has_devel_update(
+    name: &str,
+    package_base: &str,
+    installed_version: &str,
+) -> Result<Option<bool>> {
+    let mut revisions = load_devel_revisions();
+
+    let recorded = match revisions.get(name) {
+        Some(recorded) => recorded.clone(),
+        None => {
+            // The commit in the installed pkgver becomes the baseline. Taking the current upstream
+            // state instead would hide the commits the package is already behind.
+            let Some(built_commit) = parse_pkgver_commit(installed_version) else {
+                return Ok(None);
+            };
+            let sources = get_devel_sources(package_base, Some(&built_commit))?;
+            revisions.insert(name.to_string(), sources.clone());
+            save_devel_revisions(&revisions)?;
+            sources
+        }
+    };
+
+    for source in recorded {
+        // Revisions taken from a pkgver are abbreviated.
+        if !get_remote_revision(&source.url, &source.reference)?.starts_with(&source.revision) {
+            return Ok(Some(true));
+        }
+    }
+
+    return Ok(Some(false));
+}
+
+pub fn record_devel_revisions(packages: &[(String, String)]) -> Result<()> {
+    let mut revisions = load_devel_revisions();
+    let installed: BTreeMap<String, String> = list_foreign_packages()?.into_iter().collect();
+
+    for (name, package_base) in packages.iter().filter(|(name, _)| is_devel_package(name)) {
+        // The remote may have moved on while the package was building, so the commit is taken
+        // from the version that was actually installed.
+        let built_commit = installed
+            .get(name)
+            .and_then(|version| parse_pkgver_commit(version));
+
+        match get_devel_sources(package_base, built_commit.as_deref()) {
+            Ok(sources) => {
+                revisions.insert(name.clone(), sources);
+            }
+            Err(e) => eprintln!(
+                "Failed to record the upstream revision of {}: {:#}",
+                name, e
+            ),
+        }
+    }
+
+    return save_devel_revisions(&revisions);
+}
+
+pub fn list_devel_packages() -> Result<Vec<DevelPackage>> {
+    let settings = load_settings();
+    let revisions = load_devel_revisions();
+
+    return Ok(list_foreign_packages()?
+        .into_iter()
+        .filter(|(name, _)| is_devel_package(name) || is_unchecked_vcs_package(name))
+        .map(|(name, version)| DevelPackage {
+            sources: revisions.get(&name).cloned().unwrap_or_default(),
+            checkable: is_devel_package(&name),
+            checked: is_devel_package(&name) && is_devel_check_enabled(&settings, &name),
+            name,
+            version,
+        })
+        .collect());
+}
+
+fn get_devel_sources(package_base: &str, built_commit: Option<&str>) -> Result<Vec<DevelSource>> {
+    let dir = get_aur_clone_dir(package_base)?;
+    let dir = if dir.join(".SRCINFO").exists() {
+        dir
+    } else {
+        sync_aur_clone(package_base)?
+    };
+
+    // .SRCINFO has the variables of the PKGBUILD already expanded.
+    let srcinfo = fs::read_to_string(dir.join(".SRCINFO"))
+        .with_context(|| format!("{} has no .SRCINFO", package_base))?;
+
+    return parse_git_sources(&srcinfo)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (url, reference))| {
+            // pkgver() describes the first git source. Other sources fall back to the remote state.
+            let revision = match built_commit.filter(|_| index == 0) {
+                Some(commit) => commit.to_string(),
+                None => get_remote_revision(&url, &reference)?,
+            };
+            Ok(DevelSource {
+                url,
+                reference,
+                revision,
+            })
+        })
+        .collect();
+}
+
+// Returns the URL and ref of every git source that follows a branch. Sources pinned to a tag or
+// commit never move, so they are left out.
+pub fn parse_git_sources(srcinfo: &str) -> Vec<(String, String)> {
+    let mut sources = Vec::new();
+
+    for line in srcinfo.lines() {
+        let Some((key, value)) = line.trim().split_once(" = ") else {
+            continue;
+        };
+        if key != "source" && !key.starts_with("source_") {
+            continue;
+        }
+
+        let url = value.split_once("::").map(|(_, url)| url).unwrap_or(value);
+        let Some(url) = url
+            .strip_prefix("git+")
+            .or_else(|| url.starts_with("git://").then_some(url))
+        else {
+            continue;
+        };
+
+        let (url, fragment) = url.split_once('#').unwrap_or((url, ""));
+        let url = url.split_once('?').map(|(url, _)| url).unwrap_or(url);
+
+        let reference = match fragment.split_once('=') {
+            None => "HEAD".to_string(),
+            Some(("branch", branch)) => format!("refs/heads/{}", branch),
+            Some(_) => continue,
+        };
+
+        let source = (url.to_string(), reference);
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+
+    return sources;
+}
+
+// Finds the abbreviated commit in versions like `1.2.r45.gabc1234-1` or `r123.abc1234-1`.
+pub fn parse_pkgver_commit(version: &str) -> Option<String> {
+    let pkgver = version.rsplit_once('-').map(|(pkgver, _)| pkgver)?;
+    let pkgver = pkgver
+        .split_once(':')
+        .map(|(_, pkgver)| pkgver)
+        .unwrap_or(pkgver);
+
+    return pkgver
+        .rsplit(['.', '_', '+'])
+        .map(|part| part.strip_prefix('g').unwrap_or(part))
+        .find(|part| {
+            (7..=40).contains(&part.len())
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())
+                && part.chars().any(|c| c.is_ascii_alphabetic())
+        })
+        .map(|part| part.to_string());
+}
+
+pub fn get_remote_revision(url: &str, reference: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["ls-remote", "--", url, reference])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Failed to run git ls-remote")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Could not reach {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    return String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .find(|(_, name)| *name == reference)
+        .map(|(revision, _)| revision.to_string())
+        .ok_or_else(|| anyhow!("{} has no {}", url, reference));
+}
+
+fn load_devel_revisions() -> BTreeMap<String, Vec<DevelSource>> {
+    let Ok(path) = devel_revisions_path() else {
+        return BTreeMap::new();
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+
+    return match serde_json::from_str(&content) {
+        Ok(revisions) => revisions,
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            BTreeMap::new()
+        }
+    };
+}
+
+fn save_devel_revisions(revisions: &BTreeMap<String, Vec<DevelSource>>) -> Result<()> {
+    let content =
+        serde_json::to_string_pretty(revisions).context("Failed to serialize devel revisions")?;
+    fs::write(devel_revisions_path()?, content).context("Failed to write devel revisions")?;
+
+    return Ok(());
+}
+
+fn devel_revisions_path() -> Result<PathBuf> {
+    return Ok(get_state_dir("aur")?.join("devel.json"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.org"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return String::from_utf8_lossy(&output.stdout).trim().to_string();
+    }
+
+    #[test]
+    fn only_checks_git_packages_for_upstream_commits() {
+        assert!(is_devel_package("neovim-git"));
+        assert!(!is_unchecked_vcs_package("neovim-git"));
+
+        for name in [
+            "subversion-tools-svn",
+            "mercurial-ext-hg",
+            "foo-bzr",
+            "bar-fossil",
+        ] {
+            assert!(!is_devel_package(name), "{}", name);
+            assert!(is_unchecked_vcs_package(name), "{}", name);
+        }
+
+        assert!(!is_devel_package("legit"));
+        assert!(!is_unchecked_vcs_package("svnkit"));
+    }
+
+    #[test]
+    fn parses_branch_following_git_sources() {
+        let srcinfo = include_str!("../../tests/fixtures/devel_packages/multi_source.SRCINFO");
+
+        assert_eq!(
+            parse_git_sources(srcinfo),
+            [
+                (
+                    "https://github.com/neovim/neovim.git".to_string(),
+                    "HEAD".to_string()
+                ),
+                (
+                    "https://github.com/neovim/neovim-runtime.git".to_string(),
+                    "refs/heads/stable".to_string()
+                ),
+                (
+                    "git://git.example.org/legacy.git".to_string(),
+                    "HEAD".to_string()
+                ),
+                (
+                    "https://github.com/neovim/arm-support.git".to_string(),
+                    "refs/heads/main".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_the_commit_in_a_pkgver() {
+        assert_eq!(
+            parse_pkgver_commit("0.11.0.r312.g9b3c2a1f0-1").as_deref(),
+            Some("9b3c2a1f0")
+        );
+        assert_eq!(
+            parse_pkgver_commit("r1024.4e2d1ab-2").as_deref(),
+            Some("4e2d1ab")
+        );
+        assert_eq!(
+            parse_pkgver_commit("1:2.3_r5_gdeadbeef-1").as_deref(),
+            Some("deadbeef")
+        );
+        assert_eq!(parse_pkgver_commit("20240101.1234567-1"), None);
+        assert_eq!(parse_pkgver_commit("1.2.3-1"), None);
+        assert_eq!(parse_pkgver_commit("r1024"), None);
+    }
+
+    #[test]
+    fn reads_remote_revisions_with_ls_remote() {
+        let temp = tempfile::tempdir().unwrap();
+        let remote = temp.path().join("remote.git");
+        let work = temp.path().join("work");
+        fs::create_dir_all(&work).unwrap();
+
+        git(
+            temp.path(),
+            &[
+                "init",
+                "--quiet",
+                "--bare",
+                "--initial-branch=main",
+                "remote.git",
+            ],
+        );
+        git(&work, &["init", "--quiet", "--initial-branch=main"]);
+        git(
+            &work,
+            &["commit", "--quiet", "--allow-empty", "-m", "first"],
+        );
+        let first = git(&work, &["rev-parse", "HEAD"]);
+        git(&work, &["branch", "stable"]);
+        git(
+            &work,
+            &["commit", "--quiet", "--allow-empty", "-m", "second"],
+        );
+        let second = git(&work, &["rev-parse", "HEAD"]);
+        git(
+            &work,
+            &[
+                "push",
+                "--quiet",
+                remote.to_str().unwrap(),
+                "main",
+                "stable",
+            ],
+        );
+
+        let url = remote.to_string_lossy();
+        assert_eq!(get_remote_revision(&url, "HEAD").unwrap(), second);
+        assert_eq!(
+            get_remote_revision(&url, "refs/heads/stable").unwrap(),
+            first
+        );
+
+        let error = get_remote_revision(&url, "refs/heads/missing").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("{} has no refs/heads/missing", url)
+        );
+        assert!(
+            get_remote_revision(&temp.path().join("missing.git").to_string_lossy(), "HEAD")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::Path;
+use std::process::Command;
+
+pub fn get_available_space(path: &Path) -> Result<u64> {
+    return read_df_field(path, "avail");
+}
+
+pub fn get_used_space(path: &Path) -> Result<u64> {
+    return read_df_field(path, "used");
+}
+
+fn read_df_field(path: &Path, field: &str) -> Result<u64> {
+    let output = Command::new("df")
+        .args(["-B1", &format!("--output={}", field)])
+        .arg(path)
+        .output()
+        .context("Failed to run df")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "df failed for {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let Some(value) = stdout.lines().nth(1) else {
+        return Err(anyhow!("Unexpected df output for {}", path.display()));
+    };
+
+    return value
+        .trim()
+        .parse::<u64>()
+        .with_context(|| format!("Failed to parse df output for {}", path.display()));
+}
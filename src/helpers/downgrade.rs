@@ -0,0 +1,275 @@
+use anyhow::{Context, Result, anyhow};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::helpers::pacman_conf::get_cache_dirs;
+use crate::helpers::pacman_info::{query_package_info, split_list_field};
+use crate::helpers::settings::load_settings;
+use crate::helpers::vercmp::{satisfies_constraint, vercmp};
+use crate::models::downgrade_candidate::DowngradeCandidate;
+
+pub fn get_installed_version(package: &str) -> Option<String> {
+    let output = Command::new("pacman").args(["-Q", package]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    return stdout.split_whitespace().nth(1).map(|v| v.to_string());
+}
+
+pub fn list_downgrade_candidates(package: &str) -> Result<Vec<DowngradeCandidate>> {
+    let installed_version = get_installed_version(package);
+    let mut candidates = Vec::new();
+
+    for cache_dir in get_cache_dirs() {
+        candidates.extend(find_cached_versions(package, &cache_dir));
+    }
+
+    let settings = load_settings();
+    if let Some(archive_url) = settings.downgrade_archive_url.as_deref() {
+        if !archive_url.trim().is_empty() {
+            match find_archive_versions(package, archive_url.trim()) {
+                Ok(archived) => candidates.extend(archived),
+                Err(e) => eprintln!("Failed to query package archive: {}", e),
+            }
+        }
+    }
+
+    return Ok(filter_downgrade_candidates(
+        candidates,
+        installed_version.as_deref(),
+    ));
+}
+
+// Keeps one entry per version older than the installed one, newest first. Newer files in the
+// cache are left over from a downgrade and would be an upgrade.
+pub fn filter_downgrade_candidates(
+    mut candidates: Vec<DowngradeCandidate>,
+    installed_version: Option<&str>,
+) -> Vec<DowngradeCandidate> {
+    let mut seen_versions = HashSet::new();
+    candidates.retain(|candidate| {
+        installed_version
+            .is_none_or(|installed| vercmp(&candidate.version, installed) == Ordering::Less)
+            && seen_versions.insert(candidate.version.clone())
+    });
+
+    candidates.sort_by(|a, b| vercmp(&b.version, &a.version));
+
+    return candidates;
+}
+
+pub fn find_cached_versions(package: &str, cache_dir: &Path) -> Vec<DowngradeCandidate> {
+    let Ok(entries) = fs::read_dir(cache_dir) else {
+        return Vec::new();
+    };
+
+    let mut candidates = Vec::new();
+
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some((name, version, _arch)) = parse_package_filename(&file_name) else {
+            continue;
+        };
+
+        if name == package {
+            candidates.push(DowngradeCandidate {
+                name,
+                version,
+                location: entry.path().to_string_lossy().to_string(),
+                from_archive: false,
+            });
+        }
+    }
+
+    return candidates;
+}
+
+fn find_archive_versions(package: &str, archive_url: &str) -> Result<Vec<DowngradeCandidate>> {
+    let Some(first_char) = package.chars().next() else {
+        return Ok(Vec::new());
+    };
+
+    let index_url = format!(
+        "{}/packages/{}/{}/",
+        archive_url.trim_end_matches('/'),
+        first_char,
+        package
+    );
+
+    let output = Command::new("curl")
+        .args(["-fsSL", "--max-time", "20", &index_url])
+        .output()
+        .context("Failed to run curl")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Could not fetch {}: {}",
+            index_url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let href_re = Regex::new(r#"href="([^"/]+\.pkg\.tar(?:\.[a-z0-9]+)?)""#).unwrap();
+    let mut candidates = Vec::new();
+
+    for caps in href_re.captures_iter(&listing) {
+        let file_name = caps[1].replace("%3A", ":");
+        let Some((name, version, _arch)) = parse_package_filename(&file_name) else {
+            continue;
+        };
+
+        if name == package {
+            candidates.push(DowngradeCandidate {
+                name,
+                version,
+                location: format!("{}{}", index_url, &caps[1]),
+                from_archive: true,
+            });
+        }
+    }
+
+    return Ok(candidates);
+}
+
+pub fn parse_package_filename(file_name: &str) -> Option<(String, String, String)> {
+    if file_name.ends_with(".sig") || file_name.ends_with(".part") {
+        return None;
+    }
+
+    let stem_end = file_name.find(".pkg.tar")?;
+    let stem = &file_name[..stem_end];
+
+    let mut parts = stem.rsplitn(4, '-');
+    let arch = parts.next()?;
+    let pkgrel = parts.next()?;
+    let pkgver = parts.next()?;
+    let name = parts.next()?;
+
+    if name.is_empty() || pkgver.is_empty() || pkgrel.is_empty() {
+        return None;
+    }
+
+    return Some((
+        name.to_string(),
+        format!("{}-{}", pkgver, pkgrel),
+        arch.to_string(),
+    ));
+}
+
+pub fn find_dependency_conflicts(package: &str, version: &str) -> Result<Vec<String>> {
+    let info = query_package_info(&["-Qi", package])?;
+    let Some(package_info) = info.first() else {
+        return Ok(Vec::new());
+    };
+
+    let required_by = split_list_field(package_info.get("Required By"));
+    if required_by.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut args = vec!["-Qi"];
+    args.extend(required_by.iter().map(|name| name.as_str()));
+    let dependents = query_package_info(&args)?;
+
+    let constraint_re = Regex::new(r"^([^<>=]+)(>=|<=|=|<|>)(.+)$").unwrap();
+    let mut conflicts = Vec::new();
+
+    for dependent in &dependents {
+        let dependent_name = dependent.get("Name").cloned().unwrap_or_default();
+
+        for dependency in split_list_field(dependent.get("Depends On")) {
+            let Some(caps) = constraint_re.captures(&dependency) else {
+                continue;
+            };
+
+            if &caps[1] == package && !satisfies_constraint(version, &caps[2], &caps[3]) {
+                conflicts.push(format!("{} requires {}", dependent_name, dependency));
+            }
+        }
+    }
+
+    return Ok(conflicts);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(version: &str, from_archive: bool) -> DowngradeCandidate {
+        return DowngradeCandidate {
+            name: "firefox".to_string(),
+            version: version.to_string(),
+            location: format!("firefox-{}-x86_64.pkg.tar.zst", version),
+            from_archive,
+        };
+    }
+
+    #[test]
+    fn offers_only_versions_older_than_the_installed_one() {
+        let candidates = vec![
+            candidate("127.0.2-1", false),
+            candidate("129.0-1", false),
+            candidate("128.0-1", false),
+            candidate("128.0-2", false),
+            candidate("127.0.2-1", true),
+            candidate("126.0-1", true),
+        ];
+
+        let filtered = filter_downgrade_candidates(candidates, Some("128.0-2"));
+        let versions: Vec<(&str, bool)> = filtered
+            .iter()
+            .map(|c| (c.version.as_str(), c.from_archive))
+            .collect();
+
+        assert_eq!(
+            versions,
+            [("128.0-1", false), ("127.0.2-1", false), ("126.0-1", true)]
+        );
+    }
+
+    #[test]
+    fn offers_every_version_when_nothing_is_installed() {
+        let candidates = vec![candidate("1.0-1", false), candidate("2.0-1", false)];
+
+        let filtered = filter_downgrade_candidates(candidates, None);
+
+        assert_eq!(filtered[0].version, "2.0-1");
+        assert_eq!(filtered[1].version, "1.0-1");
+    }
+
+    #[test]
+    fn parses_package_filenames() {
+        assert_eq!(
+            parse_package_filename("firefox-1:128.0-2-x86_64.pkg.tar.zst"),
+            Some((
+                "firefox".to_string(),
+                "1:128.0-2".to_string(),
+                "x86_64".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_package_filename("python-foo-bar-1.0-1-any.pkg.tar.xz"),
+            Some((
+                "python-foo-bar".to_string(),
+                "1.0-1".to_string(),
+                "any".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_package_filename("firefox-128.0-2-x86_64.pkg.tar.zst.sig"),
+            None
+        );
+        assert_eq!(
+            parse_package_filename("firefox-128.0-2-x86_64.pkg.tar.zst.part"),
+            None
+        );
+        assert_eq!(parse_package_filename("notes.txt"), None);
+    }
+}
@@ -0,0 +1,246 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::helpers::aur_builder::strip_version_constraint;
+use crate::helpers::aur_rpc::{get_aur_rpc_url, list_foreign_packages, query_aur_info};
+use crate::helpers::pacman_info::{query_package_info, split_list_field};
+use crate::helpers::settings::load_settings;
+use crate::helpers::state_dir::get_state_dir;
+use crate::models::aur_package::AurPackage;
+use crate::models::foreign_package_issue::ForeignPackageIssue;
+use crate::models::foreign_package_issue_kind::ForeignPackageIssueKind;
+
+pub fn find_foreign_package_issues() -> Result<Vec<ForeignPackageIssue>> {
+    let foreign = list_foreign_packages()?;
+    if foreign.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let names: Vec<String> = foreign.iter().map(|(name, _)| name.clone()).collect();
+    let aur = query_aur_info(&get_aur_rpc_url(&load_settings()), &names)?;
+    let sync = query_package_info(&["-Si"])?;
+
+    let mut known = load_known_maintainers();
+    let issues = classify_foreign_packages(&foreign, &aur, &sync, &known);
+
+    // The first time a package is seen its current maintainer becomes the baseline.
+    let unseen: Vec<(String, Option<String>)> = aur
+        .iter()
+        .filter(|package| !known.contains_key(&package.name))
+        .map(|package| (package.name.clone(), package.maintainer.clone()))
+        .collect();
+    if !unseen.is_empty() {
+        known.extend(unseen);
+        if let Err(e) = save_known_maintainers(&known) {
+            eprintln!("Failed to record AUR maintainers: {}", e);
+        }
+    }
+
+    return Ok(issues);
+}
+
+pub fn classify_foreign_packages(
+    foreign: &[(String, String)],
+    aur: &[AurPackage],
+    sync: &[HashMap<String, String>],
+    known_maintainers: &BTreeMap<String, Option<String>>,
+) -> Vec<ForeignPackageIssue> {
+    let mut issues = Vec::new();
+
+    for (name, version) in foreign {
+        let aur_package = aur.iter().find(|package| &package.name == name);
+
+        if let Some(official) = find_official_replacement(name, sync) {
+            issues.push(ForeignPackageIssue {
+                name: name.clone(),
+                version: version.clone(),
+                kind: ForeignPackageIssueKind::InOfficialRepo {
+                    repository: official.get("Repository").cloned().unwrap_or_default(),
+                    name: official.get("Name").cloned().unwrap_or_default(),
+                    version: official.get("Version").cloned().unwrap_or_default(),
+                },
+            });
+            continue;
+        }
+
+        let Some(aur_package) = aur_package else {
+            issues.push(ForeignPackageIssue {
+                name: name.clone(),
+                version: version.clone(),
+                kind: ForeignPackageIssueKind::MissingFromAur,
+            });
+            continue;
+        };
+
+        if let Some(previous) = known_maintainers.get(name) {
+            if *previous != aur_package.maintainer {
+                issues.push(ForeignPackageIssue {
+                    name: name.clone(),
+                    version: version.clone(),
+                    kind: ForeignPackageIssueKind::MaintainerChanged {
+                        previous: previous.clone(),
+                        current: aur_package.maintainer.clone(),
+                    },
+                });
+            }
+        }
+    }
+
+    return issues;
+}
+
+// Only an official package of the same name or one that declares `replaces` counts. Provides are
+// not enough: an official foo providing foo-git says nothing about the VCS package being obsolete.
+fn find_official_replacement<'a>(
+    name: &str,
+    sync: &'a [HashMap<String, String>],
+) -> Option<&'a HashMap<String, String>> {
+    let same_name = sync
+        .iter()
+        .find(|package| package.get("Name").map(|n| n.as_str()) == Some(name));
+    if same_name.is_some() {
+        return same_name;
+    }
+
+    return sync.iter().find(|package| {
+        split_list_field(package.get("Replaces"))
+            .iter()
+            .any(|entry| strip_version_constraint(entry) == name)
+    });
+}
+
+pub fn load_known_maintainers() -> BTreeMap<String, Option<String>> {
+    let Ok(path) = maintainers_path() else {
+        return BTreeMap::new();
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+
+    return match serde_json::from_str(&content) {
+        Ok(maintainers) => maintainers,
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            BTreeMap::new()
+        }
+    };
+}
+
+pub fn record_aur_maintainers(maintainers: &[(String, Option<String>)]) -> Result<()> {
+    let mut known = load_known_maintainers();
+    known.extend(maintainers.iter().cloned());
+
+    return save_known_maintainers(&known);
+}
+
+fn save_known_maintainers(maintainers: &BTreeMap<String, Option<String>>) -> Result<()> {
+    let content =
+        serde_json::to_string_pretty(maintainers).context("Failed to serialize AUR maintainers")?;
+    fs::write(maintainers_path()?, content).context("Failed to write AUR maintainers")?;
+
+    return Ok(());
+}
+
+fn maintainers_path() -> Result<PathBuf> {
+    return Ok(get_state_dir("aur")?.join("maintainers.json"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::aur_rpc::{parse_aur_info_response, parse_foreign_packages};
+    use crate::helpers::pacman_info::parse_package_info;
+
+    fn classify(known_maintainers: &[(&str, Option<&str>)]) -> Vec<ForeignPackageIssue> {
+        let foreign = parse_foreign_packages(include_str!(
+            "../../tests/fixtures/foreign_packages/foreign.Qm"
+        ));
+        let aur = parse_aur_info_response(include_str!(
+            "../../tests/fixtures/foreign_packages/aur.json"
+        ))
+        .unwrap();
+        let sync = parse_package_info(include_str!(
+            "../../tests/fixtures/foreign_packages/sync.Si"
+        ));
+        let known_maintainers = known_maintainers
+            .iter()
+            .map(|(name, maintainer)| (name.to_string(), maintainer.map(|m| m.to_string())))
+            .collect();
+
+        return classify_foreign_packages(&foreign, &aur, &sync, &known_maintainers);
+    }
+
+    fn kinds(issues: &[ForeignPackageIssue]) -> Vec<(&str, &ForeignPackageIssueKind)> {
+        return issues
+            .iter()
+            .map(|issue| (issue.name.as_str(), &issue.kind))
+            .collect();
+    }
+
+    fn in_official_repo(repository: &str, name: &str, version: &str) -> ForeignPackageIssueKind {
+        return ForeignPackageIssueKind::InOfficialRepo {
+            repository: repository.to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+    }
+
+    #[test]
+    fn matches_official_packages_by_name_or_replaces() {
+        let issues = classify(&[]);
+
+        assert_eq!(
+            kinds(&issues),
+            [
+                ("exa", &in_official_repo("extra", "eza", "0.18.21-1")),
+                ("foo-bin", &ForeignPackageIssueKind::MissingFromAur),
+                ("zoxide", &in_official_repo("extra", "zoxide", "0.9.4-2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_official_packages_that_only_provide_the_name() {
+        let issues = classify(&[]);
+
+        // extra/yt-dlp provides youtube-dl and extra/neovim provides neovim-git, yet both AUR
+        // packages are still maintained there.
+        assert!(!issues.iter().any(|issue| issue.name == "youtube-dl"));
+        assert!(!issues.iter().any(|issue| issue.name == "neovim-git"));
+    }
+
+    #[test]
+    fn reports_changed_maintainers() {
+        let issues = classify(&[
+            ("google-chrome", Some("luzifer")),
+            ("yay-bin", Some("jguer")),
+            ("neovim-git", None),
+        ]);
+
+        let changed: Vec<(&str, &ForeignPackageIssueKind)> = kinds(&issues)
+            .into_iter()
+            .filter(|(_, kind)| matches!(kind, ForeignPackageIssueKind::MaintainerChanged { .. }))
+            .collect();
+        assert_eq!(
+            changed,
+            [
+                (
+                    "neovim-git",
+                    &ForeignPackageIssueKind::MaintainerChanged {
+                        previous: None,
+                        current: Some("fwalch".to_string()),
+                    }
+                ),
+                (
+                    "yay-bin",
+                    &ForeignPackageIssueKind::MaintainerChanged {
+                        previous: Some("jguer".to_string()),
+                        current: None,
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::helpers::ansi::strip_ansi_codes;
+use crate::helpers::state_dir::get_state_dir;
+use crate::models::install_session::InstallSession;
+use crate::models::session_package::SessionPackage;
+
+static ACTIVE_SESSION: Mutex<Option<InstallSession>> = Mutex::new(None);
+
+pub fn begin_install_session(
+    packages: Vec<SessionPackage>,
+    snapshot: Option<String>,
+    checkpoint: Option<String>,
+) -> Result<PathBuf> {
+    let started_at = Local::now();
+    let session = InstallSession {
+        id: started_at.format("%Y-%m-%d_%H-%M-%S").to_string(),
+        started_at,
+        finished_at: None,
+        packages,
+        snapshot,
+        checkpoint,
+        exit_code: None,
+    };
+
+    save_session(&session)?;
+    let transcript = transcript_path(&session)?;
+
+    *ACTIVE_SESSION.lock().unwrap() = Some(session);
+
+    return Ok(transcript);
+}
+
+pub fn finish_install_session(exit_code: i32) -> Option<InstallSession> {
+    let mut session = ACTIVE_SESSION.lock().unwrap().take()?;
+
+    session.finished_at = Some(Local::now());
+    session.exit_code = Some(exit_code);
+
+    if let Err(e) = save_session(&session) {
+        eprintln!("Failed to save install session {}: {}", session.id, e);
+    }
+
+    return Some(session);
+}
+
+// Drops a session whose command never started, so it doesn't show up as interrupted.
+pub fn discard_install_session() {
+    let Some(session) = ACTIVE_SESSION.lock().unwrap().take() else {
+        return;
+    };
+
+    if let Ok(path) = session_path(&session) {
+        let _ = fs::remove_file(path);
+    }
+    if let Ok(path) = transcript_path(&session) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+pub fn list_install_sessions() -> Result<Vec<InstallSession>> {
+    let dir = get_state_dir("history")?;
+    let mut sessions = Vec::new();
+
+    for entry in fs::read_dir(&dir).context("Failed to read history directory")? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        match serde_json::from_str::<InstallSession>(&content) {
+            Ok(session) => sessions.push(session),
+            Err(e) => eprintln!("Failed to parse {}: {}", path.display(), e),
+        }
+    }
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.started_at));
+
+    return Ok(sessions);
+}
+
+pub fn read_transcript(session: &InstallSession) -> Result<String> {
+    let path = transcript_path(session)?;
+    let bytes = fs::read(&path).context("Failed to read transcript")?;
+
+    return Ok(strip_ansi_codes(&String::from_utf8_lossy(&bytes)));
+}
+
+fn save_session(session: &InstallSession) -> Result<()> {
+    let path = session_path(session)?;
+    let content =
+        serde_json::to_string_pretty(session).context("Failed to serialize install session")?;
+
+    fs::write(&path, content).context("Failed to write install session")?;
+
+    return Ok(());
+}
+
+fn session_path(session: &InstallSession) -> Result<PathBuf> {
+    return Ok(get_state_dir("history")?.join(format!("{}.json", session.id)));
+}
+
+fn transcript_path(session: &InstallSession) -> Result<PathBuf> {
+    return Ok(get_state_dir("history")?.join(format!("{}.log", session.id)));
+}
@@ -0,0 +1,55 @@
+use anyhow::{Context, Result, anyhow};
+use gio::prelude::*;
+use std::os::fd::OwnedFd;
+
+const LOGIND_BUS_NAME: &str = "org.freedesktop.login1";
+const LOGIND_OBJECT_PATH: &str = "/org/freedesktop/login1";
+const LOGIND_MANAGER_INTERFACE: &str = "org.freedesktop.login1.Manager";
+
+pub fn reboot() -> Result<()> {
+    let connection = gio::bus_get_sync(gio::BusType::System, None::<&gio::Cancellable>)
+        .context("Failed to connect to the system bus")?;
+
+    connection
+        .call_sync(
+            Some(LOGIND_BUS_NAME),
+            LOGIND_OBJECT_PATH,
+            LOGIND_MANAGER_INTERFACE,
+            "Reboot",
+            Some(&(false,).to_variant()),
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+            None::<&gio::Cancellable>,
+        )
+        .context("logind refused to reboot the system")?;
+
+    return Ok(());
+}
+
+// logind holds the lock for as long as the returned file descriptor stays open.
+pub fn inhibit(what: &str, who: &str, why: &str) -> Result<OwnedFd> {
+    let connection = gio::bus_get_sync(gio::BusType::System, None::<&gio::Cancellable>)
+        .context("Failed to connect to the system bus")?;
+
+    let (_, fd_list) = connection
+        .call_with_unix_fd_list_sync(
+            Some(LOGIND_BUS_NAME),
+            LOGIND_OBJECT_PATH,
+            LOGIND_MANAGER_INTERFACE,
+            "Inhibit",
+            Some(&(what, who, why, "block").to_variant()),
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+            None::<&gio::UnixFDList>,
+            None::<&gio::Cancellable>,
+        )
+        .context("logind refused the inhibitor lock")?;
+
+    let Some(fd_list) = fd_list else {
+        return Err(anyhow!("logind did not return an inhibitor lock"));
+    };
+
+    return fd_list.get(0).context("Failed to read the inhibitor lock");
+}
@@ -1,7 +1,39 @@
+pub mod ansi;
 pub mod aur;
+pub mod aur_builder;
+pub mod aur_helpers;
+pub mod aur_rebuilds;
+pub mod aur_review;
+pub mod aur_rpc;
+pub mod boot_checks;
+pub mod btrfs_snapshots;
+pub mod cache_dir;
+pub mod checkpoint;
 pub mod database_lock;
+pub mod devel_packages;
+pub mod disk_space;
+pub mod downgrade;
+pub mod foreign_packages;
 pub mod get_navigation_stack;
+pub mod history;
+pub mod logind;
+pub mod orphans;
+pub mod package_cache;
 pub mod package_updates;
+pub mod pacman_conf;
+pub mod pacman_info;
+pub mod pacman_log;
+pub mod pacnew;
+pub mod pkgbuild_scanner;
+pub mod restart_check;
 pub mod settings;
+pub mod snapper;
+pub mod snapshot_metadata;
+pub mod snapshot_provider;
+pub mod snapshot_retention;
+pub mod state_dir;
 pub mod terminal;
 pub mod timeshift;
+pub mod transaction_inhibitor;
+pub mod transaction_space;
+pub mod vercmp;
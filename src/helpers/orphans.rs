@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::helpers::package_updates::parse_size_string;
+use crate::helpers::pacman_info::{query_package_info, split_list_field};
+use crate::models::orphan_package::OrphanPackage;
+
+pub fn list_orphan_packages() -> Result<Vec<OrphanPackage>> {
+    // -Qdtt also lists packages that are only optionally required by something else.
+    let output = Command::new("pacman")
+        .arg("-Qdttq")
+        .output()
+        .context("Failed to run pacman -Qdttq")?;
+
+    // pacman exits with 1 when there is nothing to list.
+    let names: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut args = vec!["-Qi"];
+    args.extend(names.iter().map(|name| name.as_str()));
+
+    let mut orphans: Vec<OrphanPackage> = query_package_info(&args)?
+        .into_iter()
+        .map(|info| OrphanPackage {
+            name: info.get("Name").cloned().unwrap_or_default(),
+            version: info.get("Version").cloned().unwrap_or_default(),
+            size: info
+                .get("Installed Size")
+                .and_then(|size| parse_size_string(size))
+                .unwrap_or(0),
+            install_reason: info.get("Install Reason").cloned().unwrap_or_default(),
+            optional_for: split_list_field(info.get("Optional For")),
+        })
+        .filter(|orphan| !orphan.name.is_empty())
+        .collect();
+
+    orphans.sort_by(|a, b| b.size.cmp(&a.size).then(a.name.cmp(&b.name)));
+
+    return Ok(orphans);
+}
@@ -0,0 +1,334 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::helpers::checkpoint::get_pinned_cache_files;
+use crate::helpers::downgrade::parse_package_filename;
+use crate::helpers::pacman_conf::get_cache_dirs;
+use crate::helpers::vercmp::vercmp;
+use crate::models::app_settings::AppSettings;
+use crate::models::cache_cleanup_policy::CacheCleanupPolicy;
+use crate::models::cache_dir_usage::CacheDirUsage;
+use crate::models::cached_package::CachedPackage;
+
+pub fn get_cache_cleanup_policy(settings: &AppSettings) -> Result<CacheCleanupPolicy> {
+    return Ok(CacheCleanupPolicy {
+        keep_versions: settings.cache_keep_versions,
+        remove_uninstalled: settings.cache_remove_uninstalled,
+        pinned_files: get_pinned_cache_files()?,
+    });
+}
+
+pub fn list_cached_packages() -> Vec<CachedPackage> {
+    return list_cached_packages_in(&get_cache_dirs());
+}
+
+pub fn list_cached_packages_in(cache_dirs: &[PathBuf]) -> Vec<CachedPackage> {
+    let mut packages = Vec::new();
+
+    for cache_dir in cache_dirs {
+        let Ok(entries) = fs::read_dir(cache_dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some((name, version, _arch)) = parse_package_filename(&file_name) else {
+                continue;
+            };
+
+            let path = entry.path();
+            let size = file_size(&path) + file_size(&signature_path(&path));
+
+            packages.push(CachedPackage {
+                name,
+                version,
+                path,
+                size,
+            });
+        }
+    }
+
+    return packages;
+}
+
+pub fn get_cache_dir_usage(cache_dirs: &[PathBuf], cached: &[CachedPackage]) -> Vec<CacheDirUsage> {
+    return cache_dirs
+        .iter()
+        .map(|cache_dir| {
+            let files: Vec<&CachedPackage> = cached
+                .iter()
+                .filter(|package| package.path.parent() == Some(cache_dir.as_path()))
+                .collect();
+
+            CacheDirUsage {
+                path: cache_dir.clone(),
+                size: files.iter().map(|package| package.size).sum(),
+                files: files.len(),
+            }
+        })
+        .collect();
+}
+
+pub fn count_cached_versions(cached: &[CachedPackage]) -> BTreeMap<String, usize> {
+    let mut versions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for package in cached {
+        versions
+            .entry(package.name.clone())
+            .or_default()
+            .insert(package.version.clone());
+    }
+
+    return versions
+        .into_iter()
+        .map(|(name, versions)| (name, versions.len()))
+        .collect();
+}
+
+// Keeps the newest `keep_versions` versions of every package plus the installed one, like
+// `paccache -rk`. With `keep_versions` set to 0 this matches `pacman -Sc`.
+pub fn plan_cache_cleanup(
+    cached: &[CachedPackage],
+    installed: &BTreeMap<String, String>,
+    policy: &CacheCleanupPolicy,
+) -> Vec<CachedPackage> {
+    let mut by_name: BTreeMap<&str, Vec<&CachedPackage>> = BTreeMap::new();
+    for package in cached {
+        by_name.entry(&package.name).or_default().push(package);
+    }
+
+    let mut to_remove = Vec::new();
+
+    for (name, packages) in by_name {
+        let installed_version = installed.get(name);
+
+        let mut versions: Vec<&str> = packages.iter().map(|p| p.version.as_str()).collect();
+        versions.sort_by(|a, b| vercmp(b, a));
+        versions.dedup();
+
+        let kept: Vec<&str> = if installed_version.is_none() && policy.remove_uninstalled {
+            Vec::new()
+        } else {
+            versions
+                .iter()
+                .take(policy.keep_versions as usize)
+                .copied()
+                .collect()
+        };
+
+        for package in packages {
+            let keep = kept.contains(&package.version.as_str())
+                || installed_version == Some(&package.version)
+                || policy.pinned_files.contains(&package.path);
+
+            if !keep {
+                to_remove.push(package.clone());
+            }
+        }
+    }
+
+    return to_remove;
+}
+
+pub fn remove_cached_packages(packages: &[CachedPackage]) -> Result<u64> {
+    let mut freed = 0;
+
+    for package in packages {
+        fs::remove_file(&package.path)
+            .with_context(|| format!("Failed to remove {}", package.path.display()))?;
+        let _ = fs::remove_file(signature_path(&package.path));
+        freed += package.size;
+    }
+
+    return Ok(freed);
+}
+
+fn signature_path(path: &Path) -> PathBuf {
+    let mut signature = path.as_os_str().to_owned();
+    signature.push(".sig");
+    return PathBuf::from(signature);
+}
+
+fn file_size(path: &Path) -> u64 {
+    return fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(dir: &Path, name: &str, size: usize) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, vec![0u8; size]).unwrap();
+        return path;
+    }
+
+    fn list_sorted(cache_dirs: &[PathBuf]) -> Vec<CachedPackage> {
+        let mut packages = list_cached_packages_in(cache_dirs);
+        packages.sort_by(|a, b| a.path.cmp(&b.path));
+        return packages;
+    }
+
+    fn file_names(packages: &[CachedPackage]) -> Vec<String> {
+        let mut names: Vec<String> = packages
+            .iter()
+            .map(|p| p.path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        return names;
+    }
+
+    fn policy(keep_versions: u32, remove_uninstalled: bool) -> CacheCleanupPolicy {
+        return CacheCleanupPolicy {
+            keep_versions,
+            remove_uninstalled,
+            pinned_files: Vec::new(),
+        };
+    }
+
+    // Three versions of bash and firefox, one of an uninstalled package, spread over two caches.
+    fn populate_cache(root: &Path) -> Vec<PathBuf> {
+        let main = root.join("pkg");
+        let extra = root.join("extra");
+        fs::create_dir_all(&main).unwrap();
+        fs::create_dir_all(&extra).unwrap();
+
+        write_file(&main, "bash-5.2.015-1-x86_64.pkg.tar.zst", 100);
+        write_file(&main, "bash-5.2.015-1-x86_64.pkg.tar.zst.sig", 10);
+        write_file(&main, "bash-5.2.026-1-x86_64.pkg.tar.zst", 200);
+        write_file(&main, "bash-5.2.037-1-x86_64.pkg.tar.zst", 300);
+        write_file(&main, "firefox-128.0-1-x86_64.pkg.tar.zst", 1000);
+        write_file(&main, "firefox-129.0-1-x86_64.pkg.tar.zst.part", 500);
+        write_file(&extra, "firefox-127.0.2-1-x86_64.pkg.tar.zst", 900);
+        write_file(&extra, "firefox-129.0-2-x86_64.pkg.tar.zst", 1100);
+        write_file(&extra, "old-tool-1:0.3-4-any.pkg.tar.xz", 50);
+        write_file(&extra, "notes.txt", 5);
+
+        return vec![main, extra];
+    }
+
+    fn installed() -> BTreeMap<String, String> {
+        return BTreeMap::from([
+            ("bash".to_string(), "5.2.026-1".to_string()),
+            ("firefox".to_string(), "129.0-2".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn lists_packages_in_every_cache_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut cache_dirs = populate_cache(temp.path());
+        cache_dirs.push(temp.path().join("missing"));
+
+        let packages = list_sorted(&cache_dirs);
+
+        assert_eq!(
+            file_names(&packages),
+            [
+                "bash-5.2.015-1-x86_64.pkg.tar.zst",
+                "bash-5.2.026-1-x86_64.pkg.tar.zst",
+                "bash-5.2.037-1-x86_64.pkg.tar.zst",
+                "firefox-127.0.2-1-x86_64.pkg.tar.zst",
+                "firefox-128.0-1-x86_64.pkg.tar.zst",
+                "firefox-129.0-2-x86_64.pkg.tar.zst",
+                "old-tool-1:0.3-4-any.pkg.tar.xz",
+            ]
+        );
+
+        let bash = packages.iter().find(|p| p.version == "5.2.015-1").unwrap();
+        assert_eq!(bash.name, "bash");
+        assert_eq!(bash.size, 110);
+
+        let usage = get_cache_dir_usage(&cache_dirs, &packages);
+        assert_eq!(usage[0].files, 4);
+        assert_eq!(usage[0].size, 1610);
+        assert_eq!(usage[1].files, 3);
+        assert_eq!(usage[1].size, 2050);
+        assert_eq!(usage[2].files, 0);
+
+        let versions = count_cached_versions(&packages);
+        assert_eq!(versions["bash"], 3);
+        assert_eq!(versions["firefox"], 3);
+        assert_eq!(versions["old-tool"], 1);
+    }
+
+    #[test]
+    fn keeps_the_newest_versions_and_the_installed_one() {
+        let temp = tempfile::tempdir().unwrap();
+        let packages = list_sorted(&populate_cache(temp.path()));
+
+        let to_remove = plan_cache_cleanup(&packages, &installed(), &policy(1, false));
+
+        assert_eq!(
+            file_names(&to_remove),
+            [
+                "bash-5.2.015-1-x86_64.pkg.tar.zst",
+                "firefox-127.0.2-1-x86_64.pkg.tar.zst",
+                "firefox-128.0-1-x86_64.pkg.tar.zst",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeping_no_versions_keeps_only_installed_packages() {
+        let temp = tempfile::tempdir().unwrap();
+        let packages = list_sorted(&populate_cache(temp.path()));
+
+        let to_remove = plan_cache_cleanup(&packages, &installed(), &policy(0, false));
+
+        assert_eq!(
+            file_names(&to_remove),
+            [
+                "bash-5.2.015-1-x86_64.pkg.tar.zst",
+                "bash-5.2.037-1-x86_64.pkg.tar.zst",
+                "firefox-127.0.2-1-x86_64.pkg.tar.zst",
+                "firefox-128.0-1-x86_64.pkg.tar.zst",
+                "old-tool-1:0.3-4-any.pkg.tar.xz",
+            ]
+        );
+    }
+
+    #[test]
+    fn removes_uninstalled_packages_but_not_pinned_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let packages = list_sorted(&populate_cache(temp.path()));
+
+        let mut policy = policy(3, true);
+        assert_eq!(
+            file_names(&plan_cache_cleanup(&packages, &installed(), &policy)),
+            ["old-tool-1:0.3-4-any.pkg.tar.xz"]
+        );
+
+        policy.pinned_files = vec![temp.path().join("extra/old-tool-1:0.3-4-any.pkg.tar.xz")];
+        assert!(plan_cache_cleanup(&packages, &installed(), &policy).is_empty());
+    }
+
+    #[test]
+    fn removes_packages_with_their_signatures() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache_dirs = populate_cache(temp.path());
+        let packages = list_sorted(&cache_dirs);
+
+        let to_remove = plan_cache_cleanup(&packages, &installed(), &policy(2, true));
+        assert_eq!(remove_cached_packages(&to_remove).unwrap(), 110 + 900 + 50);
+
+        assert!(
+            !temp
+                .path()
+                .join("pkg/bash-5.2.015-1-x86_64.pkg.tar.zst")
+                .exists()
+        );
+        assert!(
+            !temp
+                .path()
+                .join("pkg/bash-5.2.015-1-x86_64.pkg.tar.zst.sig")
+                .exists()
+        );
+        assert!(temp.path().join("extra/notes.txt").exists());
+        assert_eq!(list_cached_packages_in(&cache_dirs).len(), 4);
+    }
+}
@@ -4,9 +4,11 @@ use std::collections::HashMap;
 use std::process::Command;
 
 use crate::helpers::aur::get_aur_updates;
+use crate::helpers::aur_rebuilds::find_aur_rebuilds;
 use crate::helpers::settings::load_settings;
 use crate::models::package_info::PackageInfo;
 use crate::models::package_update::PackageUpdate;
+use crate::models::update_check::UpdateCheck;
 use crate::models::update_error::UpdateError;
 
 impl std::fmt::Display for UpdateError {
@@ -33,7 +35,7 @@ impl From<anyhow::Error> for UpdateError {
     }
 }
 
-pub fn get_package_updates() -> Result<Vec<PackageUpdate>, UpdateError> {
+pub fn get_package_updates() -> Result<UpdateCheck, UpdateError> {
     let sync_output = Command::new("sudo")
         .args(&["pacman", "-Sy"])
         .output()
@@ -70,6 +72,7 @@ pub fn get_package_updates() -> Result<Vec<PackageUpdate>, UpdateError> {
 
     let re = Regex::new(r"^(\S+)\s+(\S+)\s+->\s+(\S+)").unwrap();
     let mut package_updates = Vec::new();
+    let mut warnings = Vec::new();
 
     for line in update_list.lines() {
         if line.trim().is_empty() {
@@ -83,7 +86,7 @@ pub fn get_package_updates() -> Result<Vec<PackageUpdate>, UpdateError> {
 
             package_updates.push((package_name, current_version, new_version));
         } else {
-            eprintln!("Warning: Could not parse update line: {}", line);
+            warnings.push(format!("Could not parse pacman output: {}", line.trim()));
         }
     }
 
@@ -126,6 +129,10 @@ pub fn get_package_updates() -> Result<Vec<PackageUpdate>, UpdateError> {
                 repository,
                 selected: true,
                 size,
+                aur_info: None,
+                pkgbuild_findings: Vec::new(),
+                rebuild_reason: None,
+                rebuild_for: Vec::new(),
             });
         }
     }
@@ -133,11 +140,21 @@ pub fn get_package_updates() -> Result<Vec<PackageUpdate>, UpdateError> {
     let settings = load_settings();
     if settings.enable_aur_support {
         match get_aur_updates() {
-            Ok(mut aur_updates) => {
-                updates.append(&mut aur_updates);
+            Ok(mut aur_check) => {
+                updates.append(&mut aur_check.updates);
+                warnings.append(&mut aur_check.warnings);
             }
             Err(e) => {
-                eprintln!("Warning: Failed to get AUR updates: {}", e);
+                warnings.push(format!("Failed to get AUR updates: {}", e));
+            }
+        }
+
+        match find_aur_rebuilds(&updates) {
+            Ok(mut rebuilds) => {
+                updates.append(&mut rebuilds);
+            }
+            Err(e) => {
+                warnings.push(format!("Failed to check AUR packages for rebuilds: {}", e));
             }
         }
     }
@@ -156,7 +173,7 @@ pub fn get_package_updates() -> Result<Vec<PackageUpdate>, UpdateError> {
         };
     });
 
-    return Ok(updates);
+    return Ok(UpdateCheck { updates, warnings });
 }
 
 fn get_batch_repository_info(
@@ -291,7 +308,7 @@ fn calculate_size_difference(current_size_str: &str, new_size_str: &str) -> i64
     }
 }
 
-fn parse_size_string(size_str: &str) -> Option<u64> {
+pub fn parse_size_string(size_str: &str) -> Option<u64> {
     let size_str = size_str.trim();
 
     if size_str == "Unknown" || size_str.is_empty() {
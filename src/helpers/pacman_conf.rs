@@ -0,0 +1,169 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+const PACMAN_CONF_PATH: &str = "/etc/pacman.conf";
+const DEFAULT_CACHE_DIR: &str = "/var/cache/pacman/pkg/";
+const DEFAULT_LOG_FILE: &str = "/var/log/pacman.log";
+
+pub fn get_cache_dirs() -> Vec<PathBuf> {
+    let content = fs::read_to_string(PACMAN_CONF_PATH).unwrap_or_default();
+    let cache_dirs = parse_pacman_conf_option(&content, "CacheDir");
+
+    if cache_dirs.is_empty() {
+        return vec![PathBuf::from(DEFAULT_CACHE_DIR)];
+    }
+
+    return cache_dirs.into_iter().map(PathBuf::from).collect();
+}
+
+pub fn get_log_file() -> PathBuf {
+    let content = fs::read_to_string(PACMAN_CONF_PATH).unwrap_or_default();
+
+    return parse_pacman_conf_option(&content, "LogFile")
+        .into_iter()
+        .last()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_LOG_FILE));
+}
+
+pub fn get_ignored_packages() -> Vec<String> {
+    let content = fs::read_to_string(PACMAN_CONF_PATH).unwrap_or_default();
+    return parse_pacman_conf_option(&content, "IgnorePkg");
+}
+
+pub fn add_ignored_package(package: &str) -> Result<()> {
+    let content = fs::read_to_string(PACMAN_CONF_PATH).context("Failed to read pacman.conf")?;
+
+    if parse_pacman_conf_option(&content, "IgnorePkg")
+        .iter()
+        .any(|ignored| ignored == package)
+    {
+        return Ok(());
+    }
+
+    let updated = insert_ignored_package(&content, package);
+    fs::write(PACMAN_CONF_PATH, updated).context("Failed to write pacman.conf")?;
+
+    return Ok(());
+}
+
+pub fn parse_pacman_conf_option(content: &str, option: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut in_options = false;
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            in_options = line == "[options]";
+            continue;
+        }
+
+        if !in_options {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        if key.trim() == option {
+            values.extend(value.split_whitespace().map(|v| v.to_string()));
+        }
+    }
+
+    return values;
+}
+
+fn insert_ignored_package(content: &str, package: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let mut in_options = false;
+    let mut options_header = None;
+    let mut commented_ignore = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            in_options = trimmed == "[options]";
+            if in_options {
+                options_header = Some(index);
+            }
+            continue;
+        }
+
+        if !in_options {
+            continue;
+        }
+
+        let (code, comment) = line.split_at(line.find('#').unwrap_or(line.len()));
+        if let Some((key, _)) = code.split_once('=') {
+            if key.trim() == "IgnorePkg" {
+                // Keep the indentation and any inline comment the user had on the line.
+                let values = code.trim_end();
+                let gap = &code[values.len()..];
+                let gap = if comment.is_empty() || !gap.is_empty() {
+                    gap
+                } else {
+                    " "
+                };
+                lines[index] = format!("{} {}{}{}", values, package, gap, comment);
+                return lines.join("\n") + "\n";
+            }
+        }
+
+        if commented_ignore.is_none()
+            && trimmed
+                .trim_start_matches('#')
+                .trim_start()
+                .starts_with("IgnorePkg")
+        {
+            commented_ignore = Some(index);
+        }
+    }
+
+    let new_line = format!("IgnorePkg   = {}", package);
+
+    match (commented_ignore, options_header) {
+        (Some(index), _) | (None, Some(index)) => lines.insert(index + 1, new_line),
+        (None, None) => {
+            lines.push("[options]".to_string());
+            lines.push(new_line);
+        }
+    }
+
+    return lines.join("\n") + "\n";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_to_an_existing_ignore_line() {
+        let content = "[options]\n    IgnorePkg = linux   # pinned kernel\nColor\n";
+
+        assert_eq!(
+            insert_ignored_package(content, "nvidia"),
+            "[options]\n    IgnorePkg = linux nvidia   # pinned kernel\nColor\n"
+        );
+    }
+
+    #[test]
+    fn adds_a_line_below_the_commented_example() {
+        let content =
+            "[options]\n#IgnorePkg   =\nColor\n\n[core]\nInclude = /etc/pacman.d/mirrorlist\n";
+
+        let updated = insert_ignored_package(content, "linux");
+
+        assert_eq!(
+            updated,
+            "[options]\n#IgnorePkg   =\nIgnorePkg   = linux\nColor\n\n[core]\nInclude = /etc/pacman.d/mirrorlist\n"
+        );
+        assert_eq!(parse_pacman_conf_option(&updated, "IgnorePkg"), ["linux"]);
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::{Context, Result, anyhow};
+use std::collections::{BTreeMap, HashMap};
+use std::process::Command;
+
+pub fn query_package_info(args: &[&str]) -> Result<Vec<HashMap<String, String>>> {
+    let output = Command::new("pacman")
+        .args(args)
+        .output()
+        .context(format!("Failed to run pacman {}", args.join(" ")))?;
+
+    return Ok(parse_package_info(&String::from_utf8_lossy(&output.stdout)));
+}
+
+pub fn parse_package_info(output: &str) -> Vec<HashMap<String, String>> {
+    let mut packages = Vec::new();
+    let mut current: HashMap<String, String> = HashMap::new();
+    let mut last_key: Option<String> = None;
+
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                packages.push(std::mem::take(&mut current));
+            }
+            last_key = None;
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if let Some(value) = last_key.as_ref().and_then(|key| current.get_mut(key)) {
+                value.push_str("  ");
+                value.push_str(line.trim());
+            }
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim().to_string();
+            current.insert(key.clone(), value.trim().to_string());
+            last_key = Some(key);
+        }
+    }
+
+    if !current.is_empty() {
+        packages.push(current);
+    }
+
+    return packages;
+}
+
+pub fn split_list_field(value: Option<&String>) -> Vec<String> {
+    let Some(value) = value else {
+        return Vec::new();
+    };
+
+    if value == "None" {
+        return Vec::new();
+    }
+
+    return value.split_whitespace().map(|v| v.to_string()).collect();
+}
+
+pub fn get_installed_packages() -> Result<BTreeMap<String, String>> {
+    let output = Command::new("pacman")
+        .arg("-Q")
+        .output()
+        .context("Failed to run pacman -Q")?;
+    if !output.status.success() {
+        return Err(anyhow!("pacman -Q failed"));
+    }
+
+    return Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (name, version) = line.split_once(' ')?;
+            Some((name.to_string(), version.trim().to_string()))
+        })
+        .collect());
+}
@@ -0,0 +1,427 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone};
+use regex::Regex;
+use std::fs;
+
+use crate::helpers::pacman_conf::get_log_file;
+use crate::helpers::snapshot_provider::get_configured_snapshot_provider;
+use crate::models::pacman_action::PacmanAction;
+use crate::models::pacman_action_kind::PacmanActionKind;
+use crate::models::pacman_transaction::PacmanTransaction;
+
+pub fn load_pacman_timeline() -> Result<Vec<PacmanTransaction>> {
+    let bytes = fs::read(get_log_file()).context("Failed to read pacman log")?;
+    let mut transactions = parse_pacman_log(&String::from_utf8_lossy(&bytes));
+
+    let provider = get_configured_snapshot_provider();
+    if provider.is_available() {
+        match provider.list() {
+            Ok(snapshots) => {
+                let snapshot_times: Vec<(String, DateTime<Local>)> = snapshots
+                    .into_iter()
+                    .filter_map(|snapshot| Some((snapshot.id, snapshot.created_at?)))
+                    .collect();
+                link_snapshots(&mut transactions, &snapshot_times);
+            }
+            Err(e) => eprintln!("Failed to list snapshots for the timeline: {}", e),
+        }
+    }
+
+    return Ok(transactions);
+}
+
+pub fn parse_pacman_log(content: &str) -> Vec<PacmanTransaction> {
+    let line_re = Regex::new(r"^\[([^\]]+)\]\s+(?:\[([A-Za-z-]+)\]\s+)?(.*)$").unwrap();
+    let action_re =
+        Regex::new(r"^(installed|upgraded|downgraded|reinstalled|removed) (\S+) \((.*)\)$")
+            .unwrap();
+
+    let mut transactions = Vec::new();
+    let mut current: Option<PacmanTransaction> = None;
+    let mut current_is_implicit = false;
+    let mut pending_command: Option<String> = None;
+    let mut pending_hooks: Vec<String> = Vec::new();
+    // Post-transaction hooks are logged after "transaction completed", so their lines still
+    // belong to the transaction that was just closed.
+    let mut in_post_hooks = false;
+
+    for line in content.lines() {
+        let Some(caps) = line_re.captures(line) else {
+            if let Some(transaction) =
+                current_transaction(&mut current, &mut transactions, in_post_hooks)
+            {
+                if !line.trim().is_empty() {
+                    transaction.output.push(line.trim_end().to_string());
+                }
+            }
+            continue;
+        };
+
+        let Some(time) = parse_log_timestamp(&caps[1]) else {
+            continue;
+        };
+        let source = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+        let message = caps[3].trim_end();
+
+        if let Some(command) = message
+            .strip_prefix("Running '")
+            .and_then(|m| m.strip_suffix('\''))
+        {
+            if let Some(transaction) = current.take() {
+                transactions.push(close_transaction(transaction, current_is_implicit));
+            }
+            pending_command = Some(command.to_string());
+            pending_hooks.clear();
+            in_post_hooks = false;
+            continue;
+        }
+
+        match message {
+            "transaction started" => {
+                if let Some(transaction) = current.take() {
+                    transactions.push(close_transaction(transaction, current_is_implicit));
+                }
+                let mut transaction = new_transaction(time, pending_command.take());
+                transaction.hooks.append(&mut pending_hooks);
+                current = Some(transaction);
+                current_is_implicit = false;
+                in_post_hooks = false;
+                continue;
+            }
+            "transaction completed" => {
+                if let Some(mut transaction) = current.take() {
+                    transaction.completed = true;
+                    transaction.finished_at = Some(time);
+                    transactions.push(transaction);
+                    in_post_hooks = true;
+                }
+                continue;
+            }
+            "transaction failed" | "transaction interrupted" => {
+                if let Some(mut transaction) = current.take() {
+                    transaction.finished_at = Some(time);
+                    transactions.push(transaction);
+                    in_post_hooks = true;
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let hook = message
+            .strip_prefix("running '")
+            .and_then(|m| m.strip_suffix("'..."));
+
+        if current.is_none() && !in_post_hooks {
+            // Pre-transaction hooks run before "transaction started" is logged.
+            if let Some(hook) = hook {
+                pending_hooks.push(hook.to_string());
+                continue;
+            }
+        }
+
+        if source.starts_with("ALPM-SCRIPTLET") || source.starts_with("ALPM-HOOK") {
+            if let Some(transaction) =
+                current_transaction(&mut current, &mut transactions, in_post_hooks)
+            {
+                transaction.output.push(message.to_string());
+            }
+            continue;
+        }
+
+        if let Some(action_caps) = action_re.captures(message) {
+            let Some(kind) = PacmanActionKind::from_verb(&action_caps[1]) else {
+                continue;
+            };
+
+            let starts_new_group = match current.as_ref() {
+                None => true,
+                Some(transaction) => {
+                    current_is_implicit
+                        && transaction
+                            .finished_at
+                            .is_some_and(|last| time - last > Duration::minutes(10))
+                }
+            };
+
+            if starts_new_group {
+                if let Some(transaction) = current.take() {
+                    transactions.push(close_transaction(transaction, current_is_implicit));
+                }
+                current = Some(new_transaction(time, pending_command.take()));
+                current_is_implicit = true;
+                in_post_hooks = false;
+            }
+
+            if let Some(transaction) = current.as_mut() {
+                transaction
+                    .actions
+                    .push(parse_action(kind, &action_caps[2], &action_caps[3]));
+                if current_is_implicit {
+                    transaction.finished_at = Some(time);
+                }
+            }
+            continue;
+        }
+
+        let Some(transaction) = current_transaction(&mut current, &mut transactions, in_post_hooks)
+        else {
+            continue;
+        };
+
+        if let Some(hook) = hook {
+            transaction.hooks.push(hook.to_string());
+        } else if message.starts_with("warning:") || message.starts_with("error:") {
+            transaction.warnings.push(message.to_string());
+        } else if !source.is_empty() && source != "PACMAN" {
+            transaction.output.push(message.to_string());
+        }
+    }
+
+    if let Some(transaction) = current.take() {
+        transactions.push(close_transaction(transaction, current_is_implicit));
+    }
+
+    transactions.retain(|transaction| !transaction.actions.is_empty());
+
+    return transactions;
+}
+
+pub fn link_snapshots(
+    transactions: &mut [PacmanTransaction],
+    snapshots: &[(String, DateTime<Local>)],
+) {
+    let mut sorted_snapshots: Vec<&(String, DateTime<Local>)> = snapshots.iter().collect();
+    sorted_snapshots.sort_by_key(|(_, time)| *time);
+
+    let mut previous_start: Option<DateTime<Local>> = None;
+
+    for transaction in transactions.iter_mut() {
+        transaction.snapshot_before = sorted_snapshots
+            .iter()
+            .rev()
+            .find(|(_, time)| {
+                *time <= transaction.started_at && previous_start.is_none_or(|start| *time > start)
+            })
+            .map(|(name, _)| name.clone());
+
+        previous_start = Some(transaction.started_at);
+    }
+}
+
+fn current_transaction<'a>(
+    current: &'a mut Option<PacmanTransaction>,
+    transactions: &'a mut [PacmanTransaction],
+    in_post_hooks: bool,
+) -> Option<&'a mut PacmanTransaction> {
+    return match current {
+        Some(transaction) => Some(transaction),
+        None if in_post_hooks => transactions.last_mut(),
+        None => None,
+    };
+}
+
+fn new_transaction(time: DateTime<Local>, command: Option<String>) -> PacmanTransaction {
+    return PacmanTransaction {
+        started_at: time,
+        finished_at: None,
+        command,
+        actions: Vec::new(),
+        warnings: Vec::new(),
+        hooks: Vec::new(),
+        output: Vec::new(),
+        completed: false,
+        snapshot_before: None,
+    };
+}
+
+fn close_transaction(mut transaction: PacmanTransaction, implicit: bool) -> PacmanTransaction {
+    if implicit {
+        transaction.completed = true;
+    }
+
+    return transaction;
+}
+
+fn parse_action(kind: PacmanActionKind, package: &str, versions: &str) -> PacmanAction {
+    let (old_version, new_version) = match versions.split_once(" -> ") {
+        Some((old, new)) => (Some(old.to_string()), Some(new.to_string())),
+        None => match kind {
+            PacmanActionKind::Removed => (Some(versions.to_string()), None),
+            _ => (None, Some(versions.to_string())),
+        },
+    };
+
+    return PacmanAction {
+        kind,
+        package: package.to_string(),
+        old_version,
+        new_version,
+    };
+}
+
+fn parse_log_timestamp(timestamp: &str) -> Option<DateTime<Local>> {
+    if let Ok(time) = DateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%z") {
+        return Some(time.with_timezone(&Local));
+    }
+
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(timestamp, format) {
+            return Local.from_local_datetime(&naive).earliest();
+        }
+    }
+
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn local_time(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Local> {
+        let naive = NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap();
+        return Local.from_local_datetime(&naive).earliest().unwrap();
+    }
+
+    fn utc_time(timestamp: &str) -> DateTime<Local> {
+        return DateTime::parse_from_rfc3339(timestamp)
+            .unwrap()
+            .with_timezone(&Local);
+    }
+
+    fn packages(transaction: &PacmanTransaction) -> Vec<&str> {
+        return transaction
+            .actions
+            .iter()
+            .map(|action| action.package.as_str())
+            .collect();
+    }
+
+    #[test]
+    fn parses_old_timestamps_without_transaction_markers() {
+        let transactions = parse_pacman_log(include_str!(
+            "../../tests/fixtures/pacman_log/old_format.log"
+        ));
+
+        assert_eq!(transactions.len(), 3);
+
+        assert_eq!(transactions[0].command, None);
+        assert_eq!(transactions[0].started_at, local_time(2013, 2, 10, 11, 5));
+        assert_eq!(packages(&transactions[0]), ["filesystem", "glibc"]);
+
+        let upgrade = &transactions[1];
+        assert_eq!(upgrade.command.as_deref(), Some("pacman -Syu"));
+        assert_eq!(upgrade.started_at, local_time(2014, 6, 20, 18, 3));
+        assert_eq!(upgrade.finished_at, Some(local_time(2014, 6, 20, 18, 4)));
+        assert!(upgrade.completed);
+        assert_eq!(packages(upgrade), ["linux", "openssl", "libgnome-keyring"]);
+        assert_eq!(upgrade.output.len(), 2);
+
+        let removed = &upgrade.actions[2];
+        assert_eq!(removed.kind, PacmanActionKind::Removed);
+        assert_eq!(removed.old_version.as_deref(), Some("3.12.0-1"));
+        assert_eq!(removed.new_version, None);
+
+        assert_eq!(transactions[2].command.as_deref(), Some("pacman -S vim"));
+        assert_eq!(packages(&transactions[2]), ["vim-runtime", "vim"]);
+    }
+
+    #[test]
+    fn parses_iso_timestamps_with_offsets() {
+        let transactions = parse_pacman_log(include_str!(
+            "../../tests/fixtures/pacman_log/iso_format.log"
+        ));
+
+        assert_eq!(transactions.len(), 2);
+
+        let upgrade = &transactions[0];
+        assert_eq!(upgrade.started_at, utc_time("2024-03-10T09:15:20+00:00"));
+        assert_eq!(
+            upgrade.finished_at,
+            Some(utc_time("2024-03-10T09:15:23+00:00"))
+        );
+        assert!(upgrade.completed);
+        assert_eq!(
+            packages(upgrade),
+            ["glibc", "linux", "python-packaging", "mesa"]
+        );
+        assert_eq!(
+            upgrade.actions[0].new_version.as_deref(),
+            Some("2.39+r17+gd5cb8c9c-1")
+        );
+        assert_eq!(upgrade.actions[2].kind, PacmanActionKind::Installed);
+        assert_eq!(upgrade.actions[3].kind, PacmanActionKind::Downgraded);
+        assert_eq!(
+            upgrade.actions[3].old_version.as_deref(),
+            Some("1:24.0.2-2")
+        );
+        assert_eq!(
+            upgrade.warnings,
+            ["warning: /etc/locale.gen installed as /etc/locale.gen.pacnew"]
+        );
+        assert_eq!(
+            upgrade.hooks,
+            [
+                "60-mkinitcpio-remove.hook",
+                "30-systemd-daemon-reload.hook",
+                "60-depmod.hook",
+                "90-mkinitcpio-install.hook",
+            ]
+        );
+        assert_eq!(upgrade.output.len(), 2);
+
+        let removal = &transactions[1];
+        assert_eq!(removal.command.as_deref(), Some("pacman -Rs gnome-keyring"));
+        assert_eq!(removal.started_at, utc_time("2024-03-10T09:02:43+00:00"));
+        assert!(removal.hooks.is_empty());
+    }
+
+    #[test]
+    fn marks_interrupted_transactions_as_incomplete() {
+        let transactions = parse_pacman_log(include_str!(
+            "../../tests/fixtures/pacman_log/interrupted.log"
+        ));
+
+        assert_eq!(transactions.len(), 3);
+
+        // The log simply stops when the machine loses power mid-transaction.
+        let cut_off = &transactions[0];
+        assert!(!cut_off.completed);
+        assert_eq!(cut_off.finished_at, None);
+        assert_eq!(packages(cut_off), ["systemd-libs", "systemd"]);
+
+        let interrupted = &transactions[1];
+        assert!(!interrupted.completed);
+        assert_eq!(
+            interrupted.finished_at,
+            Some(utc_time("2024-05-01T18:15:02+00:00"))
+        );
+        assert_eq!(interrupted.actions[0].kind, PacmanActionKind::Reinstalled);
+        assert_eq!(interrupted.warnings.len(), 1);
+
+        assert!(transactions[2].completed);
+        assert_eq!(packages(&transactions[2]), ["linux"]);
+    }
+
+    #[test]
+    fn links_the_latest_snapshot_before_each_transaction() {
+        let mut transactions = parse_pacman_log(include_str!(
+            "../../tests/fixtures/pacman_log/interrupted.log"
+        ));
+        let snapshots = vec![
+            ("early".to_string(), utc_time("2024-05-01T10:00:00+00:00")),
+            ("before".to_string(), utc_time("2024-05-01T18:00:05+00:00")),
+            ("retry".to_string(), utc_time("2024-05-01T18:16:00+00:00")),
+        ];
+
+        link_snapshots(&mut transactions, &snapshots);
+
+        assert_eq!(transactions[0].snapshot_before.as_deref(), Some("before"));
+        assert_eq!(transactions[1].snapshot_before, None);
+        assert_eq!(transactions[2].snapshot_before.as_deref(), Some("retry"));
+    }
+}
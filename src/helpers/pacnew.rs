@@ -0,0 +1,259 @@
+use anyhow::{Context, Result, anyhow};
+use std::cmp::Ordering;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempPath;
+
+use crate::helpers::downgrade::get_installed_version;
+use crate::helpers::package_cache::list_cached_packages;
+use crate::helpers::vercmp::vercmp;
+use crate::models::config_file_change::ConfigFileChange;
+use crate::models::config_merge::ConfigMerge;
+
+pub fn find_config_file_changes(root: &Path) -> Vec<ConfigFileChange> {
+    let mut changes = Vec::new();
+    collect_config_file_changes(&root.join("etc"), &mut changes);
+
+    let targets: Vec<PathBuf> = changes.iter().map(|change| change.target.clone()).collect();
+    let owners = find_file_owners(&targets);
+
+    for change in &mut changes {
+        change.package = owners
+            .iter()
+            .find(|(path, _)| *path == change.target)
+            .map(|(_, package)| package.clone());
+    }
+
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    return changes;
+}
+
+fn collect_config_file_changes(dir: &Path, changes: &mut Vec<ConfigFileChange>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_config_file_changes(&path, changes);
+            continue;
+        }
+        if !file_type.is_file() {
+            continue;
+        }
+
+        if let Some(change) = parse_config_file_change(&path) {
+            changes.push(change);
+        }
+    }
+}
+
+// Matches foo.conf.pacnew, foo.conf.pacsave and the numbered foo.conf.pacsave.1 pacman
+// creates when a .pacsave already exists.
+pub fn parse_config_file_change(path: &Path) -> Option<ConfigFileChange> {
+    let file_name = path.file_name()?.to_string_lossy().to_string();
+
+    let (target_name, is_pacsave) = if let Some(name) = file_name.strip_suffix(".pacnew") {
+        (name.to_string(), false)
+    } else if let Some((name, suffix)) = file_name.rsplit_once(".pacsave") {
+        let numbered = suffix.is_empty()
+            || suffix
+                .strip_prefix('.')
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        if !numbered {
+            return None;
+        }
+        (name.to_string(), true)
+    } else {
+        return None;
+    };
+
+    if target_name.is_empty() {
+        return None;
+    }
+
+    return Some(ConfigFileChange {
+        path: path.to_path_buf(),
+        target: path.with_file_name(target_name),
+        is_pacsave,
+        package: None,
+    });
+}
+
+fn find_file_owners(paths: &[PathBuf]) -> Vec<(PathBuf, String)> {
+    let existing: Vec<&PathBuf> = paths.iter().filter(|path| path.exists()).collect();
+    if existing.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok(output) = Command::new("pacman").arg("-Qo").args(&existing).output() else {
+        return Vec::new();
+    };
+
+    return String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (path, owner) = line.split_once(" is owned by ")?;
+            let package = owner.split_whitespace().next()?;
+            Some((PathBuf::from(path.trim()), package.to_string()))
+        })
+        .collect();
+}
+
+pub fn load_config_merge(change: &ConfigFileChange) -> Result<ConfigMerge> {
+    let side_file = fs::read_to_string(&change.path)
+        .with_context(|| format!("Failed to read {}", change.path.display()))?;
+    let target = fs::read_to_string(&change.target).ok();
+
+    // A .pacsave holds the user's old file, a .pacnew the package's new default.
+    let (mine, new) = if change.is_pacsave {
+        (side_file, target)
+    } else {
+        let mine = target.ok_or_else(|| {
+            anyhow!(
+                "{} no longer exists, take the new file instead",
+                change.target.display()
+            )
+        })?;
+        (mine, Some(side_file))
+    };
+
+    let original = if change.is_pacsave {
+        None
+    } else {
+        change
+            .package
+            .as_deref()
+            .and_then(|package| extract_original_file(package, &change.target))
+    };
+
+    let (merged, has_conflicts) = match (&new, &original) {
+        (Some(new), Some(original)) => merge_three_way(&mine, original, new)?,
+        _ => (mine.clone(), false),
+    };
+
+    return Ok(ConfigMerge {
+        mine,
+        new,
+        original,
+        merged,
+        has_conflicts,
+    });
+}
+
+// The pristine file comes from the newest cached version older than the installed one, the
+// version the user's file was based on before the update.
+fn extract_original_file(package: &str, target: &Path) -> Option<String> {
+    let installed_version = get_installed_version(package)?;
+
+    let previous = list_cached_packages()
+        .into_iter()
+        .filter(|cached| cached.name == package)
+        .filter(|cached| vercmp(&cached.version, &installed_version) == Ordering::Less)
+        .max_by(|a, b| vercmp(&a.version, &b.version))?;
+
+    let member = target.to_string_lossy().trim_start_matches('/').to_string();
+    let output = Command::new("bsdtar")
+        .arg("-xOf")
+        .arg(&previous.path)
+        .arg(&member)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    return Some(String::from_utf8_lossy(&output.stdout).to_string());
+}
+
+pub fn merge_three_way(mine: &str, original: &str, new: &str) -> Result<(String, bool)> {
+    // Only the current user can enter the directory, and it is removed when dropped.
+    let dir = tempfile::Builder::new()
+        .prefix("arch-update-manager-merge-")
+        .tempdir()
+        .context("Failed to create merge directory")?;
+
+    let mine_path = dir.path().join("mine");
+    let original_path = dir.path().join("original");
+    let new_path = dir.path().join("new");
+    fs::write(&mine_path, mine)?;
+    fs::write(&original_path, original)?;
+    fs::write(&new_path, new)?;
+
+    let output = Command::new("diff3")
+        .args(["-m", "-L", "yours", "-L", "original", "-L", "new"])
+        .arg(&mine_path)
+        .arg(&original_path)
+        .arg(&new_path)
+        .output()
+        .context("Failed to run diff3")?;
+
+    // diff3 exits with 1 when the merge has conflicts and 2 on trouble.
+    return match output.status.code() {
+        Some(0) => Ok((String::from_utf8_lossy(&output.stdout).to_string(), false)),
+        Some(1) => Ok((String::from_utf8_lossy(&output.stdout).to_string(), true)),
+        _ => Err(anyhow!(
+            "diff3 failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    };
+}
+
+// Files under /etc belong to root, so the change is applied with sudo from the terminal page.
+// The returned file holds the new content and must live until that command has finished.
+pub fn build_resolve_command(
+    change: &ConfigFileChange,
+    content: Option<&str>,
+) -> Result<(Vec<String>, Option<TempPath>)> {
+    let side_file = change.path.to_string_lossy().to_string();
+
+    let Some(content) = content else {
+        return Ok((
+            vec![
+                "sudo".to_string(),
+                "rm".to_string(),
+                "-f".to_string(),
+                "--".to_string(),
+                side_file,
+            ],
+            None,
+        ));
+    };
+
+    let mut file = tempfile::Builder::new()
+        .prefix("arch-update-manager-config-")
+        .tempfile()
+        .context("Failed to create temporary file")?;
+    file.write_all(content.as_bytes())
+        .context("Failed to write temporary file")?;
+    let content_path = file.into_temp_path();
+
+    // Writing through the existing file keeps its owner and mode. A target that is gone
+    // first gets the side file's.
+    let script = r#"set -e
+if [ ! -e "$2" ]; then cp -p -- "$3" "$2"; fi
+cat -- "$1" > "$2"
+rm -f -- "$3""#;
+
+    return Ok((
+        vec![
+            "sudo".to_string(),
+            "sh".to_string(),
+            "-c".to_string(),
+            script.to_string(),
+            "sh".to_string(),
+            content_path.to_string_lossy().to_string(),
+            change.target.to_string_lossy().to_string(),
+            side_file,
+        ],
+        Some(content_path),
+    ));
+}
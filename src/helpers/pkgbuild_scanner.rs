@@ -0,0 +1,529 @@
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::LazyLock;
+
+use crate::models::pkgbuild_finding::PkgbuildFinding;
+use crate::models::pkgbuild_risk_level::PkgbuildRiskLevel;
+
+struct LineRule {
+    id: &'static str,
+    level: PkgbuildRiskLevel,
+    title: &'static str,
+    pattern: &'static str,
+}
+
+const LINE_RULES: [LineRule; 6] = [
+    LineRule {
+        id: "pipe-to-shell",
+        level: PkgbuildRiskLevel::Danger,
+        title: "Pipes a download into a shell",
+        pattern: r"\b(?:curl|wget)\b[^|#]*\|\s*(?:sudo\s+)?(?:ba|da|k|z)?sh\b",
+    },
+    LineRule {
+        id: "eval-download",
+        level: PkgbuildRiskLevel::Danger,
+        title: "Runs downloaded code",
+        pattern: r#"\b(?:eval|source|\.)\s+["']?(?:\$\(|<\()\s*(?:curl|wget)\b"#,
+    },
+    LineRule {
+        id: "sudo",
+        level: PkgbuildRiskLevel::Danger,
+        title: "Uses sudo",
+        pattern: r"(?:^|[\s;&|(`])sudo\b",
+    },
+    LineRule {
+        id: "base64-decode",
+        level: PkgbuildRiskLevel::Danger,
+        title: "Decodes base64 data",
+        pattern: r"\bbase64\s+(?:-[a-z]*d[a-z]*\b|--decode)",
+    },
+    LineRule {
+        id: "insecure-download",
+        level: PkgbuildRiskLevel::Warning,
+        title: "Downloads without HTTPS",
+        pattern: r"\b(?:curl|wget)\b[^#]*\b(?:http|ftp)://",
+    },
+    LineRule {
+        id: "raw-ip-download",
+        level: PkgbuildRiskLevel::Warning,
+        title: "Downloads from a raw IP address",
+        pattern: r"\b(?:curl|wget)\b[^#]*://\d{1,3}(?:\.\d{1,3}){3}\b",
+    },
+];
+
+static LINE_RULE_REGEXES: LazyLock<Vec<(&LineRule, Regex)>> = LazyLock::new(|| {
+    return LINE_RULES
+        .iter()
+        .map(|rule| (rule, Regex::new(rule.pattern).unwrap()))
+        .collect();
+});
+
+static BLOB_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z0-9+/]{100,}={0,2}").unwrap());
+
+static ARRAY_START_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*([A-Za-z_][A-Za-z0-9_]*)=\(").unwrap());
+
+static REDIRECT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    return Regex::new(r#"(?:^|[^0-9&<>])>>?\s*["']?(/[^\s"';|&)]*)"#).unwrap();
+});
+
+const VCS_PREFIXES: [&str; 6] = ["git+", "svn+", "hg+", "bzr+", "fossil+", "git://"];
+
+const WRITE_COMMANDS: [&str; 10] = [
+    "install", "cp", "mv", "ln", "mkdir", "touch", "tee", "rm", "chmod", "chown",
+];
+
+pub fn scan_pkgbuild(pkgbuild: &str) -> Vec<PkgbuildFinding> {
+    let mut findings = Vec::new();
+
+    for (index, line) in pkgbuild.lines().enumerate() {
+        let code = line.trim();
+        if code.is_empty() || code.starts_with('#') {
+            continue;
+        }
+
+        for (rule, re) in LINE_RULE_REGEXES.iter() {
+            if re.is_match(code) {
+                findings.push(finding(
+                    rule.id,
+                    rule.level,
+                    rule.title,
+                    Some(index + 1),
+                    code,
+                ));
+            }
+        }
+
+        // Checksums are lowercase hex, so only mixed-case runs look like encoded payloads.
+        if BLOB_REGEX.find_iter(code).any(|blob| {
+            let blob = blob.as_str();
+            blob.chars().any(|c| c.is_ascii_uppercase())
+                && blob.chars().any(|c| c.is_ascii_lowercase())
+        }) {
+            findings.push(finding(
+                "base64-blob",
+                PkgbuildRiskLevel::Warning,
+                "Contains an encoded blob",
+                Some(index + 1),
+                &truncate(code, 80),
+            ));
+        }
+
+        if let Some(target) = find_write_outside_pkgdir(code) {
+            findings.push(finding(
+                "write-outside-pkgdir",
+                PkgbuildRiskLevel::Danger,
+                "Writes outside $pkgdir",
+                Some(index + 1),
+                &format!("{}  ({})", code, target),
+            ));
+        }
+    }
+
+    let arrays = parse_arrays(pkgbuild);
+
+    for (name, sources) in arrays.iter().filter(|(name, _)| is_source_array(name)) {
+        for source in sources {
+            let url = source_url(source);
+
+            if url.starts_with("http://")
+                || url.starts_with("ftp://")
+                || url.starts_with("git://")
+                || url.starts_with("git+http://")
+            {
+                findings.push(finding(
+                    "insecure-source",
+                    PkgbuildRiskLevel::Warning,
+                    "Downloads without HTTPS",
+                    None,
+                    &format!("{}: {}", name, url),
+                ));
+            }
+
+            if host_of(url).is_some_and(is_ip_address) {
+                findings.push(finding(
+                    "raw-ip-source",
+                    PkgbuildRiskLevel::Warning,
+                    "Downloads from a raw IP address",
+                    None,
+                    &format!("{}: {}", name, url),
+                ));
+            }
+        }
+    }
+
+    for (name, sums) in arrays.iter().filter(|(name, _)| is_checksum_array(name)) {
+        let suffix = name
+            .split_once("sums")
+            .map(|(_, suffix)| suffix)
+            .unwrap_or("");
+        let Some(sources) = arrays.get(&format!("source{}", suffix)) else {
+            continue;
+        };
+
+        for (sum, source) in sums.iter().zip(sources) {
+            let url = source_url(source);
+            let is_vcs = VCS_PREFIXES.iter().any(|prefix| url.starts_with(prefix));
+
+            if sum == "SKIP" && url.contains("://") && !is_vcs {
+                findings.push(finding(
+                    "skipped-checksum",
+                    PkgbuildRiskLevel::Warning,
+                    "Skips checksum verification",
+                    None,
+                    &format!("{}: {}", name, url),
+                ));
+            }
+        }
+    }
+
+    return findings;
+}
+
+// Scans the new PKGBUILD and compares its sources with the last reviewed one.
+pub fn scan_pkgbuild_update(previous: Option<&str>, pkgbuild: &str) -> Vec<PkgbuildFinding> {
+    let mut findings = scan_pkgbuild(pkgbuild);
+
+    let Some(previous) = previous else {
+        return findings;
+    };
+
+    let old_sources = collect_sources(previous);
+    let new_sources = collect_sources(pkgbuild);
+    let old_hosts: BTreeSet<&str> = old_sources.iter().filter_map(|s| host_of(s)).collect();
+
+    for source in new_sources.difference(&old_sources) {
+        if let Some(host) = host_of(source).filter(|host| !old_hosts.contains(host)) {
+            findings.push(finding(
+                "new-source-host",
+                PkgbuildRiskLevel::Warning,
+                "Downloads from a new host",
+                None,
+                &format!("{} ({})", source, host),
+            ));
+        } else {
+            findings.push(finding(
+                "source-changed",
+                PkgbuildRiskLevel::Notice,
+                "Source URLs changed",
+                None,
+                source,
+            ));
+        }
+    }
+
+    return findings;
+}
+
+// Reads `name=(...)` arrays, including ones spread over several lines. Values are split like the
+// shell would, but variables are left unexpanded.
+pub fn parse_arrays(pkgbuild: &str) -> BTreeMap<String, Vec<String>> {
+    let mut arrays = BTreeMap::new();
+
+    for caps in ARRAY_START_REGEX.captures_iter(pkgbuild) {
+        let whole = caps.get(0).unwrap();
+        let body = &pkgbuild[whole.end()..];
+
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        let mut end = body.len();
+        for (index, c) in body.char_indices() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            match (quote, c) {
+                (_, '\\') if quote != Some('\'') => escaped = true,
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(open), _) if c == open => quote = None,
+                (None, ')') => {
+                    end = index;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let values = shlex::split(&body[..end]).unwrap_or_else(|| {
+            body[..end]
+                .split_whitespace()
+                .map(|v| v.to_string())
+                .collect()
+        });
+        arrays.insert(caps[1].to_string(), values);
+    }
+
+    return arrays;
+}
+
+fn collect_sources(pkgbuild: &str) -> BTreeSet<String> {
+    return parse_arrays(pkgbuild)
+        .into_iter()
+        .filter(|(name, _)| is_source_array(name))
+        .flat_map(|(_, sources)| sources)
+        .map(|source| source_url(&source).to_string())
+        .collect();
+}
+
+fn find_write_outside_pkgdir(code: &str) -> Option<String> {
+    for caps in REDIRECT_REGEX.captures_iter(code) {
+        if is_system_path(&caps[1]) {
+            return Some(caps[1].to_string());
+        }
+    }
+
+    let words: Vec<String> = shlex::split(code)
+        .unwrap_or_else(|| code.split_whitespace().map(|w| w.to_string()).collect());
+
+    for (index, word) in words.iter().enumerate() {
+        if !WRITE_COMMANDS.contains(&word.as_str()) {
+            continue;
+        }
+        if index > 0 {
+            let previous = words[index - 1].as_str();
+            if !previous.ends_with(';')
+                && !matches!(previous, "&&" | "||" | "|" | "then" | "do" | "else" | "{")
+            {
+                continue;
+            }
+        }
+
+        let arguments: Vec<&String> = words[index + 1..]
+            .iter()
+            .take_while(|word| !matches!(word.as_str(), "&&" | "||" | ";" | "|"))
+            .filter(|word| !word.starts_with('-'))
+            .collect();
+
+        // Only the destination matters for copies; every operand is a target for the rest.
+        let targets: Vec<&String> = if matches!(word.as_str(), "install" | "cp" | "mv" | "ln") {
+            arguments.last().copied().into_iter().collect()
+        } else {
+            arguments
+        };
+
+        if let Some(target) = targets.into_iter().find(|target| is_system_path(target)) {
+            return Some(target.clone());
+        }
+    }
+
+    return None;
+}
+
+fn is_system_path(path: &str) -> bool {
+    return path.starts_with('/')
+        && !path.starts_with("/dev/")
+        && !path.starts_with("/tmp/")
+        && path != "/tmp";
+}
+
+fn is_source_array(name: &str) -> bool {
+    return name == "source" || name.starts_with("source_");
+}
+
+fn is_checksum_array(name: &str) -> bool {
+    let Some((algorithm, suffix)) = name.split_once("sums") else {
+        return false;
+    };
+
+    return matches!(
+        algorithm,
+        "md5" | "sha1" | "sha224" | "sha256" | "sha384" | "sha512" | "b2" | "ck"
+    ) && (suffix.is_empty() || suffix.starts_with('_'));
+}
+
+fn source_url(source: &str) -> &str {
+    return source
+        .split_once("::")
+        .map(|(_, url)| url)
+        .unwrap_or(source);
+}
+
+fn host_of(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = if host.starts_with('[') {
+        host.split_inclusive(']').next()?
+    } else {
+        host.split(':').next()?
+    };
+
+    return if host.is_empty() { None } else { Some(host) };
+}
+
+fn is_ip_address(host: &str) -> bool {
+    return host.starts_with('[') || host.parse::<std::net::Ipv4Addr>().is_ok();
+}
+
+fn truncate(text: &str, length: usize) -> String {
+    if text.chars().count() <= length {
+        return text.to_string();
+    }
+
+    return format!("{}…", text.chars().take(length).collect::<String>());
+}
+
+fn finding(
+    rule: &str,
+    level: PkgbuildRiskLevel,
+    title: &str,
+    line: Option<usize>,
+    detail: &str,
+) -> PkgbuildFinding {
+    return PkgbuildFinding {
+        rule: rule.to_string(),
+        level,
+        title: title.to_string(),
+        line,
+        detail: detail.to_string(),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(findings: &[PkgbuildFinding]) -> Vec<&str> {
+        return findings.iter().map(|f| f.rule.as_str()).collect();
+    }
+
+    #[test]
+    fn clean_pkgbuild_has_no_findings() {
+        let pkgbuild = include_str!("../../tests/fixtures/pkgbuild_scanner/clean.PKGBUILD");
+
+        assert_eq!(scan_pkgbuild(pkgbuild), []);
+    }
+
+    #[test]
+    fn flags_downloads_piped_into_a_shell() {
+        let pkgbuild = include_str!("../../tests/fixtures/pkgbuild_scanner/pipe_to_shell.PKGBUILD");
+        let findings = scan_pkgbuild(pkgbuild);
+
+        assert_eq!(rules(&findings), ["pipe-to-shell", "eval-download"]);
+        assert_eq!(findings[0].line, Some(10));
+        assert_eq!(findings[0].level, PkgbuildRiskLevel::Danger);
+        assert_eq!(findings[1].line, Some(11));
+    }
+
+    #[test]
+    fn flags_insecure_and_raw_ip_sources() {
+        let pkgbuild =
+            include_str!("../../tests/fixtures/pkgbuild_scanner/insecure_sources.PKGBUILD");
+        let findings = scan_pkgbuild(pkgbuild);
+
+        assert_eq!(
+            rules(&findings),
+            [
+                "insecure-download",
+                "raw-ip-download",
+                "insecure-source",
+                "raw-ip-source",
+                "skipped-checksum",
+            ]
+        );
+        assert_eq!(findings[0].line, Some(13));
+        assert_eq!(findings[1].line, Some(14));
+        assert_eq!(
+            findings[2].detail,
+            "source: http://downloads.example.org/legacy-fetch-$pkgver.tar.gz"
+        );
+        assert_eq!(
+            findings[3].detail,
+            "source: https://203.0.113.7/patches/extra.patch"
+        );
+        assert_eq!(
+            findings[4].detail,
+            "sha256sums: https://203.0.113.7/patches/extra.patch"
+        );
+    }
+
+    #[test]
+    fn flags_base64_blobs_and_decoding() {
+        let pkgbuild = include_str!("../../tests/fixtures/pkgbuild_scanner/base64_blob.PKGBUILD");
+        let findings = scan_pkgbuild(pkgbuild);
+
+        assert_eq!(rules(&findings), ["base64-blob", "base64-decode"]);
+        assert_eq!(findings[0].line, Some(9));
+        assert!(findings[0].detail.ends_with('…'));
+        assert_eq!(findings[1].line, Some(10));
+    }
+
+    #[test]
+    fn flags_writes_outside_pkgdir() {
+        let pkgbuild =
+            include_str!("../../tests/fixtures/pkgbuild_scanner/write_outside_pkgdir.PKGBUILD");
+        let findings = scan_pkgbuild(pkgbuild);
+
+        assert_eq!(
+            rules(&findings),
+            ["write-outside-pkgdir", "write-outside-pkgdir"]
+        );
+        assert_eq!(findings[0].line, Some(10));
+        assert!(
+            findings[0]
+                .detail
+                .ends_with("(/usr/lib/systemd/system/sneaky.service)")
+        );
+        assert_eq!(findings[1].line, Some(11));
+        assert!(findings[1].detail.ends_with("(/etc/profile)"));
+    }
+
+    #[test]
+    fn flags_sudo() {
+        let pkgbuild = include_str!("../../tests/fixtures/pkgbuild_scanner/sudo.PKGBUILD");
+        let findings = scan_pkgbuild(pkgbuild);
+
+        assert_eq!(rules(&findings), ["sudo"]);
+        assert_eq!(findings[0].line, Some(10));
+    }
+
+    #[test]
+    fn flags_skipped_checksums_of_downloads_only() {
+        let pkgbuild =
+            include_str!("../../tests/fixtures/pkgbuild_scanner/skip_checksums.PKGBUILD");
+        let findings = scan_pkgbuild(pkgbuild);
+
+        assert_eq!(rules(&findings), ["skipped-checksum", "skipped-checksum"]);
+        assert_eq!(
+            findings[0].detail,
+            "sha256sums: https://example.org/unchecked-$pkgver.tar.gz"
+        );
+        assert_eq!(
+            findings[1].detail,
+            "sha256sums_x86_64: https://example.org/unchecked-bin-$pkgver-x86_64.tar.gz"
+        );
+    }
+
+    #[test]
+    fn reports_changed_sources_and_new_hosts() {
+        let previous =
+            include_str!("../../tests/fixtures/pkgbuild_scanner/source_change_old.PKGBUILD");
+        let pkgbuild =
+            include_str!("../../tests/fixtures/pkgbuild_scanner/source_change_new.PKGBUILD");
+        let findings = scan_pkgbuild_update(Some(previous), pkgbuild);
+
+        assert_eq!(rules(&findings), ["new-source-host", "source-changed"]);
+        assert_eq!(
+            findings[0].detail,
+            "https://cdn.example.net/moving-target/extras.tar.gz (cdn.example.net)"
+        );
+        assert_eq!(findings[0].level, PkgbuildRiskLevel::Warning);
+        assert_eq!(
+            findings[1].detail,
+            "https://github.com/example/moving-target/releases/download/v$pkgver/plugins.tar.gz"
+        );
+        assert_eq!(findings[1].level, PkgbuildRiskLevel::Notice);
+
+        assert_eq!(scan_pkgbuild_update(None, pkgbuild), []);
+    }
+
+    #[test]
+    fn parses_multiline_arrays() {
+        let arrays =
+            parse_arrays("source=(\"a b.tar.gz\"\n        'c' # comment\n        d)\narch=(any)\n");
+
+        assert_eq!(arrays["source"], ["a b.tar.gz", "c", "d"]);
+        assert_eq!(arrays["arch"], ["any"]);
+    }
+}
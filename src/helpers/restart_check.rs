@@ -0,0 +1,360 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use shlex::try_quote as quote;
+
+use crate::models::restart_report::RestartReport;
+use crate::models::stale_process::StaleProcess;
+
+// Restarting these ends the graphical session or the system bus, a reboot is the safer choice.
+const UNSAFE_TO_RESTART: &[&str] = &[
+    "dbus.service",
+    "dbus-broker.service",
+    "display-manager.service",
+    "gdm.service",
+    "sddm.service",
+    "lightdm.service",
+    "ly.service",
+    "systemd-logind.service",
+    "systemd-journald.service",
+];
+
+pub fn check_restart_needed(root: &Path) -> RestartReport {
+    let running_kernel = fs::read_to_string(root.join("proc/sys/kernel/osrelease"))
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    let kernel_missing =
+        !running_kernel.is_empty() && !root.join("usr/lib/modules").join(&running_kernel).exists();
+
+    return RestartReport {
+        kernel_missing,
+        running_kernel,
+        processes: find_stale_processes(root),
+    };
+}
+
+pub fn find_stale_processes(root: &Path) -> Vec<StaleProcess> {
+    let Ok(entries) = fs::read_dir(root.join("proc")) else {
+        return Vec::new();
+    };
+
+    let mut processes: Vec<StaleProcess> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_string_lossy().parse().ok()?;
+            let maps = fs::read_to_string(entry.path().join("maps")).ok()?;
+            let libraries = parse_deleted_mappings(&maps);
+            if libraries.is_empty() {
+                return None;
+            }
+
+            let command = fs::read_to_string(entry.path().join("comm"))
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            let cgroup = fs::read_to_string(entry.path().join("cgroup")).unwrap_or_default();
+            let (unit, user_id) = match parse_cgroup_unit(&cgroup) {
+                Some((unit, user_id)) => (Some(unit), user_id),
+                None => (None, None),
+            };
+
+            Some(StaleProcess {
+                pid,
+                command,
+                libraries,
+                unit,
+                user_id,
+            })
+        })
+        .collect();
+
+    processes.sort_by_key(|process| process.pid);
+
+    return processes;
+}
+
+pub fn parse_deleted_mappings(maps: &str) -> Vec<String> {
+    let libraries: BTreeSet<String> = maps
+        .lines()
+        .filter_map(|line| {
+            let path = line.splitn(6, char::is_whitespace).nth(5)?.trim();
+            let path = path.strip_suffix(" (deleted)")?;
+
+            // Shared memory and memfd mappings are also reported as deleted.
+            let system_file = ["/usr/", "/lib", "/opt/"]
+                .iter()
+                .any(|prefix| path.starts_with(prefix));
+            if !system_file {
+                return None;
+            }
+
+            Some(path.to_string())
+        })
+        .collect();
+
+    return libraries.into_iter().collect();
+}
+
+// Returns the service a process belongs to and, for user services, the owning uid.
+pub fn parse_cgroup_unit(cgroup: &str) -> Option<(String, Option<u32>)> {
+    let path = cgroup
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .or_else(|| {
+            cgroup
+                .lines()
+                .find_map(|line| line.split(":name=systemd:").nth(1))
+        })?;
+
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+
+    let user_manager = components
+        .iter()
+        .position(|component| component.starts_with("user@") && component.ends_with(".service"));
+
+    if let Some(index) = user_manager {
+        let user_id = components[index]
+            .trim_start_matches("user@")
+            .trim_end_matches(".service")
+            .parse()
+            .ok()?;
+
+        let unit = components[index + 1..]
+            .iter()
+            .rev()
+            .find(|component| component.ends_with(".service"))?;
+
+        return Some((unit.to_string(), Some(user_id)));
+    }
+
+    let unit = components
+        .iter()
+        .rev()
+        .find(|component| component.ends_with(".service"))?;
+
+    return Some((unit.to_string(), None));
+}
+
+pub fn is_restartable(process: &StaleProcess) -> bool {
+    let Some(unit) = &process.unit else {
+        return false;
+    };
+
+    return process.user_id.is_some() || !UNSAFE_TO_RESTART.contains(&unit.as_str());
+}
+
+pub fn build_restart_command(processes: &[StaleProcess]) -> Option<String> {
+    let mut system_units = BTreeSet::new();
+    let mut user_units: BTreeSet<(u32, String)> = BTreeSet::new();
+
+    for process in processes.iter().filter(|p| is_restartable(p)) {
+        let Some(unit) = process.unit.clone() else {
+            continue;
+        };
+
+        match process.user_id {
+            Some(user_id) => {
+                user_units.insert((user_id, unit));
+            }
+            None => {
+                system_units.insert(unit);
+            }
+        }
+    }
+
+    let mut commands = Vec::new();
+
+    if !system_units.is_empty() {
+        commands.push(format!(
+            "systemctl restart {}",
+            quote_units(system_units.iter())
+        ));
+    }
+
+    let user_ids: BTreeSet<u32> = user_units.iter().map(|(user_id, _)| *user_id).collect();
+    for user_id in user_ids {
+        let units = user_units
+            .iter()
+            .filter(|(id, _)| *id == user_id)
+            .map(|(_, unit)| unit);
+        commands.push(format!(
+            "systemctl --user --machine=\"$(id -nu {})\"@.host restart {}",
+            user_id,
+            quote_units(units)
+        ));
+    }
+
+    if commands.is_empty() {
+        return None;
+    }
+
+    return Some(commands.join("; "));
+}
+
+fn quote_units<'a>(units: impl Iterator<Item = &'a String>) -> String {
+    return units
+        .filter_map(|unit| quote(unit).ok().map(|q| q.into_owned()))
+        .collect::<Vec<_>>()
+        .join(" ");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_process(root: &Path, pid: &str, comm: &str, maps: &str, cgroup: &str) {
+        let dir = root.join("proc").join(pid);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("comm"), format!("{comm}\n")).unwrap();
+        fs::write(dir.join("maps"), maps).unwrap();
+        fs::write(dir.join("cgroup"), cgroup).unwrap();
+    }
+
+    fn fake_root() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        add_process(
+            root.path(),
+            "812",
+            "sshd",
+            include_str!("../../tests/fixtures/restart_check/sshd.maps"),
+            "0::/system.slice/sshd.service\n",
+        );
+        add_process(
+            root.path(),
+            "2204",
+            "firefox",
+            include_str!("../../tests/fixtures/restart_check/firefox.maps"),
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox.scope\n",
+        );
+        add_process(
+            root.path(),
+            "1950",
+            "bash",
+            include_str!("../../tests/fixtures/restart_check/bash.maps"),
+            "0::/user.slice/user-1000.slice/session-2.scope\n",
+        );
+        // /proc/self and friends are not processes of their own.
+        add_process(
+            root.path(),
+            "self",
+            "sshd",
+            include_str!("../../tests/fixtures/restart_check/sshd.maps"),
+            "",
+        );
+        return root;
+    }
+
+    #[test]
+    fn finds_processes_mapping_deleted_system_files() {
+        let root = fake_root();
+
+        let processes = find_stale_processes(root.path());
+
+        let pids: Vec<u32> = processes.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![812, 2204]);
+
+        let sshd = &processes[0];
+        assert_eq!(sshd.command, "sshd");
+        assert_eq!(
+            sshd.libraries,
+            vec!["/usr/lib/libc.so.6", "/usr/lib/libcrypto.so.3"]
+        );
+        assert_eq!(sshd.unit.as_deref(), Some("sshd.service"));
+        assert_eq!(sshd.user_id, None);
+
+        let firefox = &processes[1];
+        assert_eq!(
+            firefox.libraries,
+            vec!["/opt/google/chrome/libEGL.so", "/usr/lib/firefox/libxul.so"]
+        );
+        assert_eq!(firefox.unit, None);
+    }
+
+    #[test]
+    fn ignores_shared_memory_and_files_outside_system_directories() {
+        let maps = include_str!("../../tests/fixtures/restart_check/bash.maps");
+        assert!(parse_deleted_mappings(maps).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_modules_for_the_running_kernel() {
+        let root = fake_root();
+        fs::create_dir_all(root.path().join("proc/sys/kernel")).unwrap();
+        fs::write(
+            root.path().join("proc/sys/kernel/osrelease"),
+            "6.10.3-arch1-1\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.path().join("usr/lib/modules/6.10.4-arch1-1")).unwrap();
+
+        let report = check_restart_needed(root.path());
+        assert_eq!(report.running_kernel, "6.10.3-arch1-1");
+        assert!(report.kernel_missing);
+        assert_eq!(report.processes.len(), 2);
+
+        fs::create_dir_all(root.path().join("usr/lib/modules/6.10.3-arch1-1")).unwrap();
+        assert!(!check_restart_needed(root.path()).kernel_missing);
+    }
+
+    #[test]
+    fn unknown_kernel_release_is_not_reported_missing() {
+        let root = tempfile::tempdir().unwrap();
+
+        let report = check_restart_needed(root.path());
+        assert!(!report.kernel_missing);
+        assert!(report.processes.is_empty());
+    }
+
+    #[test]
+    fn parses_system_and_user_units_from_cgroups() {
+        assert_eq!(
+            parse_cgroup_unit("0::/system.slice/NetworkManager.service\n"),
+            Some(("NetworkManager.service".to_string(), None))
+        );
+        assert_eq!(
+            parse_cgroup_unit(
+                "0::/user.slice/user-1000.slice/user@1000.service/session.slice/pipewire.service\n"
+            ),
+            Some(("pipewire.service".to_string(), Some(1000)))
+        );
+        assert_eq!(
+            parse_cgroup_unit("12:pids:/\n1:name=systemd:/system.slice/cronie.service\n"),
+            Some(("cronie.service".to_string(), None))
+        );
+        assert_eq!(
+            parse_cgroup_unit("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn builds_restart_commands_for_safe_units() {
+        let process = |pid: u32, unit: &str, user_id: Option<u32>| {
+            return StaleProcess {
+                pid,
+                command: String::new(),
+                libraries: Vec::new(),
+                unit: Some(unit.to_string()),
+                user_id,
+            };
+        };
+        let processes = vec![
+            process(1, "sshd.service", None),
+            process(2, "dbus-broker.service", None),
+            process(3, "cronie.service", None),
+            process(4, "pipewire.service", Some(1000)),
+        ];
+
+        assert_eq!(
+            build_restart_command(&processes).as_deref(),
+            Some(
+                "systemctl restart cronie.service sshd.service; \
+                 systemctl --user --machine=\"$(id -nu 1000)\"@.host restart pipewire.service"
+            )
+        );
+        assert_eq!(build_restart_command(&processes[1..2]), None);
+    }
+}
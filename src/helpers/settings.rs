@@ -3,9 +3,11 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::{Mutex, OnceLock};
 
+use crate::constants::BUILTIN_AUR_BUILDER;
 use crate::helpers::aur::is_command_available;
+use crate::helpers::aur_helpers::get_aur_helper_definitions;
 use crate::models::app_settings::AppSettings;
-use crate::models::snapshot_retention_period::SnapshotRetentionPeriod;
+use crate::models::aur_helper_definition::AurHelperDefinition;
 
 static SETTINGS_CACHE: OnceLock<Mutex<AppSettings>> = OnceLock::new();
 
@@ -15,13 +17,7 @@ pub fn load_settings() -> AppSettings {
             Ok(settings) => settings,
             Err(e) => {
                 eprintln!("Failed to load settings: {}, using defaults", e);
-                AppSettings {
-                    enable_aur_support: false,
-                    preferred_aur_helper: None,
-                    create_timeshift_snapshot: true,
-                    snapshot_retention_count: 1,
-                    snapshot_retention_period: SnapshotRetentionPeriod::Forever,
-                }
+                AppSettings::default()
             }
         };
         Mutex::new(settings)
@@ -46,51 +42,37 @@ pub fn save_settings(settings: &AppSettings) -> Result<()> {
     return Ok(());
 }
 
-pub fn get_available_aur_helpers() -> Vec<String> {
-    let helpers = ["yay", "paru", "trizen", "pikaur", "pamac"];
-    let mut available = Vec::new();
-
-    for helper in &helpers {
-        if is_command_available(helper) {
-            available.push(helper.to_string());
-        }
-    }
-
-    return available;
+pub fn get_available_aur_helpers(settings: &AppSettings) -> Vec<AurHelperDefinition> {
+    return get_aur_helper_definitions(settings)
+        .into_iter()
+        .filter(|helper| is_command_available(&helper.binary))
+        .collect();
 }
 
-pub fn get_effective_aur_helper(settings: &AppSettings) -> Option<String> {
+pub fn get_effective_aur_helper(settings: &AppSettings) -> Option<AurHelperDefinition> {
     if !settings.enable_aur_support {
         return None;
     }
 
+    let available = get_available_aur_helpers(settings);
+
     if let Some(ref preferred) = settings.preferred_aur_helper {
-        if is_command_available(preferred) {
-            return Some(preferred.clone());
+        if preferred == BUILTIN_AUR_BUILDER {
+            return None;
         }
-    }
-
-    let helpers = ["yay", "paru", "trizen", "pikaur", "pamac"];
-    for helper in &helpers {
-        if is_command_available(helper) {
-            return Some(helper.to_string());
+        if let Some(helper) = available.iter().find(|helper| helper.id == *preferred) {
+            return Some(helper.clone());
         }
     }
 
-    return None;
+    return available.into_iter().next();
 }
 
 fn load_from_file() -> Result<AppSettings> {
     let path = settings_path()?;
 
     if !path.exists() {
-        return Ok(AppSettings {
-            enable_aur_support: false,
-            preferred_aur_helper: None,
-            create_timeshift_snapshot: true,
-            snapshot_retention_count: 1,
-            snapshot_retention_period: SnapshotRetentionPeriod::Forever,
-        });
+        return Ok(AppSettings::default());
     }
 
     let content = fs::read_to_string(&path).context("Failed to read settings file")?;
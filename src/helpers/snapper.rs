@@ -0,0 +1,199 @@
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::helpers::aur::is_command_available;
+use crate::helpers::snapshot_provider::{
+    MIN_SNAPSHOT_FREE_SPACE, SnapshotProvider, ensure_free_space,
+};
+use crate::models::system_snapshot::SystemSnapshot;
+
+const SNAPPER_CONFIG: &str = "root";
+
+pub struct SnapperProvider;
+
+impl SnapshotProvider for SnapperProvider {
+    fn name(&self) -> &'static str {
+        return "Snapper";
+    }
+
+    fn is_available(&self) -> bool {
+        return is_command_available("snapper")
+            && Path::new("/etc/snapper/configs")
+                .join(SNAPPER_CONFIG)
+                .exists();
+    }
+
+    fn preflight(&self) -> Result<()> {
+        return ensure_free_space(Path::new("/"), MIN_SNAPSHOT_FREE_SPACE);
+    }
+
+    fn create(
+        &self,
+        description: &str,
+        on_output: &mut dyn FnMut(&str),
+        cancelled: &AtomicBool,
+    ) -> Result<SystemSnapshot> {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(anyhow!("Snapshot cancelled"));
+        }
+
+        on_output("Creating Snapper pre snapshot...");
+        let number = run_snapper(&[
+            "create",
+            "--type",
+            "pre",
+            "--cleanup-algorithm",
+            "number",
+            "--print-number",
+            "--description",
+            description,
+        ])?;
+
+        return Ok(SystemSnapshot {
+            id: number.trim().to_string(),
+            created_at: Some(Local::now()),
+            description: description.to_string(),
+            labels: vec!["Pre".to_string()],
+            post_id: None,
+        });
+    }
+
+    fn complete(&self, snapshot: &SystemSnapshot, description: &str) -> Result<()> {
+        run_snapper(&[
+            "create",
+            "--type",
+            "post",
+            "--pre-number",
+            &snapshot.id,
+            "--cleanup-algorithm",
+            "number",
+            "--description",
+            description,
+        ])?;
+
+        return Ok(());
+    }
+
+    fn list(&self) -> Result<Vec<SystemSnapshot>> {
+        let output = run_snapper(&[
+            "list",
+            "--columns",
+            "number,type,pre-number,date,description",
+        ])?;
+
+        return Ok(parse_snapper_list(&output));
+    }
+
+    fn delete(&self, snapshot: &SystemSnapshot) -> Result<()> {
+        let mut args = vec!["delete", snapshot.id.as_str()];
+        if let Some(post_id) = &snapshot.post_id {
+            args.push(post_id);
+        }
+
+        run_snapper(&args)?;
+
+        return Ok(());
+    }
+
+    fn restore_command(&self, snapshot: &SystemSnapshot) -> Result<Vec<String>> {
+        return Ok(vec![
+            "snapper".to_string(),
+            "-c".to_string(),
+            SNAPPER_CONFIG.to_string(),
+            "rollback".to_string(),
+            snapshot.id.clone(),
+        ]);
+    }
+}
+
+fn run_snapper(args: &[&str]) -> Result<String> {
+    let output = Command::new("snapper")
+        .args(["--csvout", "--iso", "-c", SNAPPER_CONFIG])
+        .args(args)
+        .output()
+        .context("Failed to run snapper")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "snapper {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+}
+
+fn parse_snapper_list(output: &str) -> Vec<SystemSnapshot> {
+    let mut snapshots: Vec<SystemSnapshot> = Vec::new();
+
+    for line in output.lines().skip(1) {
+        let fields = split_csv_line(line);
+        if fields.len() < 5 {
+            continue;
+        }
+
+        let number = fields[0].trim();
+        let kind = fields[1].trim();
+        let pre_number = fields[2].trim();
+
+        // Snapshot 0 is the live system, not a snapshot that can be deleted or restored.
+        if number.is_empty() || number == "0" {
+            continue;
+        }
+
+        if kind == "post" {
+            if let Some(pre) = snapshots.iter_mut().find(|s| s.id == pre_number) {
+                pre.post_id = Some(number.to_string());
+                pre.labels.push("Post".to_string());
+                continue;
+            }
+        }
+
+        let label = match kind {
+            "pre" => "Pre",
+            "post" => "Post",
+            _ => "Single",
+        };
+
+        snapshots.push(SystemSnapshot {
+            id: number.to_string(),
+            created_at: parse_snapper_date(fields[3].trim()),
+            description: fields[4].trim().to_string(),
+            labels: vec![label.to_string()],
+            post_id: None,
+        });
+    }
+
+    return snapshots;
+}
+
+fn parse_snapper_date(date: &str) -> Option<DateTime<Local>> {
+    let naive = NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").ok()?;
+    return Local.from_local_datetime(&naive).single();
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+
+    return fields;
+}
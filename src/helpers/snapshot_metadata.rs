@@ -0,0 +1,164 @@
+use anyhow::{Context, Result, anyhow};
+use chrono::Local;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::helpers::pacman_info::get_installed_packages;
+use crate::helpers::state_dir::get_state_dir;
+use crate::helpers::vercmp::vercmp;
+use crate::models::pacman_action::PacmanAction;
+use crate::models::pacman_action_kind::PacmanActionKind;
+use crate::models::session_package::SessionPackage;
+use crate::models::snapshot_metadata::SnapshotMetadata;
+use crate::models::snapshot_provider_kind::SnapshotProviderKind;
+use crate::models::system_snapshot::SystemSnapshot;
+
+pub fn record_snapshot_metadata(
+    provider: &SnapshotProviderKind,
+    snapshot: &SystemSnapshot,
+    packages: Vec<SessionPackage>,
+    aur_packages: Vec<String>,
+) -> Result<()> {
+    let metadata = SnapshotMetadata {
+        snapshot: snapshot.id.clone(),
+        provider: provider.id().to_string(),
+        created_at: snapshot.created_at.unwrap_or_else(Local::now),
+        host: read_command_output("uname", &["-n"]).unwrap_or_default(),
+        kernel: read_command_output("uname", &["-r"]).unwrap_or_default(),
+        packages,
+        aur_packages,
+        installed_packages: get_installed_packages()?,
+    };
+
+    let content =
+        serde_json::to_string_pretty(&metadata).context("Failed to serialize snapshot metadata")?;
+    fs::write(metadata_path(provider, &snapshot.id)?, content)
+        .context("Failed to write snapshot metadata")?;
+
+    return Ok(());
+}
+
+pub fn load_snapshot_metadata(
+    provider: &SnapshotProviderKind,
+    snapshot_id: &str,
+) -> Option<SnapshotMetadata> {
+    let path = metadata_path(provider, snapshot_id).ok()?;
+    let content = fs::read_to_string(&path).ok()?;
+
+    return match serde_json::from_str(&content) {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            None
+        }
+    };
+}
+
+pub fn delete_snapshot_metadata(provider: &SnapshotProviderKind, snapshot_id: &str) {
+    let Ok(path) = metadata_path(provider, snapshot_id) else {
+        return;
+    };
+
+    if let Err(e) = fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            eprintln!("Failed to delete {}: {}", path.display(), e);
+        }
+    }
+}
+
+// Removes the metadata of snapshots that were deleted outside the app, for example with the
+// provider's own tools.
+pub fn prune_snapshot_metadata(provider: &SnapshotProviderKind, existing: &[SystemSnapshot]) {
+    let Ok(dir) = get_state_dir("snapshots") else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+
+    let prefix = format!("{}_", provider.id());
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(snapshot_id) = file_name
+            .strip_prefix(&prefix)
+            .and_then(|name| name.strip_suffix(".json"))
+        else {
+            continue;
+        };
+
+        if !existing.iter().any(|snapshot| snapshot.id == snapshot_id) {
+            delete_snapshot_metadata(provider, snapshot_id);
+        }
+    }
+}
+
+pub fn diff_against_installed(metadata: &SnapshotMetadata) -> Result<Vec<PacmanAction>> {
+    let installed = get_installed_packages()?;
+    return Ok(diff_package_sets(&metadata.installed_packages, &installed));
+}
+
+pub fn diff_package_sets(
+    before: &BTreeMap<String, String>,
+    after: &BTreeMap<String, String>,
+) -> Vec<PacmanAction> {
+    let mut changes = Vec::new();
+
+    for (name, old_version) in before {
+        match after.get(name) {
+            None => changes.push(PacmanAction {
+                kind: PacmanActionKind::Removed,
+                package: name.clone(),
+                old_version: Some(old_version.clone()),
+                new_version: None,
+            }),
+            Some(new_version) => {
+                let kind = match vercmp(new_version, old_version) {
+                    Ordering::Greater => PacmanActionKind::Upgraded,
+                    Ordering::Less => PacmanActionKind::Downgraded,
+                    Ordering::Equal => continue,
+                };
+                changes.push(PacmanAction {
+                    kind,
+                    package: name.clone(),
+                    old_version: Some(old_version.clone()),
+                    new_version: Some(new_version.clone()),
+                });
+            }
+        }
+    }
+
+    for (name, new_version) in after {
+        if !before.contains_key(name) {
+            changes.push(PacmanAction {
+                kind: PacmanActionKind::Installed,
+                package: name.clone(),
+                old_version: None,
+                new_version: Some(new_version.clone()),
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| a.package.cmp(&b.package));
+
+    return changes;
+}
+
+fn read_command_output(command: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(command)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {}", command))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("{} {} failed", command, args.join(" ")));
+    }
+
+    return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+}
+
+fn metadata_path(provider: &SnapshotProviderKind, snapshot_id: &str) -> Result<PathBuf> {
+    return Ok(get_state_dir("snapshots")?.join(format!("{}_{}.json", provider.id(), snapshot_id)));
+}
@@ -0,0 +1,114 @@
+use anyhow::{Result, anyhow};
+use chrono::Local;
+use glib::format_size;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use crate::helpers::btrfs_snapshots::BtrfsProvider;
+use crate::helpers::disk_space::get_available_space;
+use crate::helpers::settings::load_settings;
+use crate::helpers::snapper::SnapperProvider;
+use crate::helpers::snapshot_metadata::{delete_snapshot_metadata, prune_snapshot_metadata};
+use crate::helpers::snapshot_retention::{get_retention_policy, plan_snapshot_retention};
+use crate::helpers::timeshift::TimeshiftProvider;
+use crate::models::{
+    app_settings::AppSettings, snapshot_provider_kind::SnapshotProviderKind,
+    system_snapshot::SystemSnapshot,
+};
+
+pub trait SnapshotProvider {
+    fn name(&self) -> &'static str;
+
+    fn is_available(&self) -> bool;
+
+    // Checks that a snapshot can be taken right now, so problems show up before
+    // anything is written instead of halfway through.
+    fn preflight(&self) -> Result<()> {
+        return Ok(());
+    }
+
+    fn create(
+        &self,
+        description: &str,
+        on_output: &mut dyn FnMut(&str),
+        cancelled: &AtomicBool,
+    ) -> Result<SystemSnapshot>;
+
+    // Called once the transaction the snapshot was taken for has finished,
+    // so providers that work with pre/post pairs can take the second half.
+    fn complete(&self, _snapshot: &SystemSnapshot, _description: &str) -> Result<()> {
+        return Ok(());
+    }
+
+    fn list(&self) -> Result<Vec<SystemSnapshot>>;
+
+    fn delete(&self, snapshot: &SystemSnapshot) -> Result<()>;
+
+    fn restore_command(&self, snapshot: &SystemSnapshot) -> Result<Vec<String>>;
+}
+
+pub const MIN_SNAPSHOT_FREE_SPACE: u64 = 1024 * 1024 * 1024;
+
+pub fn ensure_free_space(path: &Path, required: u64) -> Result<()> {
+    let available = get_available_space(path)?;
+    if available < required {
+        return Err(anyhow!(
+            "Not enough free space on {}: {} available, about {} needed for the snapshot.",
+            path.display(),
+            format_size(available),
+            format_size(required)
+        ));
+    }
+
+    return Ok(());
+}
+
+pub fn get_snapshot_provider(kind: &SnapshotProviderKind) -> Box<dyn SnapshotProvider> {
+    return match kind {
+        SnapshotProviderKind::Timeshift => Box::new(TimeshiftProvider),
+        SnapshotProviderKind::Snapper => Box::new(SnapperProvider),
+        SnapshotProviderKind::Btrfs => Box::new(BtrfsProvider),
+    };
+}
+
+pub fn get_configured_snapshot_provider() -> Box<dyn SnapshotProvider> {
+    return get_snapshot_provider(&load_settings().snapshot_provider);
+}
+
+pub fn cleanup_snapshots(
+    provider: &dyn SnapshotProvider,
+    description: &str,
+    settings: &AppSettings,
+    keep_snapshot: &SystemSnapshot,
+) -> Result<()> {
+    let snapshots = list_managed_snapshots(provider, description)?;
+
+    let mut policy = get_retention_policy(settings);
+    policy.pinned.push(keep_snapshot.id.clone());
+
+    for snapshot in plan_snapshot_retention(&snapshots, &policy, Local::now()) {
+        provider.delete(&snapshot)?;
+        delete_snapshot_metadata(&settings.snapshot_provider, &snapshot.id);
+        println!("Deleted snapshot {}", snapshot.id);
+    }
+
+    match provider.list() {
+        Ok(remaining) => prune_snapshot_metadata(&settings.snapshot_provider, &remaining),
+        Err(e) => eprintln!("Failed to list snapshots to prune their metadata: {}", e),
+    }
+
+    return Ok(());
+}
+
+pub fn list_managed_snapshots(
+    provider: &dyn SnapshotProvider,
+    description: &str,
+) -> Result<Vec<SystemSnapshot>> {
+    let snapshots = provider
+        .list()?
+        .into_iter()
+        .filter(|s| s.description.trim() == description)
+        .collect();
+
+    return Ok(snapshots);
+}
@@ -0,0 +1,220 @@
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate};
+use std::collections::HashSet;
+
+use crate::models::{
+    app_settings::AppSettings, snapshot_retention_policy::SnapshotRetentionPolicy,
+    system_snapshot::SystemSnapshot,
+};
+
+pub fn get_retention_policy(settings: &AppSettings) -> SnapshotRetentionPolicy {
+    return SnapshotRetentionPolicy {
+        keep_last: settings.snapshot_retention_count,
+        keep_daily: settings.snapshot_retention_days,
+        keep_weekly: settings.snapshot_retention_weeks,
+        pinned: settings.pinned_snapshots.clone(),
+    };
+}
+
+pub fn plan_snapshot_retention(
+    snapshots: &[SystemSnapshot],
+    policy: &SnapshotRetentionPolicy,
+    now: DateTime<Local>,
+) -> Vec<SystemSnapshot> {
+    // Snapshots without a creation time are never deleted, we can't tell how old they are.
+    let mut dated: Vec<&SystemSnapshot> = snapshots
+        .iter()
+        .filter(|snapshot| snapshot.created_at.is_some())
+        .collect();
+    dated.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+
+    let mut keep: HashSet<&str> = policy.pinned.iter().map(|id| id.as_str()).collect();
+
+    for snapshot in dated.iter().take(policy.keep_last as usize) {
+        keep.insert(&snapshot.id);
+    }
+
+    let today = now.date_naive();
+    let this_week = start_of_week(today);
+    let mut kept_days = HashSet::new();
+    let mut kept_weeks = HashSet::new();
+
+    for snapshot in &dated {
+        let Some(created_at) = snapshot.created_at else {
+            continue;
+        };
+        let date = created_at.date_naive();
+
+        let age_days = (today - date).num_days();
+        if age_days < 0 {
+            keep.insert(&snapshot.id);
+            continue;
+        }
+
+        if age_days < policy.keep_daily as i64 && kept_days.insert(date) {
+            keep.insert(&snapshot.id);
+        }
+
+        let week = start_of_week(date);
+        let age_weeks = (this_week - week).num_weeks();
+        if age_weeks < policy.keep_weekly as i64 && kept_weeks.insert(week) {
+            keep.insert(&snapshot.id);
+        }
+    }
+
+    return dated
+        .into_iter()
+        .filter(|snapshot| !keep.contains(snapshot.id.as_str()))
+        .cloned()
+        .collect();
+}
+
+fn start_of_week(date: NaiveDate) -> NaiveDate {
+    let offset = date.weekday().num_days_from_monday();
+    return date
+        .checked_sub_days(Days::new(offset as u64))
+        .unwrap_or(date);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDateTime, TimeZone};
+
+    // Wednesday, so the current week started on 2024-03-11.
+    fn now() -> DateTime<Local> {
+        return snapshot("2024-03-13_12-00-00").created_at.unwrap();
+    }
+
+    // Synthetic snapshots are named after their creation time, the way Timeshift names them.
+    fn snapshot(id: &str) -> SystemSnapshot {
+        let naive = NaiveDateTime::parse_from_str(id, "%Y-%m-%d_%H-%M-%S").unwrap();
+        return SystemSnapshot {
+            id: id.to_string(),
+            created_at: Local.from_local_datetime(&naive).earliest(),
+            description: String::new(),
+            labels: Vec::new(),
+            post_id: None,
+        };
+    }
+
+    fn policy(keep_last: u32, keep_daily: u32, keep_weekly: u32) -> SnapshotRetentionPolicy {
+        return SnapshotRetentionPolicy {
+            keep_last,
+            keep_daily,
+            keep_weekly,
+            pinned: Vec::new(),
+        };
+    }
+
+    fn deleted_ids(snapshots: &[SystemSnapshot], policy: &SnapshotRetentionPolicy) -> Vec<String> {
+        let mut ids: Vec<String> = plan_snapshot_retention(snapshots, policy, now())
+            .into_iter()
+            .map(|snapshot| snapshot.id)
+            .collect();
+        ids.sort();
+        return ids;
+    }
+
+    #[test]
+    fn keep_last_keeps_the_newest_snapshots() {
+        let snapshots = [
+            snapshot("2024-03-01_08-00-00"),
+            snapshot("2024-03-13_09-00-00"),
+            snapshot("2024-03-05_08-00-00"),
+            snapshot("2024-03-12_08-00-00"),
+        ];
+
+        assert_eq!(
+            deleted_ids(&snapshots, &policy(2, 0, 0)),
+            ["2024-03-01_08-00-00", "2024-03-05_08-00-00"]
+        );
+    }
+
+    #[test]
+    fn keep_daily_keeps_the_newest_snapshot_of_each_recent_day() {
+        let snapshots = [
+            snapshot("2024-03-13_08-00-00"),
+            snapshot("2024-03-13_10-00-00"),
+            snapshot("2024-03-12_08-00-00"),
+            snapshot("2024-03-12_20-00-00"),
+            snapshot("2024-03-11_08-00-00"),
+            snapshot("2024-03-10_08-00-00"),
+        ];
+
+        assert_eq!(
+            deleted_ids(&snapshots, &policy(0, 3, 0)),
+            [
+                "2024-03-10_08-00-00",
+                "2024-03-12_08-00-00",
+                "2024-03-13_08-00-00",
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_weekly_keeps_the_newest_snapshot_of_each_recent_week() {
+        let snapshots = [
+            snapshot("2024-03-12_08-00-00"),
+            snapshot("2024-03-11_08-00-00"),
+            snapshot("2024-03-10_08-00-00"),
+            snapshot("2024-03-04_08-00-00"),
+            snapshot("2024-02-27_08-00-00"),
+        ];
+
+        assert_eq!(
+            deleted_ids(&snapshots, &policy(0, 0, 2)),
+            [
+                "2024-02-27_08-00-00",
+                "2024-03-04_08-00-00",
+                "2024-03-11_08-00-00",
+            ]
+        );
+    }
+
+    #[test]
+    fn rules_are_combined() {
+        let snapshots = [
+            snapshot("2024-03-13_09-00-00"),
+            snapshot("2024-03-13_08-00-00"),
+            snapshot("2024-03-12_08-00-00"),
+            snapshot("2024-03-06_08-00-00"),
+            snapshot("2024-03-01_08-00-00"),
+        ];
+
+        assert_eq!(
+            deleted_ids(&snapshots, &policy(2, 2, 2)),
+            ["2024-03-01_08-00-00"]
+        );
+    }
+
+    #[test]
+    fn pinned_and_undated_snapshots_are_never_deleted() {
+        let mut undated = snapshot("2024-01-01_00-00-00");
+        undated.id = "manual".to_string();
+        undated.created_at = None;
+
+        let snapshots = [
+            snapshot("2024-03-13_08-00-00"),
+            snapshot("2024-02-01_08-00-00"),
+            snapshot("2024-01-15_08-00-00"),
+            undated,
+        ];
+        let mut policy = policy(1, 0, 0);
+        policy.pinned = vec!["2024-01-15_08-00-00".to_string()];
+
+        assert_eq!(deleted_ids(&snapshots, &policy), ["2024-02-01_08-00-00"]);
+    }
+
+    #[test]
+    fn snapshots_from_the_future_are_kept() {
+        let snapshots = [
+            snapshot("2024-03-20_08-00-00"),
+            snapshot("2024-03-13_08-00-00"),
+        ];
+
+        assert_eq!(
+            deleted_ids(&snapshots, &policy(0, 0, 0)),
+            ["2024-03-13_08-00-00"]
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+pub fn get_state_dir(name: &str) -> Result<PathBuf> {
+    let state_home = if let Ok(state_home) = std::env::var("XDG_STATE_HOME") {
+        PathBuf::from(state_home)
+    } else if let Ok(home) = std::env::var("HOME") {
+        PathBuf::from(home).join(".local").join("state")
+    } else {
+        return Err(anyhow::anyhow!("Could not determine state directory"));
+    };
+
+    let dir = state_home.join("arch-update-manager").join(name);
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir).context("Failed to create state directory")?;
+    }
+
+    return Ok(dir);
+}
@@ -1,6 +1,10 @@
 use vte4::TerminalExtManual;
 
-pub fn spawn_terminal(terminal: &vte4::Terminal, args: Vec<&str>) {
+pub fn spawn_terminal(
+    terminal: &vte4::Terminal,
+    args: Vec<&str>,
+    on_error: impl FnOnce(glib::Error) + 'static,
+) {
     terminal.spawn_async(
         vte4::PtyFlags::DEFAULT,   // no special flags
         None,                      // default working directory
@@ -10,10 +14,19 @@ pub fn spawn_terminal(terminal: &vte4::Terminal, args: Vec<&str>) {
         || {},                     // child setup function
         -1,                        // timeout
         None::<&gio::Cancellable>, // cancellable
-        |result| {
+        move |result| {
             if let Err(e) = result {
                 eprintln!("Failed to spawn terminal: {}", e);
+                on_error(e);
             }
         },
     );
 }
+
+pub fn exit_status_code(status: i32) -> i32 {
+    if status & 0x7f == 0 {
+        return (status >> 8) & 0xff;
+    }
+
+    return 128 + (status & 0x7f);
+}
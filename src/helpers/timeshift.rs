@@ -1,99 +1,193 @@
 use anyhow::{Context, Result, anyhow};
-use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use regex::Regex;
-use std::process::Command;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
 
-use crate::models::{
-    app_settings::AppSettings, snapshot_retention_period::SnapshotRetentionPeriod,
+use crate::helpers::ansi::strip_ansi_codes;
+use crate::helpers::aur::is_command_available;
+use crate::helpers::disk_space::get_used_space;
+use crate::helpers::snapshot_provider::{
+    MIN_SNAPSHOT_FREE_SPACE, SnapshotProvider, ensure_free_space,
 };
+use crate::models::{system_snapshot::SystemSnapshot, timeshift_snapshot::TimeshiftSnapshot};
 
-pub fn create_timeshift_snapshot(comment: &str) -> Result<String> {
-    let status = Command::new("timeshift")
-        .args(["--create", "--tags", "O", "--comments", comment, "--yes"])
-        .status()?;
-    if !status.success() {
-        return Err(anyhow!("timeshift snapshot create failed"));
+const TIMESHIFT_CONFIG_PATH: &str = "/etc/timeshift/timeshift.json";
+
+pub struct TimeshiftProvider;
+
+impl SnapshotProvider for TimeshiftProvider {
+    fn name(&self) -> &'static str {
+        return "Timeshift";
     }
 
-    let snaps = list_timeshift_snapshots_with_comments()?;
-    let mut candidates: Vec<_> = snaps
-        .into_iter()
-        .filter(|(_, c)| c.as_deref().unwrap_or("") == comment)
-        .collect();
-    if candidates.is_empty() {
-        return Err(anyhow!("created snapshot not found in list"));
+    fn is_available(&self) -> bool {
+        return is_command_available("timeshift");
     }
 
-    candidates.sort_by(|a, b| a.0.cmp(&b.0));
-    return Ok(candidates.last().unwrap().0.clone());
-}
+    fn preflight(&self) -> Result<()> {
+        let content = fs::read_to_string(TIMESHIFT_CONFIG_PATH).map_err(|_| {
+            anyhow!(
+                "Timeshift has not been set up yet. Run Timeshift once and select a backup device."
+            )
+        })?;
+        let config: serde_json::Value = serde_json::from_str(&content)
+            .context("Failed to parse the Timeshift configuration")?;
 
-pub fn cleanup_timeshift_snapshots(
-    comment: &str,
-    settings: &AppSettings,
-    keep_snapshot: &str,
-) -> Result<()> {
-    let mut snaps = list_timeshift_snapshots_with_comments()?;
-    for (n, c) in snaps.iter_mut() {
-        *n = n.trim().to_string();
-        if let Some(s) = c.as_mut() {
-            *s = s.trim().to_string();
+        let uuid = config["backup_device_uuid"].as_str().unwrap_or("").trim();
+        if uuid.is_empty() {
+            return Err(anyhow!(
+                "Timeshift has no backup device configured. Select one in Timeshift's settings."
+            ));
+        }
+
+        if !Path::new("/dev/disk/by-uuid").join(uuid).exists() {
+            return Err(anyhow!(
+                "The Timeshift backup device ({}) is not connected.",
+                uuid
+            ));
         }
+
+        let Some(mount_point) = find_mount_point(uuid) else {
+            return Err(anyhow!(
+                "The Timeshift backup device ({}) is not mounted.",
+                uuid
+            ));
+        };
+
+        // Btrfs snapshots and incremental rsync snapshots only store what changed; the
+        // first rsync snapshot is a full copy of the root filesystem.
+        let btrfs_mode = config["btrfs_mode"].as_str() == Some("true");
+        let first_snapshot = !btrfs_mode
+            && list_timeshift_snapshots_with_comments()
+                .map(|snapshots| snapshots.is_empty())
+                .unwrap_or(false);
+        let required = if first_snapshot {
+            get_used_space(Path::new("/"))?
+        } else {
+            MIN_SNAPSHOT_FREE_SPACE
+        };
+
+        return ensure_free_space(Path::new(&mount_point), required);
     }
-    snaps.sort_by(|a, b| a.0.cmp(&b.0));
 
-    let mut same_comment: Vec<(String, Option<String>)> = snaps
-        .into_iter()
-        .filter(|(_, c)| c.as_deref() == Some(comment))
-        .collect();
+    fn create(
+        &self,
+        description: &str,
+        on_output: &mut dyn FnMut(&str),
+        cancelled: &AtomicBool,
+    ) -> Result<SystemSnapshot> {
+        let name = create_timeshift_snapshot(description, on_output, cancelled)?;
+
+        return Ok(SystemSnapshot {
+            created_at: parse_snapshot_timestamp(&name).ok(),
+            id: name,
+            description: description.to_string(),
+            labels: vec!["On-demand".to_string()],
+            post_id: None,
+        });
+    }
+
+    fn list(&self) -> Result<Vec<SystemSnapshot>> {
+        let snapshots = list_timeshift_snapshots_with_comments()?
+            .into_iter()
+            .map(|snapshot| SystemSnapshot {
+                created_at: parse_snapshot_timestamp(&snapshot.name).ok(),
+                labels: describe_tags(&snapshot.tags),
+                description: snapshot.comment.unwrap_or_default(),
+                id: snapshot.name,
+                post_id: None,
+            })
+            .collect();
+
+        return Ok(snapshots);
+    }
+
+    fn delete(&self, snapshot: &SystemSnapshot) -> Result<()> {
+        let status = Command::new("timeshift")
+            .args(["--delete", "--snapshot", &snapshot.id, "--yes"])
+            .status()?;
+        if !status.success() {
+            return Err(anyhow!("failed to delete snapshot {}", snapshot.id));
+        }
 
-    if same_comment.is_empty() {
         return Ok(());
     }
 
-    let now = Local::now();
-    let cutoff_time = match settings.snapshot_retention_period {
-        SnapshotRetentionPeriod::Forever => None,
-        SnapshotRetentionPeriod::Day => Some(now - Duration::days(1)),
-        SnapshotRetentionPeriod::Week => Some(now - Duration::weeks(1)),
-        SnapshotRetentionPeriod::Month => Some(now - Duration::days(30)),
-        SnapshotRetentionPeriod::Year => Some(now - Duration::days(365)),
-    };
+    fn restore_command(&self, snapshot: &SystemSnapshot) -> Result<Vec<String>> {
+        return Ok(vec![
+            "timeshift".to_string(),
+            "--restore".to_string(),
+            "--snapshot".to_string(),
+            snapshot.id.clone(),
+        ]);
+    }
+}
 
-    if let Some(cutoff) = cutoff_time {
-        same_comment.retain(|(name, _)| {
-            if let Ok(snapshot_time) = parse_snapshot_timestamp(name) {
-                snapshot_time >= cutoff
-            } else {
-                true // Keep snapshots we can't parse
-            }
-        });
+pub fn create_timeshift_snapshot(
+    comment: &str,
+    on_output: &mut dyn FnMut(&str),
+    cancelled: &AtomicBool,
+) -> Result<String> {
+    let mut child = Command::new("timeshift")
+        .args(["--create", "--tags", "O", "--comments", comment, "--yes"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run timeshift")?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        let tx = tx.clone();
+        thread::spawn(move || forward_output_lines(stdout, tx));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        thread::spawn(move || forward_output_lines(stderr, tx));
+    } else {
+        drop(tx);
     }
 
-    if same_comment.len() > settings.snapshot_retention_count as usize {
-        let keep_count = settings.snapshot_retention_count as usize;
-        let to_delete = same_comment.len() - keep_count;
-        let snapshots_to_delete: Vec<_> = same_comment.drain(0..to_delete).collect();
+    loop {
+        if cancelled.load(Ordering::SeqCst) {
+            let _ = Command::new("kill")
+                .args(["-TERM", &child.id().to_string()])
+                .status();
+            let _ = child.wait();
+            return Err(anyhow!("Snapshot cancelled"));
+        }
 
-        for (name, _) in snapshots_to_delete {
-            if name == keep_snapshot {
-                continue;
-            }
-            let status = Command::new("timeshift")
-                .args(["--delete", "--snapshot", &name, "--yes"])
-                .status()?;
-            if status.success() {
-                println!("Deleted snapshot {name}");
-            } else {
-                return Err(anyhow!("failed to delete snapshot {name}"));
-            }
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(line) => on_output(&line),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
         }
     }
 
-    return Ok(());
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow!("timeshift snapshot create failed"));
+    }
+
+    let snaps = list_timeshift_snapshots_with_comments()?;
+    let mut candidates: Vec<_> = snaps
+        .into_iter()
+        .filter(|s| s.comment.as_deref().unwrap_or("") == comment)
+        .collect();
+    if candidates.is_empty() {
+        return Err(anyhow!("created snapshot not found in list"));
+    }
+
+    candidates.sort_by(|a, b| a.name.cmp(&b.name));
+    return Ok(candidates.last().unwrap().name.clone());
 }
 
-fn parse_snapshot_timestamp(snapshot_name: &str) -> Result<DateTime<Local>> {
+pub fn parse_snapshot_timestamp(snapshot_name: &str) -> Result<DateTime<Local>> {
     // Parse snapshot name format: YYYY-MM-DD_HH-MM-SS
     let re =
         Regex::new(r"([0-9]{4})-([0-9]{2})-([0-9]{2})_([0-9]{2})-([0-9]{2})-([0-9]{2})").unwrap();
@@ -126,7 +220,7 @@ fn parse_snapshot_timestamp(snapshot_name: &str) -> Result<DateTime<Local>> {
     ));
 }
 
-fn list_timeshift_snapshots_with_comments() -> Result<Vec<(String, Option<String>)>> {
+pub fn list_timeshift_snapshots_with_comments() -> Result<Vec<TimeshiftSnapshot>> {
     let out = Command::new("timeshift")
         .args(["--list"])
         .output()
@@ -137,16 +231,16 @@ fn list_timeshift_snapshots_with_comments() -> Result<Vec<(String, Option<String
     let s = String::from_utf8_lossy(&out.stdout);
 
     let re_table = Regex::new(
-        r"(?m)^\s*\d+\s+(?:>\s+)?([0-9]{4}-[0-9]{2}-[0-9]{2}_[0-9]{2}-[0-9]{2}-[0-9]{2})\s+\S+(?:\s+(.*\S))?\s*$",
+        r"(?m)^\s*\d+\s+(?:>\s+)?([0-9]{4}-[0-9]{2}-[0-9]{2}_[0-9]{2}-[0-9]{2}-[0-9]{2})\s+(\S+)(?:\s+(.*\S))?\s*$",
     )
     .unwrap();
 
-    let mut result: Vec<(String, Option<String>)> = re_table
+    let mut result: Vec<TimeshiftSnapshot> = re_table
         .captures_iter(&s)
-        .map(|cap| {
-            let name = cap[1].to_string();
-            let comment = cap.get(2).map(|m| m.as_str().trim().to_string());
-            (name, comment)
+        .map(|cap| TimeshiftSnapshot {
+            name: cap[1].to_string(),
+            tags: cap[2].to_string(),
+            comment: cap.get(3).map(|m| m.as_str().trim().to_string()),
         })
         .collect();
 
@@ -168,25 +262,95 @@ fn list_timeshift_snapshots_with_comments() -> Result<Vec<(String, Option<String
     )
     .unwrap();
     let re_comm = Regex::new(r"(?m)^\s*Comments\s*:\s*(.*)\s*$").unwrap();
+    let re_tags = Regex::new(r"(?m)^\s*Tags\s*:\s*(.*)\s*$").unwrap();
 
     result.clear();
     let mut cur_name: Option<String> = None;
+    let mut cur_tags = String::new();
     let mut cur_comment: Option<String> = None;
 
     for line in sv.lines() {
         if let Some(cap) = re_snap.captures(line) {
             if let Some(name) = cur_name.take() {
-                result.push((name, cur_comment.take()));
+                result.push(TimeshiftSnapshot {
+                    name,
+                    tags: std::mem::take(&mut cur_tags),
+                    comment: cur_comment.take(),
+                });
             }
             cur_name = Some(cap[1].to_string());
             cur_comment = None;
+        } else if let Some(cap) = re_tags.captures(line) {
+            cur_tags = cap[1].trim().to_string();
         } else if let Some(cap) = re_comm.captures(line) {
             cur_comment = Some(cap[1].trim().to_string());
         }
     }
     if let Some(name) = cur_name {
-        result.push((name, cur_comment));
+        result.push(TimeshiftSnapshot {
+            name,
+            tags: cur_tags,
+            comment: cur_comment,
+        });
     }
 
     return Ok(result);
 }
+
+fn describe_tags(tags: &str) -> Vec<String> {
+    return tags
+        .chars()
+        .filter_map(|tag| match tag {
+            'O' => Some("On-demand"),
+            'B' => Some("Boot"),
+            'H' => Some("Hourly"),
+            'D' => Some("Daily"),
+            'W' => Some("Weekly"),
+            'M' => Some("Monthly"),
+            _ => None,
+        })
+        .map(|label| label.to_string())
+        .collect();
+}
+
+fn find_mount_point(uuid: &str) -> Option<String> {
+    let output = Command::new("findmnt")
+        .args(["-n", "-o", "TARGET", "--source", &format!("UUID={}", uuid)])
+        .output()
+        .ok()?;
+
+    return String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty());
+}
+
+// Timeshift redraws its progress line with carriage returns, so both \r and \n end a line.
+fn forward_output_lines(mut reader: impl Read, tx: Sender<String>) {
+    let mut buffer = [0u8; 4096];
+    let mut pending = Vec::new();
+
+    while let Ok(read) = reader.read(&mut buffer) {
+        if read == 0 {
+            break;
+        }
+
+        for &byte in &buffer[..read] {
+            if byte == b'\r' || byte == b'\n' {
+                let line = strip_ansi_codes(&String::from_utf8_lossy(&pending));
+                pending.clear();
+                if !line.trim().is_empty() && tx.send(line.trim().to_string()).is_err() {
+                    return;
+                }
+            } else {
+                pending.push(byte);
+            }
+        }
+    }
+
+    let line = strip_ansi_codes(&String::from_utf8_lossy(&pending));
+    if !line.trim().is_empty() {
+        let _ = tx.send(line.trim().to_string());
+    }
+}
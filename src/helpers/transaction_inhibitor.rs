@@ -0,0 +1,65 @@
+use gtk4::prelude::*;
+use gtk4::{ApplicationInhibitFlags, ApplicationWindow};
+use std::cell::RefCell;
+use std::os::fd::OwnedFd;
+
+use crate::helpers::logind::inhibit;
+
+struct ActiveInhibitor {
+    application: Option<gtk4::Application>,
+    cookie: u32,
+    _lock: Option<OwnedFd>,
+}
+
+thread_local! {
+    static ACTIVE_INHIBITOR: RefCell<Option<ActiveInhibitor>> = const { RefCell::new(None) };
+}
+
+pub fn begin_transaction_inhibit(window: &ApplicationWindow, reason: &str) {
+    if is_transaction_inhibited() {
+        return;
+    }
+
+    let lock = match inhibit("shutdown:sleep:idle", "Arch Update Manager", reason) {
+        Ok(lock) => Some(lock),
+        Err(e) => {
+            eprintln!("Failed to take logind inhibitor lock: {:#}", e);
+            None
+        }
+    };
+
+    let application = window.application();
+    let cookie = application.as_ref().map_or(0, |application| {
+        application.inhibit(
+            Some(window),
+            ApplicationInhibitFlags::LOGOUT
+                | ApplicationInhibitFlags::SUSPEND
+                | ApplicationInhibitFlags::IDLE,
+            Some(reason),
+        )
+    });
+
+    ACTIVE_INHIBITOR.with(|inhibitor| {
+        inhibitor.replace(Some(ActiveInhibitor {
+            application,
+            cookie,
+            _lock: lock,
+        }));
+    });
+}
+
+pub fn end_transaction_inhibit() {
+    let Some(inhibitor) = ACTIVE_INHIBITOR.with(|inhibitor| inhibitor.take()) else {
+        return;
+    };
+
+    if let Some(application) = &inhibitor.application {
+        if inhibitor.cookie != 0 {
+            application.uninhibit(inhibitor.cookie);
+        }
+    }
+}
+
+pub fn is_transaction_inhibited() -> bool {
+    return ACTIVE_INHIBITOR.with(|inhibitor| inhibitor.borrow().is_some());
+}
@@ -0,0 +1,96 @@
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::constants::AUR_NAME;
+use crate::helpers::boot_checks::estimate_boot_space;
+use crate::helpers::disk_space::get_available_space;
+use crate::helpers::pacman_conf::get_cache_dirs;
+use crate::models::package_update::PackageUpdate;
+use crate::models::space_shortfall::SpaceShortfall;
+
+pub fn check_transaction_space(packages: &[PackageUpdate]) -> Result<Vec<SpaceShortfall>> {
+    let official: Vec<String> = packages
+        .iter()
+        .filter(|p| p.repository != AUR_NAME)
+        .map(|p| p.name.clone())
+        .collect();
+    if official.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cache_dir = get_cache_dirs()
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| PathBuf::from("/var/cache/pacman/pkg"));
+    let installed_delta: i64 = packages.iter().map(|p| p.size).sum();
+
+    let requirements = vec![
+        (cache_dir, get_download_size(&official)?),
+        (PathBuf::from("/"), installed_delta.max(0) as u64),
+        (
+            PathBuf::from("/boot"),
+            estimate_boot_space(Path::new("/"), &official),
+        ),
+    ];
+
+    return find_space_shortfalls(&requirements);
+}
+
+// pacman only counts packages that are not in the cache yet.
+pub fn get_download_size(packages: &[String]) -> Result<u64> {
+    let output = Command::new("pacman")
+        .args(["-Sp", "--print-format", "%s"])
+        .args(packages)
+        .output()
+        .context("Failed to run pacman -Sp")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "pacman -Sp failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    return Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse::<u64>().ok())
+        .sum());
+}
+
+pub fn find_space_shortfalls(requirements: &[(PathBuf, u64)]) -> Result<Vec<SpaceShortfall>> {
+    let mut filesystems: Vec<(u64, SpaceShortfall)> = Vec::new();
+
+    for (path, required) in requirements {
+        let Ok(metadata) = fs::metadata(path) else {
+            continue;
+        };
+
+        let path_name = path.to_string_lossy().to_string();
+        match filesystems
+            .iter_mut()
+            .find(|(device, _)| *device == metadata.dev())
+        {
+            Some((_, filesystem)) => {
+                filesystem.paths.push(path_name);
+                filesystem.required += required;
+            }
+            None => filesystems.push((
+                metadata.dev(),
+                SpaceShortfall {
+                    paths: vec![path_name],
+                    required: *required,
+                    available: get_available_space(path)?,
+                },
+            )),
+        }
+    }
+
+    return Ok(filesystems
+        .into_iter()
+        .map(|(_, filesystem)| filesystem)
+        .filter(|filesystem| filesystem.required > filesystem.available)
+        .collect());
+}
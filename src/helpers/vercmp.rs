@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+
+pub fn vercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+
+    let (epoch_a, version_a, release_a) = parse_evr(a);
+    let (epoch_b, version_b, release_b) = parse_evr(b);
+
+    let mut result = rpmvercmp(epoch_a, epoch_b);
+    if result == Ordering::Equal {
+        result = rpmvercmp(version_a, version_b);
+        if result == Ordering::Equal {
+            if let (Some(release_a), Some(release_b)) = (release_a, release_b) {
+                result = rpmvercmp(release_a, release_b);
+            }
+        }
+    }
+
+    return result;
+}
+
+pub fn satisfies_constraint(version: &str, operator: &str, required: &str) -> bool {
+    let ordering = vercmp(version, required);
+
+    return match operator {
+        "=" => ordering == Ordering::Equal,
+        ">=" => ordering != Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        "<" => ordering == Ordering::Less,
+        _ => true,
+    };
+}
+
+fn parse_evr(evr: &str) -> (&str, &str, Option<&str>) {
+    let digits_end = evr.find(|c: char| !c.is_ascii_digit()).unwrap_or(evr.len());
+
+    let (epoch, rest) = if evr[digits_end..].starts_with(':') {
+        let epoch = &evr[..digits_end];
+        (
+            if epoch.is_empty() { "0" } else { epoch },
+            &evr[digits_end + 1..],
+        )
+    } else {
+        ("0", evr)
+    };
+
+    return match rest.rfind('-') {
+        Some(dash) => (epoch, &rest[..dash], Some(&rest[dash + 1..])),
+        None => (epoch, rest, None),
+    };
+}
+
+fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+
+    let one = a.as_bytes();
+    let two = b.as_bytes();
+    let (mut i, mut j) = (0, 0);
+
+    while i < one.len() && j < two.len() {
+        let segment_start_one = i;
+        let segment_start_two = j;
+
+        while i < one.len() && !one[i].is_ascii_alphanumeric() {
+            i += 1;
+        }
+        while j < two.len() && !two[j].is_ascii_alphanumeric() {
+            j += 1;
+        }
+
+        if i >= one.len() || j >= two.len() {
+            break;
+        }
+
+        if i - segment_start_one != j - segment_start_two {
+            return (i - segment_start_one).cmp(&(j - segment_start_two));
+        }
+
+        let is_numeric = one[i].is_ascii_digit();
+        let (mut end_one, mut end_two) = (i, j);
+
+        if is_numeric {
+            while end_one < one.len() && one[end_one].is_ascii_digit() {
+                end_one += 1;
+            }
+            while end_two < two.len() && two[end_two].is_ascii_digit() {
+                end_two += 1;
+            }
+        } else {
+            while end_one < one.len() && one[end_one].is_ascii_alphabetic() {
+                end_one += 1;
+            }
+            while end_two < two.len() && two[end_two].is_ascii_alphabetic() {
+                end_two += 1;
+            }
+        }
+
+        if end_two == j {
+            return if is_numeric {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        let mut segment_one = &one[i..end_one];
+        let mut segment_two = &two[j..end_two];
+
+        if is_numeric {
+            while segment_one.first() == Some(&b'0') {
+                segment_one = &segment_one[1..];
+            }
+            while segment_two.first() == Some(&b'0') {
+                segment_two = &segment_two[1..];
+            }
+
+            let length_order = segment_one.len().cmp(&segment_two.len());
+            if length_order != Ordering::Equal {
+                return length_order;
+            }
+        }
+
+        let segment_order = segment_one.cmp(segment_two);
+        if segment_order != Ordering::Equal {
+            return segment_order;
+        }
+
+        i = end_one;
+        j = end_two;
+    }
+
+    if i >= one.len() && j >= two.len() {
+        return Ordering::Equal;
+    }
+
+    if (i >= one.len() && !two[j].is_ascii_alphabetic())
+        || (i < one.len() && one[i].is_ascii_alphabetic())
+    {
+        return Ordering::Less;
+    }
+
+    return Ordering::Greater;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_ascending(versions: &[&str]) {
+        for (i, a) in versions.iter().enumerate() {
+            assert_eq!(vercmp(a, a), Ordering::Equal, "{} = {}", a, a);
+            for b in &versions[i + 1..] {
+                assert_eq!(vercmp(a, b), Ordering::Less, "{} < {}", a, b);
+                assert_eq!(vercmp(b, a), Ordering::Greater, "{} > {}", b, a);
+            }
+        }
+    }
+
+    // The orderings listed in the vercmp(8) man page.
+    #[test]
+    fn orders_numeric_segments() {
+        assert_ascending(&["1", "1.0", "1.1", "1.1.1", "1.2", "2.0", "3.0.0", "10.0"]);
+    }
+
+    #[test]
+    fn orders_alpha_segments_before_the_release() {
+        assert_ascending(&[
+            "1.0a", "1.0alpha", "1.0b", "1.0beta", "1.0p", "1.0pre", "1.0rc", "1.0", "1.0.a",
+            "1.0.1",
+        ]);
+    }
+
+    #[test]
+    fn compares_epochs_first() {
+        assert_ascending(&["2.0", "1:1.0", "1:1.1", "2:0.1"]);
+        assert_eq!(vercmp("0:1.0", "1.0"), Ordering::Equal);
+        assert_eq!(vercmp(":1.0", "1.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn compares_pkgrel_only_when_both_have_one() {
+        assert_ascending(&["1.0-1", "1.0-2", "1.0-10", "1.1-1"]);
+        assert_eq!(vercmp("1.0-2", "1.0"), Ordering::Equal);
+        assert_eq!(vercmp("1.0", "1.0-2"), Ordering::Equal);
+        assert_eq!(vercmp("1.0-1.1", "1.0-1"), Ordering::Greater);
+    }
+
+    #[test]
+    fn ignores_leading_zeros_and_separator_kinds() {
+        assert_eq!(vercmp("1.01", "1.1"), Ordering::Equal);
+        assert_eq!(vercmp("1.0", "1_0"), Ordering::Equal);
+        assert_eq!(vercmp("1.0", "1..0"), Ordering::Less);
+        assert_eq!(
+            vercmp("1.0.r12.gabc1234", "1.0.r9.gfff0000"),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn checks_dependency_constraints() {
+        assert!(satisfies_constraint("1.2-1", ">=", "1.2"));
+        assert!(satisfies_constraint("1.2-1", "<", "1.10"));
+        assert!(satisfies_constraint("1:0.1", ">", "9.9"));
+        assert!(!satisfies_constraint("1.0", "=", "1.0.1"));
+        assert!(!satisfies_constraint("2.0", "<=", "1.9"));
+    }
+}
@@ -127,6 +127,10 @@ fn set_user_environment_vars(user: &str, uid: &str) {
             env::set_var("XDG_CACHE_HOME", format!("{}/.cache", user_home));
         }
 
+        if env::var("XDG_STATE_HOME").is_err() {
+            env::set_var("XDG_STATE_HOME", format!("{}/.local/state", user_home));
+        }
+
         if env::var("XAUTHORITY").is_err() {
             env::set_var("XAUTHORITY", format!("{}/.Xauthority", user_home));
         }
@@ -1,30 +1,76 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-use crate::models::snapshot_retention_period::SnapshotRetentionPeriod;
-
-impl std::fmt::Display for SnapshotRetentionPeriod {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            SnapshotRetentionPeriod::Forever => write!(f, "Forever"),
-            SnapshotRetentionPeriod::Day => write!(f, "1 Day"),
-            SnapshotRetentionPeriod::Week => write!(f, "1 Week"),
-            SnapshotRetentionPeriod::Month => write!(f, "1 Month"),
-            SnapshotRetentionPeriod::Year => write!(f, "1 Year"),
-        }
-    }
-}
+use crate::models::aur_helper_definition::AurHelperDefinition;
+use crate::models::snapshot_provider_kind::SnapshotProviderKind;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub enable_aur_support: bool,
     pub preferred_aur_helper: Option<String>,
+    #[serde(default)]
+    pub custom_aur_helpers: Vec<AurHelperDefinition>,
     pub create_timeshift_snapshot: bool,
+    #[serde(default)]
+    pub snapshot_provider: SnapshotProviderKind,
     #[serde(default = "default_snapshot_retention_count")]
     pub snapshot_retention_count: u32,
     #[serde(default)]
-    pub snapshot_retention_period: SnapshotRetentionPeriod,
+    pub snapshot_retention_days: u32,
+    #[serde(default)]
+    pub snapshot_retention_weeks: u32,
+    #[serde(default)]
+    pub pinned_snapshots: Vec<String>,
+    #[serde(default)]
+    pub downgrade_archive_url: Option<String>,
+    #[serde(default = "default_cache_keep_versions")]
+    pub cache_keep_versions: u32,
+    #[serde(default)]
+    pub cache_remove_uninstalled: bool,
+    #[serde(default)]
+    pub kept_orphans: Vec<String>,
+    #[serde(default)]
+    pub aur_rpc_url: Option<String>,
+    #[serde(default = "default_aur_clean_chroot")]
+    pub aur_clean_chroot: bool,
+    #[serde(default)]
+    pub check_devel_updates: bool,
+    #[serde(default)]
+    pub devel_check_overrides: BTreeMap<String, bool>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            enable_aur_support: false,
+            preferred_aur_helper: None,
+            custom_aur_helpers: Vec::new(),
+            create_timeshift_snapshot: true,
+            snapshot_provider: SnapshotProviderKind::Timeshift,
+            snapshot_retention_count: default_snapshot_retention_count(),
+            snapshot_retention_days: 0,
+            snapshot_retention_weeks: 0,
+            pinned_snapshots: Vec::new(),
+            downgrade_archive_url: None,
+            cache_keep_versions: default_cache_keep_versions(),
+            cache_remove_uninstalled: false,
+            kept_orphans: Vec::new(),
+            aur_rpc_url: None,
+            aur_clean_chroot: default_aur_clean_chroot(),
+            check_devel_updates: false,
+            devel_check_overrides: BTreeMap::new(),
+        }
+    }
 }
 
 fn default_snapshot_retention_count() -> u32 {
     1
 }
+
+fn default_cache_keep_versions() -> u32 {
+    return 3;
+}
+
+fn default_aur_clean_chroot() -> bool {
+    return true;
+}
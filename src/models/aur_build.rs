@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+use crate::models::aur_build_plan::AurBuildPlan;
+
+#[derive(Debug, Clone)]
+pub struct AurBuild {
+    pub plan: AurBuildPlan,
+    pub script: PathBuf,
+    pub status_file: PathBuf,
+}
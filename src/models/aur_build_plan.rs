@@ -0,0 +1,7 @@
+use crate::models::aur_build_step::AurBuildStep;
+
+#[derive(Debug, Clone)]
+pub struct AurBuildPlan {
+    pub steps: Vec<AurBuildStep>,
+    pub repo_dependencies: Vec<String>,
+}
@@ -0,0 +1,38 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AurBuildStatus {
+    Waiting,
+    Building,
+    Built,
+    Failed,
+}
+
+impl AurBuildStatus {
+    pub fn from_marker(marker: &str) -> Option<Self> {
+        match marker {
+            "building" => Some(AurBuildStatus::Building),
+            "built" => Some(AurBuildStatus::Built),
+            "failed" => Some(AurBuildStatus::Failed),
+            _ => None,
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            AurBuildStatus::Waiting => "dim-label",
+            AurBuildStatus::Building => "accent",
+            AurBuildStatus::Built => "success",
+            AurBuildStatus::Failed => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for AurBuildStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AurBuildStatus::Waiting => write!(f, "waiting"),
+            AurBuildStatus::Building => write!(f, "building"),
+            AurBuildStatus::Built => write!(f, "built"),
+            AurBuildStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone)]
+pub struct AurBuildStep {
+    pub package_base: String,
+    pub packages: Vec<String>,
+    pub as_dependency: bool,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::aur_update_format::AurUpdateFormat;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AurHelperDefinition {
+    pub id: String,
+    pub binary: String,
+    pub check_args: Vec<String>,
+    #[serde(default)]
+    pub no_color_args: Vec<String>,
+    pub install_args: Vec<String>,
+    pub update_pattern: String,
+    #[serde(default)]
+    pub update_format: AurUpdateFormat,
+    #[serde(default = "default_run_as_user")]
+    pub run_as_user: bool,
+}
+
+fn default_run_as_user() -> bool {
+    return true;
+}
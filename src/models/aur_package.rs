@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AurPackage {
+    pub name: String,
+    pub package_base: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub maintainer: Option<String>,
+    #[serde(default)]
+    pub num_votes: u32,
+    #[serde(default)]
+    pub popularity: f64,
+    #[serde(default)]
+    pub out_of_date: Option<i64>,
+    #[serde(default)]
+    pub last_modified: i64,
+    #[serde(default)]
+    pub depends: Vec<String>,
+    #[serde(default)]
+    pub make_depends: Vec<String>,
+    #[serde(default)]
+    pub check_depends: Vec<String>,
+    #[serde(default)]
+    pub provides: Vec<String>,
+}
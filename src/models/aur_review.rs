@@ -0,0 +1,11 @@
+use crate::models::pkgbuild_finding::PkgbuildFinding;
+
+#[derive(Debug, Clone)]
+pub struct AurReview {
+    pub package: String,
+    pub package_base: String,
+    pub commit: String,
+    pub reviewed_commit: Option<String>,
+    pub diff: String,
+    pub findings: Vec<PkgbuildFinding>,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+// How the update list of an AUR helper is laid out. Custom helpers are read with their pattern.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AurUpdateFormat {
+    #[default]
+    Pattern,
+    Pacman,
+    Pikaur,
+    Pamac,
+    Aura,
+}
@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct CacheCleanupPolicy {
+    pub keep_versions: u32,
+    pub remove_uninstalled: bool,
+    pub pinned_files: Vec<PathBuf>,
+}
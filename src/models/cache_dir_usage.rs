@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct CacheDirUsage {
+    pub path: PathBuf,
+    pub size: u64,
+    pub files: usize,
+}
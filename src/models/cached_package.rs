@@ -0,0 +1,9 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct CachedPackage {
+    pub name: String,
+    pub version: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone)]
+pub struct CheckpointRollback {
+    pub files: Vec<String>,
+    pub unavailable: Vec<String>,
+    pub installed_since: Vec<String>,
+}
@@ -0,0 +1,9 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct ConfigFileChange {
+    pub path: PathBuf,
+    pub target: PathBuf,
+    pub is_pacsave: bool,
+    pub package: Option<String>,
+}
@@ -0,0 +1,8 @@
+#[derive(Debug, Clone)]
+pub struct ConfigMerge {
+    pub mine: String,
+    pub new: Option<String>,
+    pub original: Option<String>,
+    pub merged: String,
+    pub has_conflicts: bool,
+}
@@ -0,0 +1,11 @@
+use crate::models::devel_source::DevelSource;
+
+#[derive(Debug, Clone)]
+pub struct DevelPackage {
+    pub name: String,
+    pub version: String,
+    pub sources: Vec<DevelSource>,
+    // False for VCS packages other than -git, whose upstream is never checked.
+    pub checkable: bool,
+    pub checked: bool,
+}
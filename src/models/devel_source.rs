@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DevelSource {
+    pub url: String,
+    pub reference: String,
+    pub revision: String,
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct DowngradeCandidate {
+    pub name: String,
+    pub version: String,
+    pub location: String,
+    pub from_archive: bool,
+}
@@ -0,0 +1,8 @@
+use crate::models::foreign_package_issue_kind::ForeignPackageIssueKind;
+
+#[derive(Debug, Clone)]
+pub struct ForeignPackageIssue {
+    pub name: String,
+    pub version: String,
+    pub kind: ForeignPackageIssueKind,
+}
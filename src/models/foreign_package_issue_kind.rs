@@ -0,0 +1,13 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForeignPackageIssueKind {
+    MissingFromAur,
+    InOfficialRepo {
+        repository: String,
+        name: String,
+        version: String,
+    },
+    MaintainerChanged {
+        previous: Option<String>,
+        current: Option<String>,
+    },
+}
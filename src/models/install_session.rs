@@ -0,0 +1,16 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::models::session_package::SessionPackage;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallSession {
+    pub id: String,
+    pub started_at: DateTime<Local>,
+    pub finished_at: Option<DateTime<Local>>,
+    pub packages: Vec<SessionPackage>,
+    pub snapshot: Option<String>,
+    #[serde(default)]
+    pub checkpoint: Option<String>,
+    pub exit_code: Option<i32>,
+}
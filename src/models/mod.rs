@@ -1,7 +1,42 @@
 pub mod app_settings;
-pub mod aur_managers;
+pub mod aur_build;
+pub mod aur_build_plan;
+pub mod aur_build_status;
+pub mod aur_build_step;
+pub mod aur_helper_definition;
+pub mod aur_package;
+pub mod aur_review;
+pub mod aur_update_format;
+pub mod cache_cleanup_policy;
+pub mod cache_dir_usage;
+pub mod cached_package;
+pub mod checkpoint_rollback;
+pub mod config_file_change;
+pub mod config_merge;
+pub mod devel_package;
+pub mod devel_source;
+pub mod downgrade_candidate;
+pub mod foreign_package_issue;
+pub mod foreign_package_issue_kind;
+pub mod install_session;
+pub mod orphan_package;
+pub mod package_checkpoint;
 pub mod package_info;
 pub mod package_object;
 pub mod package_update;
-pub mod snapshot_retention_period;
+pub mod pacman_action;
+pub mod pacman_action_kind;
+pub mod pacman_transaction;
+pub mod pkgbuild_finding;
+pub mod pkgbuild_risk_level;
+pub mod restart_report;
+pub mod session_package;
+pub mod snapshot_metadata;
+pub mod snapshot_provider_kind;
+pub mod snapshot_retention_policy;
+pub mod space_shortfall;
+pub mod stale_process;
+pub mod system_snapshot;
+pub mod timeshift_snapshot;
+pub mod update_check;
 pub mod update_error;
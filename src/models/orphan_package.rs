@@ -0,0 +1,8 @@
+#[derive(Debug, Clone)]
+pub struct OrphanPackage {
+    pub name: String,
+    pub version: String,
+    pub size: u64,
+    pub install_reason: String,
+    pub optional_for: Vec<String>,
+}
@@ -0,0 +1,12 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageCheckpoint {
+    pub id: String,
+    pub created_at: DateTime<Local>,
+    pub packages: BTreeMap<String, String>,
+    pub cached_files: BTreeMap<String, String>,
+    pub missing_from_cache: Vec<String>,
+}
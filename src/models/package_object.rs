@@ -3,6 +3,7 @@ use glib::subclass::prelude::*;
 use std::cell::RefCell;
 
 use crate::models::package_update::PackageUpdate;
+use crate::models::pkgbuild_finding::PkgbuildFinding;
 
 mod imp {
     use super::*;
@@ -40,4 +41,9 @@ impl PackageUpdateObject {
         let mut data = self.imp().data.borrow_mut();
         data.selected = selected;
     }
+
+    pub fn set_pkgbuild_findings(&self, findings: Vec<PkgbuildFinding>) {
+        let mut data = self.imp().data.borrow_mut();
+        data.pkgbuild_findings = findings;
+    }
 }
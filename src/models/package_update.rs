@@ -1,3 +1,6 @@
+use crate::models::aur_package::AurPackage;
+use crate::models::pkgbuild_finding::PkgbuildFinding;
+
 #[derive(Clone, Debug)]
 pub struct PackageUpdate {
     pub repository: String,
@@ -7,6 +10,11 @@ pub struct PackageUpdate {
     pub current_version: String,
     pub new_version: String,
     pub size: i64,
+    pub aur_info: Option<AurPackage>,
+    pub pkgbuild_findings: Vec<PkgbuildFinding>,
+    pub rebuild_reason: Option<String>,
+    // The updates a rebuild is needed for. It is only offered while one of them is selected.
+    pub rebuild_for: Vec<String>,
 }
 
 impl Default for PackageUpdate {
@@ -19,6 +27,10 @@ impl Default for PackageUpdate {
             current_version: String::new(),
             new_version: String::new(),
             size: 0,
+            aur_info: None,
+            pkgbuild_findings: Vec::new(),
+            rebuild_reason: None,
+            rebuild_for: Vec::new(),
         }
     }
 }
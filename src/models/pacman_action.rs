@@ -0,0 +1,9 @@
+use crate::models::pacman_action_kind::PacmanActionKind;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PacmanAction {
+    pub kind: PacmanActionKind,
+    pub package: String,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+}
@@ -0,0 +1,33 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PacmanActionKind {
+    Installed,
+    Upgraded,
+    Downgraded,
+    Reinstalled,
+    Removed,
+}
+
+impl PacmanActionKind {
+    pub fn from_verb(verb: &str) -> Option<Self> {
+        match verb {
+            "installed" => Some(PacmanActionKind::Installed),
+            "upgraded" => Some(PacmanActionKind::Upgraded),
+            "downgraded" => Some(PacmanActionKind::Downgraded),
+            "reinstalled" => Some(PacmanActionKind::Reinstalled),
+            "removed" => Some(PacmanActionKind::Removed),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PacmanActionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PacmanActionKind::Installed => write!(f, "installed"),
+            PacmanActionKind::Upgraded => write!(f, "upgraded"),
+            PacmanActionKind::Downgraded => write!(f, "downgraded"),
+            PacmanActionKind::Reinstalled => write!(f, "reinstalled"),
+            PacmanActionKind::Removed => write!(f, "removed"),
+        }
+    }
+}
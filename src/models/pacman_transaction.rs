@@ -0,0 +1,16 @@
+use chrono::{DateTime, Local};
+
+use crate::models::pacman_action::PacmanAction;
+
+#[derive(Debug, Clone)]
+pub struct PacmanTransaction {
+    pub started_at: DateTime<Local>,
+    pub finished_at: Option<DateTime<Local>>,
+    pub command: Option<String>,
+    pub actions: Vec<PacmanAction>,
+    pub warnings: Vec<String>,
+    pub hooks: Vec<String>,
+    pub output: Vec<String>,
+    pub completed: bool,
+    pub snapshot_before: Option<String>,
+}
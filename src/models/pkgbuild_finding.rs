@@ -0,0 +1,10 @@
+use crate::models::pkgbuild_risk_level::PkgbuildRiskLevel;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PkgbuildFinding {
+    pub rule: String,
+    pub level: PkgbuildRiskLevel,
+    pub title: String,
+    pub line: Option<usize>,
+    pub detail: String,
+}
@@ -0,0 +1,16 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PkgbuildRiskLevel {
+    Notice,
+    Warning,
+    Danger,
+}
+
+impl PkgbuildRiskLevel {
+    pub fn css_class(&self) -> &'static str {
+        return match self {
+            PkgbuildRiskLevel::Notice => "dim-label",
+            PkgbuildRiskLevel::Warning => "warning",
+            PkgbuildRiskLevel::Danger => "error",
+        };
+    }
+}
@@ -0,0 +1,8 @@
+use crate::models::stale_process::StaleProcess;
+
+#[derive(Debug, Clone)]
+pub struct RestartReport {
+    pub kernel_missing: bool,
+    pub running_kernel: String,
+    pub processes: Vec<StaleProcess>,
+}
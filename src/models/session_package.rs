@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPackage {
+    pub name: String,
+    pub repository: String,
+    pub old_version: String,
+    pub new_version: String,
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::models::session_package::SessionPackage;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotMetadata {
+    pub snapshot: String,
+    pub provider: String,
+    pub created_at: DateTime<Local>,
+    pub host: String,
+    pub kernel: String,
+    pub packages: Vec<SessionPackage>,
+    pub aur_packages: Vec<String>,
+    pub installed_packages: BTreeMap<String, String>,
+}
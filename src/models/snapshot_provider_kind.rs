@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub enum SnapshotProviderKind {
+    #[default]
+    Timeshift,
+    Snapper,
+    Btrfs,
+}
+
+impl SnapshotProviderKind {
+    pub fn id(&self) -> &'static str {
+        return match self {
+            SnapshotProviderKind::Timeshift => "timeshift",
+            SnapshotProviderKind::Snapper => "snapper",
+            SnapshotProviderKind::Btrfs => "btrfs",
+        };
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        return match id {
+            "timeshift" => Some(SnapshotProviderKind::Timeshift),
+            "snapper" => Some(SnapshotProviderKind::Snapper),
+            "btrfs" => Some(SnapshotProviderKind::Btrfs),
+            _ => None,
+        };
+    }
+
+    pub fn all() -> Vec<Self> {
+        return vec![
+            SnapshotProviderKind::Timeshift,
+            SnapshotProviderKind::Snapper,
+            SnapshotProviderKind::Btrfs,
+        ];
+    }
+}
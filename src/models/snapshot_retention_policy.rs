@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct SnapshotRetentionPolicy {
+    pub keep_last: u32,
+    pub keep_daily: u32,
+    pub keep_weekly: u32,
+    pub pinned: Vec<String>,
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone)]
+pub struct SpaceShortfall {
+    pub paths: Vec<String>,
+    pub required: u64,
+    pub available: u64,
+}
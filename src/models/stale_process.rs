@@ -0,0 +1,8 @@
+#[derive(Debug, Clone)]
+pub struct StaleProcess {
+    pub pid: u32,
+    pub command: String,
+    pub libraries: Vec<String>,
+    pub unit: Option<String>,
+    pub user_id: Option<u32>,
+}
@@ -0,0 +1,10 @@
+use chrono::{DateTime, Local};
+
+#[derive(Debug, Clone)]
+pub struct SystemSnapshot {
+    pub id: String,
+    pub created_at: Option<DateTime<Local>>,
+    pub description: String,
+    pub labels: Vec<String>,
+    pub post_id: Option<String>,
+}
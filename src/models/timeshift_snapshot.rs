@@ -0,0 +1,6 @@
+#[derive(Debug, Clone)]
+pub struct TimeshiftSnapshot {
+    pub name: String,
+    pub tags: String,
+    pub comment: Option<String>,
+}
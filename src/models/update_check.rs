@@ -0,0 +1,7 @@
+use crate::models::package_update::PackageUpdate;
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateCheck {
+    pub updates: Vec<PackageUpdate>,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,31 @@
+use glib::clone;
+use gtk4::ApplicationWindow;
+use std::time::Duration;
+
+use crate::helpers::aur_builder::read_build_statuses;
+use crate::models::aur_build::AurBuild;
+use crate::ui::terminal_page::{is_terminal_task_running, show_terminal_progress};
+
+// The build script appends to its status file, so polling it keeps the badges above the
+// terminal in step with the packages that were built so far.
+pub fn watch_aur_build(window: &ApplicationWindow, build: AurBuild) {
+    show_terminal_progress(window, &read_build_statuses(&build));
+
+    glib::timeout_add_local(
+        Duration::from_millis(500),
+        clone!(
+            #[weak]
+            window,
+            #[upgrade_or]
+            glib::ControlFlow::Break,
+            move || {
+                show_terminal_progress(&window, &read_build_statuses(&build));
+
+                if is_terminal_task_running() {
+                    return glib::ControlFlow::Continue;
+                }
+                return glib::ControlFlow::Break;
+            }
+        ),
+    );
+}
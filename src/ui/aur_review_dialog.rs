@@ -0,0 +1,389 @@
+use glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, ButtonsType, CheckButton, Label, MessageDialog,
+    MessageType, Notebook, Orientation, ResponseType, ScrolledWindow, TextBuffer, TextTag,
+    TextView, Window,
+};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::constants::AUR_NAME;
+use crate::helpers::aur::detect_aur_helper;
+use crate::helpers::aur_builder::resolve_aur_build_plan;
+use crate::helpers::aur_review::{mark_aur_reviewed, prepare_aur_review};
+use crate::models::aur_review::AurReview;
+use crate::models::package_update::PackageUpdate;
+use crate::ui::dialogs::{create_progress_dialog, show_error_dialog};
+use crate::ui::risk_badges::fill_risk_badges;
+
+// Called with false when the AUR packages must be left out of the update.
+type ApprovedCallback = Box<dyn FnOnce(bool)>;
+
+pub fn review_aur_changes(
+    window: &ApplicationWindow,
+    packages: &[PackageUpdate],
+    on_approved: impl FnOnce(bool) + 'static,
+) {
+    let mut targets: Vec<(String, String)> = Vec::new();
+    for package in packages.iter().filter(|p| p.repository == AUR_NAME) {
+        let package_base = package
+            .aur_info
+            .as_ref()
+            .map(|info| info.package_base.clone())
+            .unwrap_or_else(|| package.name.clone());
+        if !targets.iter().any(|(_, base)| *base == package_base) {
+            targets.push((package.name.clone(), package_base));
+        }
+    }
+
+    if targets.is_empty() {
+        on_approved(true);
+        return;
+    }
+
+    let has_other_packages = packages.iter().any(|p| p.repository != AUR_NAME);
+
+    let progress_dialog = create_progress_dialog(
+        window.upcast_ref::<Window>(),
+        "Fetching Build Scripts",
+        "Downloading the PKGBUILDs of the selected AUR packages for review...",
+    );
+    let cancelled = Rc::new(Cell::new(false));
+    progress_dialog.connect_response(clone!(
+        #[strong]
+        cancelled,
+        move |dialog, _| {
+            cancelled.set(true);
+            dialog.close();
+        }
+    ));
+
+    let window = window.clone();
+    let on_approved: ApprovedCallback = Box::new(on_approved);
+    let helper = detect_aur_helper().map(|helper| helper.binary);
+    let builtin_builder = helper.is_none();
+
+    glib::spawn_future_local(async move {
+        let result = gio::spawn_blocking(move || {
+            // A package that cannot be fetched is reported on its own, the others are still reviewed.
+            let mut failures = Vec::new();
+
+            // The built-in builder also builds missing AUR dependencies, so those are reviewed too.
+            if builtin_builder {
+                let names: Vec<String> = targets.iter().map(|(name, _)| name.clone()).collect();
+                match resolve_aur_build_plan(&names) {
+                    Ok(plan) => {
+                        for step in plan.steps {
+                            if !targets.iter().any(|(_, base)| *base == step.package_base) {
+                                targets.push((step.packages[0].clone(), step.package_base));
+                            }
+                        }
+                    }
+                    Err(e) => failures.push(format!("AUR dependencies: {:#}", e)),
+                }
+            }
+
+            let mut reviews = Vec::new();
+            for (package, package_base) in &targets {
+                match prepare_aur_review(package, package_base) {
+                    Ok(review) => reviews.push(review),
+                    Err(e) => failures.push(format!("{}: {:#}", package_base, e)),
+                }
+            }
+
+            (reviews, failures)
+        })
+        .await;
+
+        if cancelled.get() {
+            return;
+        }
+        progress_dialog.close();
+
+        let (reviews, failures) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("Error in background thread: {:?}", e);
+                return;
+            }
+        };
+
+        let pending: Vec<AurReview> = reviews
+            .into_iter()
+            .filter(|review| review.reviewed_commit.as_deref() != Some(review.commit.as_str()))
+            .collect();
+
+        if pending.is_empty() && failures.is_empty() {
+            on_approved(true);
+            return;
+        }
+
+        if pending.is_empty() {
+            let error = failures.join("\n");
+            if has_other_packages {
+                offer_skipping_aur_packages(&window, &error, on_approved);
+            } else {
+                show_error_dialog(
+                    window.upcast_ref::<Window>(),
+                    "Cannot Review AUR Packages",
+                    &format!(
+                        "The build scripts could not be fetched, so nothing will be built.\n\n{}",
+                        error
+                    ),
+                );
+            }
+            return;
+        }
+
+        show_review_window(&window, pending, &failures, helper.as_deref(), on_approved);
+    });
+}
+
+fn offer_skipping_aur_packages(
+    parent: &ApplicationWindow,
+    error: &str,
+    on_approved: ApprovedCallback,
+) {
+    let dialog = MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .message_type(MessageType::Warning)
+        .buttons(ButtonsType::None)
+        .text("Cannot Review AUR Packages")
+        .secondary_text(format!(
+            "The build scripts could not be fetched, so the AUR packages cannot be built. The other updates can still be installed.\n\n{}",
+            error
+        ))
+        .build();
+
+    dialog.add_button("Cancel", ResponseType::Cancel);
+    dialog.add_button("Skip AUR Packages", ResponseType::Accept);
+
+    let on_approved = Cell::new(Some(on_approved));
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+        if response == ResponseType::Accept {
+            if let Some(on_approved) = on_approved.take() {
+                on_approved(false);
+            }
+        }
+    });
+
+    dialog.present();
+}
+
+fn show_review_window(
+    parent: &ApplicationWindow,
+    reviews: Vec<AurReview>,
+    failures: &[String],
+    helper: Option<&str>,
+    on_approved: ApprovedCallback,
+) {
+    let dialog = Window::builder()
+        .title("Review AUR Build Scripts")
+        .transient_for(parent)
+        .modal(true)
+        .default_width(900)
+        .default_height(640)
+        .build();
+
+    let content_box = GtkBox::new(Orientation::Vertical, 12);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+    content_box.set_margin_top(12);
+    content_box.set_margin_bottom(12);
+
+    let hint_label = Label::new(Some(
+        "AUR packages run their PKGBUILD and install scripts with your permissions. Read the changes and mark each package as reviewed before building.",
+    ));
+    hint_label.set_xalign(0.0);
+    hint_label.set_wrap(true);
+    content_box.append(&hint_label);
+
+    // Helpers download the build scripts again themselves, so only the built-in builder builds what was reviewed.
+    if let Some(helper) = helper {
+        let helper_label = Label::new(Some(&format!(
+            "{} fetches the build scripts again when it builds, so it may build a newer version than the one shown here. Select the built-in builder in the settings to build exactly the reviewed commits.",
+            helper
+        )));
+        helper_label.set_xalign(0.0);
+        helper_label.set_wrap(true);
+        helper_label.add_css_class("warning");
+        content_box.append(&helper_label);
+    }
+
+    if !failures.is_empty() {
+        let failures_label = Label::new(Some(&format!(
+            "These build scripts could not be fetched and were not reviewed. Building stops at them unless they can be fetched by then:\n{}",
+            failures.join("\n")
+        )));
+        failures_label.set_xalign(0.0);
+        failures_label.set_wrap(true);
+        failures_label.add_css_class("warning");
+        content_box.append(&failures_label);
+    }
+
+    let notebook = Notebook::new();
+    notebook.set_vexpand(true);
+    notebook.set_scrollable(true);
+    content_box.append(&notebook);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(gtk4::Align::End);
+
+    let cancel_btn = Button::with_label("Cancel");
+    let build_btn = Button::with_label("Build");
+    build_btn.add_css_class("suggested-action");
+    build_btn.set_sensitive(false);
+
+    button_box.append(&cancel_btn);
+    button_box.append(&build_btn);
+    content_box.append(&button_box);
+
+    let checks: Rc<RefCell<Vec<CheckButton>>> = Rc::new(RefCell::new(Vec::new()));
+
+    for review in &reviews {
+        let page_box = GtkBox::new(Orientation::Vertical, 6);
+        page_box.set_margin_top(6);
+
+        let summary = match &review.reviewed_commit {
+            Some(reviewed) => format!(
+                "Changes from {} to {}",
+                short_commit(reviewed),
+                short_commit(&review.commit)
+            ),
+            None => format!(
+                "First review, showing the full files at {}",
+                short_commit(&review.commit)
+            ),
+        };
+        let summary_label = Label::new(Some(&summary));
+        summary_label.set_xalign(0.0);
+        summary_label.add_css_class("dim-label");
+        page_box.append(&summary_label);
+
+        let badge_box = GtkBox::new(Orientation::Horizontal, 8);
+        fill_risk_badges(&badge_box, &review.findings);
+        page_box.append(&badge_box);
+
+        let diff_view = create_diff_view(&review.diff);
+        let scrolled = ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .child(&diff_view)
+            .build();
+        page_box.append(&scrolled);
+
+        let reviewed_check =
+            CheckButton::with_label(&format!("I have reviewed {}", review.package_base));
+        reviewed_check.connect_toggled(clone!(
+            #[weak]
+            build_btn,
+            #[strong]
+            checks,
+            move |_| {
+                build_btn.set_sensitive(checks.borrow().iter().all(|check| check.is_active()));
+            }
+        ));
+        checks.borrow_mut().push(reviewed_check.clone());
+        page_box.append(&reviewed_check);
+
+        notebook.append_page(&page_box, Some(&Label::new(Some(&review.package))));
+    }
+
+    dialog.set_child(Some(&content_box));
+
+    cancel_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        move |_| {
+            dialog.close();
+        }
+    ));
+
+    let on_approved = Cell::new(Some(on_approved));
+    build_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        move |_| {
+            for review in &reviews {
+                if let Err(e) = mark_aur_reviewed(&review.package_base, &review.commit) {
+                    eprintln!("Failed to record review of {}: {}", review.package_base, e);
+                }
+            }
+
+            dialog.close();
+            if let Some(on_approved) = on_approved.take() {
+                on_approved(true);
+            }
+        }
+    ));
+
+    dialog.present();
+}
+
+fn create_diff_view(diff: &str) -> TextView {
+    let buffer = TextBuffer::new(None);
+    let tag_table = buffer.tag_table();
+
+    let added_tag = TextTag::builder()
+        .name("added")
+        .foreground("#26a269")
+        .build();
+    let removed_tag = TextTag::builder()
+        .name("removed")
+        .foreground("#c01c28")
+        .build();
+    let hunk_tag = TextTag::builder()
+        .name("hunk")
+        .foreground("#1c71d8")
+        .build();
+    let header_tag = TextTag::builder().name("header").weight(700).build();
+    tag_table.add(&added_tag);
+    tag_table.add(&removed_tag);
+    tag_table.add(&hunk_tag);
+    tag_table.add(&header_tag);
+
+    if diff.trim().is_empty() {
+        buffer.set_text("No file changed since your last review.");
+    }
+
+    for line in diff.split_inclusive('\n') {
+        let tag = if line.starts_with("diff --git")
+            || line.starts_with("index ")
+            || line.starts_with("+++")
+            || line.starts_with("---")
+            || line.starts_with("new file")
+            || line.starts_with("deleted file")
+        {
+            Some(&header_tag)
+        } else if line.starts_with("@@") {
+            Some(&hunk_tag)
+        } else if line.starts_with('+') {
+            Some(&added_tag)
+        } else if line.starts_with('-') {
+            Some(&removed_tag)
+        } else {
+            None
+        };
+
+        let mut end = buffer.end_iter();
+        match tag {
+            Some(tag) => buffer.insert_with_tags(&mut end, line, &[tag]),
+            None => buffer.insert(&mut end, line),
+        }
+    }
+
+    let text_view = TextView::with_buffer(&buffer);
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+
+    return text_view;
+}
+
+fn short_commit(commit: &str) -> &str {
+    return &commit[..commit.len().min(8)];
+}
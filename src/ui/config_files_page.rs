@@ -0,0 +1,423 @@
+use glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, Label, ListBox, MessageDialog, MessageType, Notebook,
+    Orientation, ResponseType, ScrolledWindow, SelectionMode, Spinner, TextView, Window,
+};
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::helpers::get_navigation_stack::get_navigation_stack;
+use crate::helpers::pacnew::{build_resolve_command, find_config_file_changes, load_config_merge};
+use crate::models::config_file_change::ConfigFileChange;
+use crate::models::config_merge::ConfigMerge;
+use crate::ui::dialogs::show_error_dialog;
+use crate::ui::main_window::return_to_package_list;
+use crate::ui::terminal_page::run_in_terminal_page;
+
+pub fn create_config_files_page() -> GtkBox {
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let title_box = GtkBox::new(Orientation::Vertical, 6);
+    title_box.set_hexpand(true);
+
+    let title_label = Label::new(Some("Configuration Files"));
+    title_label.add_css_class("title-2");
+    title_label.set_halign(gtk4::Align::Start);
+
+    let subtitle_label = Label::new(Some(
+        "New default configuration (.pacnew) and saved old files (.pacsave) left behind by updates.",
+    ));
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.set_halign(gtk4::Align::Start);
+
+    title_box.append(&title_label);
+    title_box.append(&subtitle_label);
+    header_box.append(&title_box);
+
+    let spinner = Spinner::new();
+    spinner.set_valign(gtk4::Align::Center);
+    header_box.append(&spinner);
+
+    let back_btn = Button::with_label("Back");
+    back_btn.set_valign(gtk4::Align::Center);
+    header_box.append(&back_btn);
+
+    main_box.append(&header_box);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some("No .pacnew or .pacsave files found."));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&list_box)
+        .build();
+
+    main_box.append(&scrolled);
+
+    main_box.connect_map(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        move |_| {
+            load_config_file_changes(&list_box, &spinner);
+        }
+    ));
+
+    back_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        move |_| {
+            return_to_package_list(&main_box);
+        }
+    ));
+
+    return main_box;
+}
+
+pub fn offer_config_file_review(window: &ApplicationWindow) {
+    let window = window.clone();
+
+    glib::spawn_future_local(async move {
+        let changes = match gio::spawn_blocking(|| find_config_file_changes(Path::new("/"))).await {
+            Ok(changes) => changes,
+            Err(e) => {
+                eprintln!("Error in background thread: {:?}", e);
+                return;
+            }
+        };
+
+        if changes.is_empty() {
+            return;
+        }
+
+        let dialog = MessageDialog::builder()
+            .transient_for(&window)
+            .modal(true)
+            .message_type(MessageType::Info)
+            .text("Configuration Files Need Attention")
+            .secondary_text(format!(
+                "{} configuration files were left next to their originals by the update:\n\n{}",
+                changes.len(),
+                changes
+                    .iter()
+                    .map(|change| change.path.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ))
+            .build();
+
+        dialog.add_button("Later", ResponseType::Cancel);
+        dialog.add_button("Review", ResponseType::Accept);
+
+        dialog.connect_response(clone!(
+            #[weak]
+            window,
+            move |dialog, response| {
+                dialog.close();
+
+                if response != ResponseType::Accept {
+                    return;
+                }
+
+                if let Some((stack, _, _)) = get_navigation_stack(&window) {
+                    stack.set_visible_child_name("config-files");
+                }
+            }
+        ));
+
+        dialog.show();
+    });
+}
+
+fn load_config_file_changes(list_box: &ListBox, spinner: &Spinner) {
+    while let Some(row) = list_box.row_at_index(0) {
+        list_box.remove(&row);
+    }
+
+    spinner.start();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        async move {
+            let result = gio::spawn_blocking(|| find_config_file_changes(Path::new("/"))).await;
+            spinner.stop();
+
+            let changes = match result {
+                Ok(changes) => changes,
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    return;
+                }
+            };
+
+            for change in &changes {
+                list_box.append(&create_change_row(change));
+            }
+        }
+    ));
+}
+
+fn create_change_row(change: &ConfigFileChange) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Horizontal, 12);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(8);
+    row_box.set_margin_bottom(8);
+
+    let text_box = GtkBox::new(Orientation::Vertical, 4);
+    text_box.set_hexpand(true);
+
+    let name_label = Label::new(None);
+    name_label.set_xalign(0.0);
+    name_label.set_markup(&format!(
+        "<b>{}</b>  {}",
+        glib::markup_escape_text(&change.target.to_string_lossy()),
+        if change.is_pacsave {
+            ".pacsave"
+        } else {
+            ".pacnew"
+        }
+    ));
+    text_box.append(&name_label);
+
+    let owner_label = Label::new(Some(&match &change.package {
+        Some(package) => format!("Owned by {}", package),
+        None => "Not owned by any installed package".to_string(),
+    }));
+    owner_label.set_xalign(0.0);
+    owner_label.add_css_class("dim-label");
+    text_box.append(&owner_label);
+
+    row_box.append(&text_box);
+
+    let merge_btn = Button::with_label("Merge…");
+    merge_btn.set_valign(gtk4::Align::Center);
+
+    let change = change.clone();
+    merge_btn.connect_clicked(move |button| {
+        let Some(window) = button.root().and_downcast::<ApplicationWindow>() else {
+            return;
+        };
+
+        let change = change.clone();
+        glib::spawn_future_local(clone!(
+            #[weak]
+            window,
+            async move {
+                let loaded = change.clone();
+                let result = gio::spawn_blocking(move || load_config_merge(&loaded)).await;
+
+                match result {
+                    Ok(Ok(merge)) => show_merge_window(&window, change, merge),
+                    Ok(Err(e)) => show_error_dialog(
+                        window.upcast_ref::<Window>(),
+                        "Cannot Merge File",
+                        &format!("{:#}", e),
+                    ),
+                    Err(e) => eprintln!("Error in background thread: {:?}", e),
+                }
+            }
+        ));
+    });
+    row_box.append(&merge_btn);
+
+    return row_box;
+}
+
+fn show_merge_window(parent: &ApplicationWindow, change: ConfigFileChange, merge: ConfigMerge) {
+    let dialog = Window::builder()
+        .title(format!("Merge {}", change.target.display()))
+        .transient_for(parent)
+        .modal(true)
+        .default_width(900)
+        .default_height(640)
+        .build();
+
+    let content_box = GtkBox::new(Orientation::Vertical, 12);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+    content_box.set_margin_top(12);
+    content_box.set_margin_bottom(12);
+
+    let hint = if merge.has_conflicts {
+        "The result has conflicts marked with <<<<<<< and >>>>>>>. Edit them before saving."
+    } else if merge.original.is_some() {
+        "Your changes and the package's changes were merged automatically. Check the result before saving."
+    } else {
+        "The original file is not in the package cache, so the result starts from your version."
+    };
+    let hint_label = Label::new(Some(hint));
+    hint_label.set_xalign(0.0);
+    hint_label.set_wrap(true);
+    content_box.append(&hint_label);
+
+    let notebook = Notebook::new();
+    notebook.set_vexpand(true);
+
+    let result_view = create_text_view(&merge.merged, true);
+    notebook.append_page(
+        &wrap_scrolled(&result_view),
+        Some(&Label::new(Some("Result"))),
+    );
+    notebook.append_page(
+        &wrap_scrolled(&create_text_view(&merge.mine, false)),
+        Some(&Label::new(Some("Yours"))),
+    );
+    if let Some(new) = &merge.new {
+        notebook.append_page(
+            &wrap_scrolled(&create_text_view(new, false)),
+            Some(&Label::new(Some("New"))),
+        );
+    }
+    if let Some(original) = &merge.original {
+        notebook.append_page(
+            &wrap_scrolled(&create_text_view(original, false)),
+            Some(&Label::new(Some("Original"))),
+        );
+    }
+    content_box.append(&notebook);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(gtk4::Align::End);
+
+    let cancel_btn = Button::with_label("Cancel");
+    let keep_btn = Button::with_label("Keep Mine");
+    let take_btn = Button::with_label("Take New");
+    let save_btn = Button::with_label("Save Result");
+    save_btn.add_css_class("suggested-action");
+
+    button_box.append(&cancel_btn);
+    button_box.append(&keep_btn);
+    button_box.append(&take_btn);
+    button_box.append(&save_btn);
+    content_box.append(&button_box);
+
+    dialog.set_child(Some(&content_box));
+
+    cancel_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        move |_| {
+            dialog.close();
+        }
+    ));
+
+    let is_pacsave = change.is_pacsave;
+    let resolve = Rc::new(clone!(
+        #[weak]
+        dialog,
+        #[weak]
+        parent,
+        move |content: Option<String>| {
+            let (args, content_file) = match build_resolve_command(&change, content.as_deref()) {
+                Ok(command) => command,
+                Err(e) => {
+                    show_error_dialog(
+                        dialog.upcast_ref::<Window>(),
+                        "Failed to Save",
+                        &format!("{:#}", e),
+                    );
+                    return;
+                }
+            };
+
+            let finished_window = parent.clone();
+            let result = run_in_terminal_page(
+                &parent,
+                &format!("Updating {}", change.target.display()),
+                "Configuration Update",
+                args.iter().map(|arg| arg.as_str()).collect(),
+                move |exit_code| {
+                    drop(content_file);
+
+                    // Going back reloads the list. On failure the terminal stays up so the
+                    // error can be read.
+                    if exit_code == 0 {
+                        if let Some((stack, _, _)) = get_navigation_stack(&finished_window) {
+                            stack.set_visible_child_name("config-files");
+                        }
+                    }
+                },
+            );
+
+            match result {
+                Ok(()) => dialog.close(),
+                Err(e) => show_error_dialog(
+                    dialog.upcast_ref::<Window>(),
+                    "Failed to Save",
+                    &e.to_string(),
+                ),
+            }
+        }
+    ));
+
+    let mine = merge.mine.clone();
+    keep_btn.connect_clicked(clone!(
+        #[strong]
+        resolve,
+        move |_| {
+            // Keeping a .pacnew just drops it; keeping a .pacsave puts it back in place.
+            resolve(if is_pacsave { Some(mine.clone()) } else { None });
+        }
+    ));
+
+    let new = merge.new.clone();
+    take_btn.connect_clicked(clone!(
+        #[strong]
+        resolve,
+        move |_| {
+            resolve(if is_pacsave { None } else { new.clone() });
+        }
+    ));
+
+    save_btn.connect_clicked(clone!(
+        #[weak]
+        result_view,
+        move |_| {
+            let buffer = result_view.buffer();
+            let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+            resolve(Some(text.to_string()));
+        }
+    ));
+
+    dialog.present();
+}
+
+fn create_text_view(text: &str, editable: bool) -> TextView {
+    let text_view = TextView::new();
+    text_view.set_editable(editable);
+    text_view.set_cursor_visible(editable);
+    text_view.set_monospace(true);
+    text_view.buffer().set_text(text);
+
+    return text_view;
+}
+
+fn wrap_scrolled(text_view: &TextView) -> ScrolledWindow {
+    return ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(text_view)
+        .build();
+}
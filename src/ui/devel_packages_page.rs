@@ -0,0 +1,207 @@
+use glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    Box as GtkBox, Button, Label, ListBox, Orientation, ScrolledWindow, SelectionMode, Spinner,
+    Switch,
+};
+
+use crate::helpers::devel_packages::list_devel_packages;
+use crate::helpers::settings::{load_settings, save_settings};
+use crate::models::devel_package::DevelPackage;
+use crate::ui::main_window::return_to_package_list;
+
+pub fn create_devel_packages_page() -> GtkBox {
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let title_box = GtkBox::new(Orientation::Vertical, 6);
+    title_box.set_hexpand(true);
+
+    let title_label = Label::new(Some("Devel Packages"));
+    title_label.add_css_class("title-2");
+    title_label.set_halign(gtk4::Align::Start);
+
+    let subtitle_label = Label::new(Some(
+        "Devel (-git) packages are checked for new upstream commits with git ls-remote. Choose which ones.",
+    ));
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.set_halign(gtk4::Align::Start);
+
+    title_box.append(&title_label);
+    title_box.append(&subtitle_label);
+    header_box.append(&title_box);
+
+    let spinner = Spinner::new();
+    spinner.set_valign(gtk4::Align::Center);
+    header_box.append(&spinner);
+
+    let back_btn = Button::with_label("Back");
+    back_btn.set_valign(gtk4::Align::Center);
+    header_box.append(&back_btn);
+
+    main_box.append(&header_box);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some("No devel packages are installed."));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&list_box)
+        .build();
+
+    main_box.append(&scrolled);
+
+    main_box.connect_map(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        empty_label,
+        move |_| {
+            load_devel_packages(&list_box, &spinner, &empty_label);
+        }
+    ));
+
+    back_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        move |_| {
+            return_to_package_list(&main_box);
+        }
+    ));
+
+    return main_box;
+}
+
+fn load_devel_packages(list_box: &ListBox, spinner: &Spinner, empty_label: &Label) {
+    while let Some(row) = list_box.row_at_index(0) {
+        list_box.remove(&row);
+    }
+
+    spinner.start();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        empty_label,
+        async move {
+            let result = gio::spawn_blocking(list_devel_packages).await;
+            spinner.stop();
+
+            let packages = match result {
+                Ok(Ok(packages)) => packages,
+                Ok(Err(e)) => {
+                    empty_label.set_text(&format!("Failed to list devel packages: {:#}", e));
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    return;
+                }
+            };
+
+            empty_label.set_text("No devel packages are installed.");
+
+            for package in &packages {
+                list_box.append(&create_devel_row(package));
+            }
+        }
+    ));
+}
+
+fn create_devel_row(package: &DevelPackage) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Horizontal, 12);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(8);
+    row_box.set_margin_bottom(8);
+
+    let text_box = GtkBox::new(Orientation::Vertical, 4);
+    text_box.set_hexpand(true);
+
+    let name_label = Label::new(None);
+    name_label.set_xalign(0.0);
+    name_label.set_markup(&format!(
+        "<b>{}</b>  {}",
+        glib::markup_escape_text(&package.name),
+        glib::markup_escape_text(&package.version)
+    ));
+    text_box.append(&name_label);
+
+    let details = if !package.checkable {
+        "Upstream changes are only checked for -git packages, this one updates with its AUR version"
+            .to_string()
+    } else if package.sources.is_empty() {
+        "Upstream state unknown: no revision recorded yet".to_string()
+    } else {
+        package
+            .sources
+            .iter()
+            .map(|source| {
+                format!(
+                    "{} {} at {}",
+                    source.url,
+                    source.reference.trim_start_matches("refs/heads/"),
+                    &source.revision[..source.revision.len().min(8)]
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let details_label = Label::new(Some(&details));
+    details_label.set_xalign(0.0);
+    details_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    details_label.add_css_class("dim-label");
+    text_box.append(&details_label);
+
+    row_box.append(&text_box);
+
+    let check_switch = Switch::new();
+    check_switch.set_tooltip_text(Some("Check for new upstream commits"));
+    check_switch.set_valign(gtk4::Align::Center);
+    check_switch.set_active(package.checked);
+    check_switch.set_sensitive(package.checkable);
+    row_box.append(&check_switch);
+
+    let name = package.name.clone();
+    check_switch.connect_active_notify(move |switch| {
+        set_devel_check_override(&name, switch.is_active());
+    });
+
+    return row_box;
+}
+
+// Only packages that differ from the global setting are stored, so they follow it otherwise.
+fn set_devel_check_override(name: &str, checked: bool) {
+    let mut settings = load_settings();
+    if checked == settings.check_devel_updates {
+        settings.devel_check_overrides.remove(name);
+    } else {
+        settings
+            .devel_check_overrides
+            .insert(name.to_string(), checked);
+    }
+
+    if let Err(e) = save_settings(&settings) {
+        eprintln!("Failed to save settings: {}", e);
+    }
+}
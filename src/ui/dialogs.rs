@@ -1,5 +1,6 @@
+use crate::helpers::logind::reboot;
 use gtk4::{ApplicationWindow, ResponseType, prelude::*};
-use gtk4::{Box as GtkBox, ButtonsType, Dialog, MessageDialog, MessageType, Spinner, Window};
+use gtk4::{Box as GtkBox, ButtonsType, MessageDialog, MessageType, Spinner, Window};
 
 pub fn show_error_dialog(parent: &Window, title: &str, message: &str) {
     let dialog = MessageDialog::builder()
@@ -18,7 +19,7 @@ pub fn show_error_dialog(parent: &Window, title: &str, message: &str) {
     dialog.show();
 }
 
-pub fn create_progress_dialog(parent: &Window, title: &str, message: &str) -> Dialog {
+pub fn create_progress_dialog(parent: &Window, title: &str, message: &str) -> MessageDialog {
     let dialog = MessageDialog::builder()
         .transient_for(parent)
         .modal(true)
@@ -44,7 +45,7 @@ pub fn create_progress_dialog(parent: &Window, title: &str, message: &str) -> Di
 
     dialog.show();
 
-    return dialog.upcast::<Dialog>();
+    return dialog;
 }
 
 pub fn show_confirm_dialog(
@@ -67,3 +68,35 @@ pub fn show_confirm_dialog(
 
     return dialog;
 }
+
+pub fn offer_reboot(parent: &ApplicationWindow, title: &str, message: &str) {
+    let dialog = MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .message_type(MessageType::Question)
+        .text(title)
+        .secondary_text(message)
+        .build();
+
+    dialog.add_button("Later", ResponseType::Cancel);
+    dialog.add_button("Reboot Now", ResponseType::Accept);
+
+    let parent = parent.clone();
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+
+        if response != ResponseType::Accept {
+            return;
+        }
+
+        if let Err(e) = reboot() {
+            show_error_dialog(
+                parent.upcast_ref::<Window>(),
+                "Failed to Reboot",
+                &format!("{:#}", e),
+            );
+        }
+    });
+
+    dialog.show();
+}
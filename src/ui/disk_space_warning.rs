@@ -0,0 +1,154 @@
+use glib::format_size;
+use gtk4::prelude::*;
+use gtk4::{ApplicationWindow, MessageDialog, MessageType, ResponseType, Window};
+use std::cell::Cell;
+
+use crate::helpers::checkpoint::get_pinned_cache_files;
+use crate::helpers::package_cache::{
+    list_cached_packages, plan_cache_cleanup, remove_cached_packages,
+};
+use crate::helpers::pacman_info::get_installed_packages;
+use crate::helpers::transaction_space::check_transaction_space;
+use crate::models::cache_cleanup_policy::CacheCleanupPolicy;
+use crate::models::cached_package::CachedPackage;
+use crate::models::package_update::PackageUpdate;
+use crate::models::space_shortfall::SpaceShortfall;
+use crate::ui::dialogs::show_error_dialog;
+
+type ReadyCallback = Box<dyn FnOnce()>;
+
+pub fn ensure_disk_space(
+    window: &ApplicationWindow,
+    packages: Vec<PackageUpdate>,
+    on_ready: impl FnOnce() + 'static,
+) {
+    check_space_and_continue(window.clone(), packages, Box::new(on_ready));
+}
+
+fn check_space_and_continue(
+    window: ApplicationWindow,
+    packages: Vec<PackageUpdate>,
+    on_ready: ReadyCallback,
+) {
+    glib::spawn_future_local(async move {
+        let check_packages = packages.clone();
+        let result = gio::spawn_blocking(move || {
+            let shortfalls = check_transaction_space(&check_packages)?;
+            if shortfalls.is_empty() {
+                return Ok((shortfalls, Vec::new()));
+            }
+
+            let installed = get_installed_packages()?;
+            let policy = CacheCleanupPolicy {
+                keep_versions: 0,
+                remove_uninstalled: true,
+                pinned_files: get_pinned_cache_files()?,
+            };
+            let cleanup = plan_cache_cleanup(&list_cached_packages(), &installed, &policy);
+
+            return Ok::<_, anyhow::Error>((shortfalls, cleanup));
+        })
+        .await;
+
+        match result {
+            Ok(Ok((shortfalls, _))) if shortfalls.is_empty() => on_ready(),
+            Ok(Ok((shortfalls, cleanup))) => {
+                show_space_warning(window, packages, shortfalls, cleanup, on_ready);
+            }
+            Ok(Err(e)) => {
+                eprintln!("Failed to check free disk space: {:#}", e);
+                on_ready();
+            }
+            Err(e) => {
+                eprintln!("Error in background thread: {:?}", e);
+                on_ready();
+            }
+        }
+    });
+}
+
+fn show_space_warning(
+    window: ApplicationWindow,
+    packages: Vec<PackageUpdate>,
+    shortfalls: Vec<SpaceShortfall>,
+    cleanup: Vec<CachedPackage>,
+    on_ready: ReadyCallback,
+) {
+    let freeable: u64 = cleanup.iter().map(|package| package.size).sum();
+
+    let mut message = shortfalls
+        .iter()
+        .map(|shortfall| {
+            format!(
+                "{} needs {} but only {} is free.",
+                shortfall.paths.join(" and "),
+                format_size(shortfall.required),
+                format_size(shortfall.available)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if freeable > 0 {
+        message.push_str(&format!(
+            "\n\nRemoving {} cached package files that are not installed would free {}.",
+            cleanup.len(),
+            format_size(freeable)
+        ));
+    }
+
+    let dialog = MessageDialog::builder()
+        .transient_for(&window)
+        .modal(true)
+        .message_type(MessageType::Warning)
+        .text("Not Enough Disk Space")
+        .secondary_text(message)
+        .build();
+
+    dialog.add_button("Cancel", ResponseType::Cancel);
+    if freeable > 0 {
+        dialog.add_button(
+            &format!("Clean Cache ({})", format_size(freeable)),
+            ResponseType::Apply,
+        );
+    }
+    let install_button = dialog.add_button("Install Anyway", ResponseType::Accept);
+    install_button.add_css_class("destructive-action");
+
+    let on_ready = Cell::new(Some(on_ready));
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+
+        let Some(on_ready) = on_ready.take() else {
+            return;
+        };
+
+        match response {
+            ResponseType::Accept => on_ready(),
+            ResponseType::Apply => {
+                clean_cache_and_retry(window.clone(), packages.clone(), cleanup.clone(), on_ready);
+            }
+            _ => {}
+        }
+    });
+
+    dialog.show();
+}
+
+fn clean_cache_and_retry(
+    window: ApplicationWindow,
+    packages: Vec<PackageUpdate>,
+    cleanup: Vec<CachedPackage>,
+    on_ready: ReadyCallback,
+) {
+    glib::spawn_future_local(async move {
+        match gio::spawn_blocking(move || remove_cached_packages(&cleanup)).await {
+            Ok(Ok(_)) => check_space_and_continue(window, packages, on_ready),
+            Ok(Err(e)) => show_error_dialog(
+                window.upcast_ref::<Window>(),
+                "Failed to Clean Package Cache",
+                &format!("{:#}", e),
+            ),
+            Err(e) => eprintln!("Error in background thread: {:?}", e),
+        }
+    });
+}
@@ -0,0 +1,311 @@
+use glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, ButtonsType, Label, ListBox, MessageDialog,
+    MessageType, Orientation, ResponseType, ScrolledWindow, SelectionMode, Spinner, Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::helpers::downgrade::{
+    find_dependency_conflicts, get_installed_version, list_downgrade_candidates,
+};
+use crate::helpers::pacman_conf::{add_ignored_package, get_ignored_packages};
+use crate::models::downgrade_candidate::DowngradeCandidate;
+use crate::ui::dialogs::show_error_dialog;
+use crate::ui::terminal_page::run_in_terminal_page;
+
+pub fn show_downgrade_dialog(window: &ApplicationWindow, package: &str) {
+    let dialog = Window::builder()
+        .title(format!("Downgrade {}", package))
+        .transient_for(window)
+        .modal(true)
+        .default_width(560)
+        .default_height(460)
+        .build();
+
+    let content_box = GtkBox::new(Orientation::Vertical, 12);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+    content_box.set_margin_top(12);
+    content_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let installed_label = Label::new(Some("Looking for older versions..."));
+    installed_label.set_xalign(0.0);
+    installed_label.set_hexpand(true);
+    header_box.append(&installed_label);
+
+    let spinner = Spinner::new();
+    spinner.start();
+    header_box.append(&spinner);
+
+    content_box.append(&header_box);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::Single);
+    list_box.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some(
+        "No other versions were found in the package cache or the archive.",
+    ));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_wrap(true);
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&list_box)
+        .build();
+    content_box.append(&scrolled);
+
+    let conflicts_label = Label::new(None);
+    conflicts_label.set_xalign(0.0);
+    conflicts_label.set_wrap(true);
+    conflicts_label.add_css_class("error");
+    conflicts_label.set_visible(false);
+    content_box.append(&conflicts_label);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 12);
+    button_box.set_halign(gtk4::Align::End);
+
+    let cancel_btn = Button::with_label("Cancel");
+    let downgrade_btn = Button::with_label("Downgrade");
+    downgrade_btn.add_css_class("destructive-action");
+    downgrade_btn.set_sensitive(false);
+
+    button_box.append(&cancel_btn);
+    button_box.append(&downgrade_btn);
+    content_box.append(&button_box);
+
+    dialog.set_child(Some(&content_box));
+
+    let candidates: Rc<RefCell<Vec<DowngradeCandidate>>> = Rc::new(RefCell::new(Vec::new()));
+    let package = package.to_string();
+
+    let package_clone = package.clone();
+    glib::spawn_future_local(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        installed_label,
+        #[strong]
+        candidates,
+        async move {
+            let result = gio::spawn_blocking(move || {
+                let installed = get_installed_version(&package_clone);
+                let candidates = list_downgrade_candidates(&package_clone);
+                (installed, candidates)
+            })
+            .await;
+
+            spinner.stop();
+
+            let Ok((installed, result)) = result else {
+                installed_label.set_text("Failed to look for older versions.");
+                return;
+            };
+
+            installed_label.set_text(&format!(
+                "Installed version: {}",
+                installed.as_deref().unwrap_or("not installed")
+            ));
+
+            match result {
+                Ok(found) => {
+                    for candidate in &found {
+                        list_box.append(&create_candidate_row(candidate));
+                    }
+                    candidates.replace(found);
+                }
+                Err(e) => {
+                    installed_label.set_text(&format!("Failed to look for older versions: {}", e));
+                }
+            }
+        }
+    ));
+
+    list_box.connect_row_selected(clone!(
+        #[weak]
+        downgrade_btn,
+        #[weak]
+        conflicts_label,
+        #[strong]
+        candidates,
+        #[strong]
+        package,
+        move |_, row| {
+            conflicts_label.set_visible(false);
+
+            let Some(candidate) = row
+                .and_then(|row| usize::try_from(row.index()).ok())
+                .and_then(|index| candidates.borrow().get(index).cloned())
+            else {
+                downgrade_btn.set_sensitive(false);
+                return;
+            };
+
+            downgrade_btn.set_sensitive(true);
+
+            let package = package.clone();
+            glib::spawn_future_local(clone!(
+                #[weak]
+                conflicts_label,
+                async move {
+                    let version = candidate.version.clone();
+                    let result =
+                        gio::spawn_blocking(move || find_dependency_conflicts(&package, &version))
+                            .await;
+
+                    match result {
+                        Ok(Ok(conflicts)) if !conflicts.is_empty() => {
+                            conflicts_label.set_text(&format!(
+                                "Installing {} will break the dependencies of other packages:\n{}",
+                                candidate.version,
+                                conflicts
+                                    .iter()
+                                    .map(|c| format!("• {}", c))
+                                    .collect::<Vec<_>>()
+                                    .join("\n")
+                            ));
+                            conflicts_label.set_visible(true);
+                        }
+                        Ok(Err(e)) => {
+                            eprintln!("Failed to check reverse dependencies: {}", e);
+                        }
+                        _ => {}
+                    }
+                }
+            ));
+        }
+    ));
+
+    cancel_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        move |_| {
+            dialog.close();
+        }
+    ));
+
+    downgrade_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        #[weak]
+        list_box,
+        #[weak]
+        window,
+        #[strong]
+        candidates,
+        move |_| {
+            let Some(candidate) = list_box
+                .selected_row()
+                .and_then(|row| usize::try_from(row.index()).ok())
+                .and_then(|index| candidates.borrow().get(index).cloned())
+            else {
+                return;
+            };
+
+            dialog.close();
+            start_downgrade(&window, &candidate);
+        }
+    ));
+
+    dialog.present();
+}
+
+fn create_candidate_row(candidate: &DowngradeCandidate) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Vertical, 2);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(6);
+    row_box.set_margin_bottom(6);
+
+    let version_label = Label::new(Some(&candidate.version));
+    version_label.set_xalign(0.0);
+    row_box.append(&version_label);
+
+    let source_label = Label::new(Some(if candidate.from_archive {
+        "Package archive"
+    } else {
+        "Package cache"
+    }));
+    source_label.set_xalign(0.0);
+    source_label.add_css_class("dim-label");
+    source_label.add_css_class("caption");
+    row_box.append(&source_label);
+
+    return row_box;
+}
+
+fn start_downgrade(window: &ApplicationWindow, candidate: &DowngradeCandidate) {
+    let package = candidate.name.clone();
+    let window_clone = window.clone();
+
+    let result = run_in_terminal_page(
+        window,
+        &format!("Downgrading {} to {}", candidate.name, candidate.version),
+        "Downgrade",
+        vec!["sudo", "pacman", "-U", &candidate.location],
+        move |exit_code| {
+            if exit_code == 0 && !get_ignored_packages().contains(&package) {
+                offer_ignore_package(&window_clone, &package);
+            }
+        },
+    );
+
+    if let Err(e) = result {
+        show_error_dialog(
+            window.upcast_ref::<Window>(),
+            "Downgrade Error",
+            &format!("Failed to start the downgrade: {}", e),
+        );
+    }
+}
+
+fn offer_ignore_package(window: &ApplicationWindow, package: &str) {
+    let dialog = MessageDialog::builder()
+        .transient_for(window)
+        .modal(true)
+        .message_type(MessageType::Question)
+        .buttons(ButtonsType::None)
+        .text(format!("Keep {} at this version?", package))
+        .secondary_text(format!(
+            "Adding {} to IgnorePkg in /etc/pacman.conf stops it from being upgraded again until you remove it from the list.",
+            package
+        ))
+        .build();
+
+    dialog.add_button("Not Now", ResponseType::Cancel);
+    dialog.add_button("Add to IgnorePkg", ResponseType::Accept);
+
+    let package = package.to_string();
+    dialog.connect_response(clone!(
+        #[weak]
+        window,
+        move |dialog, response| {
+            dialog.close();
+
+            if response != ResponseType::Accept {
+                return;
+            }
+
+            if let Err(e) = add_ignored_package(&package) {
+                show_error_dialog(
+                    window.upcast_ref::<Window>(),
+                    "Failed to Update pacman.conf",
+                    &e.to_string(),
+                );
+            }
+        }
+    ));
+
+    dialog.show();
+}
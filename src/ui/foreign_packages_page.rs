@@ -0,0 +1,233 @@
+use glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, Label, ListBox, ListBoxRow, Orientation,
+    ScrolledWindow, SelectionMode, Spinner, Window,
+};
+
+use crate::helpers::foreign_packages::{find_foreign_package_issues, record_aur_maintainers};
+use crate::models::foreign_package_issue::ForeignPackageIssue;
+use crate::models::foreign_package_issue_kind::ForeignPackageIssueKind;
+use crate::ui::dialogs::show_error_dialog;
+use crate::ui::main_window::return_to_package_list;
+use crate::ui::terminal_page::run_in_terminal_page;
+
+pub fn create_foreign_packages_page() -> GtkBox {
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let title_box = GtkBox::new(Orientation::Vertical, 6);
+    title_box.set_hexpand(true);
+
+    let title_label = Label::new(Some("Foreign Packages"));
+    title_label.add_css_class("title-2");
+    title_label.set_halign(gtk4::Align::Start);
+
+    let subtitle_label = Label::new(Some(
+        "Packages installed from outside the official repositories that need a second look.",
+    ));
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.set_halign(gtk4::Align::Start);
+
+    title_box.append(&title_label);
+    title_box.append(&subtitle_label);
+    header_box.append(&title_box);
+
+    let spinner = Spinner::new();
+    spinner.set_valign(gtk4::Align::Center);
+    header_box.append(&spinner);
+
+    let back_btn = Button::with_label("Back");
+    back_btn.set_valign(gtk4::Align::Center);
+    header_box.append(&back_btn);
+
+    main_box.append(&header_box);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some(
+        "All foreign packages are still maintained in the AUR.",
+    ));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&list_box)
+        .build();
+
+    main_box.append(&scrolled);
+
+    main_box.connect_map(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        empty_label,
+        move |_| {
+            load_foreign_package_issues(&list_box, &spinner, &empty_label);
+        }
+    ));
+
+    back_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        move |_| {
+            return_to_package_list(&main_box);
+        }
+    ));
+
+    return main_box;
+}
+
+fn load_foreign_package_issues(list_box: &ListBox, spinner: &Spinner, empty_label: &Label) {
+    while let Some(row) = list_box.row_at_index(0) {
+        list_box.remove(&row);
+    }
+
+    spinner.start();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        empty_label,
+        async move {
+            let result = gio::spawn_blocking(find_foreign_package_issues).await;
+            spinner.stop();
+
+            let issues = match result {
+                Ok(Ok(issues)) => issues,
+                Ok(Err(e)) => {
+                    empty_label.set_text(&format!("Failed to check foreign packages: {:#}", e));
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    return;
+                }
+            };
+
+            empty_label.set_text("All foreign packages are still maintained in the AUR.");
+
+            for issue in &issues {
+                list_box.append(&create_issue_row(issue));
+            }
+        }
+    ));
+}
+
+fn create_issue_row(issue: &ForeignPackageIssue) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Horizontal, 12);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(8);
+    row_box.set_margin_bottom(8);
+
+    let text_box = GtkBox::new(Orientation::Vertical, 4);
+    text_box.set_hexpand(true);
+
+    let name_label = Label::new(None);
+    name_label.set_xalign(0.0);
+    name_label.set_markup(&format!(
+        "<b>{}</b>  {}",
+        glib::markup_escape_text(&issue.name),
+        glib::markup_escape_text(&issue.version)
+    ));
+    text_box.append(&name_label);
+
+    let details = match &issue.kind {
+        ForeignPackageIssueKind::MissingFromAur => {
+            "No longer in the AUR. It will not receive updates anymore.".to_string()
+        }
+        ForeignPackageIssueKind::InOfficialRepo {
+            repository,
+            name,
+            version,
+        } => format!("Now available as {}/{} {}", repository, name, version),
+        ForeignPackageIssueKind::MaintainerChanged { previous, current } => format!(
+            "Maintainer changed from {} to {} since it was installed",
+            previous.as_deref().unwrap_or("nobody"),
+            current.as_deref().unwrap_or("nobody (orphaned)")
+        ),
+    };
+
+    let details_label = Label::new(Some(&details));
+    details_label.set_xalign(0.0);
+    details_label.set_wrap(true);
+    details_label.add_css_class("dim-label");
+    text_box.append(&details_label);
+
+    row_box.append(&text_box);
+
+    match &issue.kind {
+        ForeignPackageIssueKind::MissingFromAur => {}
+        ForeignPackageIssueKind::InOfficialRepo {
+            repository, name, ..
+        } => {
+            let switch_btn = Button::with_label("Switch");
+            switch_btn.set_tooltip_text(Some(&format!(
+                "Replace {} with {}/{}",
+                issue.name, repository, name
+            )));
+            switch_btn.set_valign(gtk4::Align::Center);
+
+            let target = format!("{}/{}", repository, name);
+            switch_btn.connect_clicked(move |button| {
+                let Some(window) = button.root().and_downcast::<ApplicationWindow>() else {
+                    return;
+                };
+
+                // pacman asks in the terminal whether to remove the conflicting foreign package.
+                if let Err(e) = run_in_terminal_page(
+                    &window,
+                    "Switching to the Official Package",
+                    "Installation",
+                    vec!["sudo", "pacman", "-S", &target],
+                    |_| {},
+                ) {
+                    show_error_dialog(
+                        window.upcast_ref::<Window>(),
+                        "Failed to Switch Package",
+                        &e.to_string(),
+                    );
+                }
+            });
+            row_box.append(&switch_btn);
+        }
+        ForeignPackageIssueKind::MaintainerChanged { current, .. } => {
+            let acknowledge_btn = Button::with_label("Acknowledge");
+            acknowledge_btn.set_tooltip_text(Some("Trust the new maintainer from now on"));
+            acknowledge_btn.set_valign(gtk4::Align::Center);
+
+            let maintainer = (issue.name.clone(), current.clone());
+            acknowledge_btn.connect_clicked(move |button| {
+                if let Err(e) = record_aur_maintainers(std::slice::from_ref(&maintainer)) {
+                    eprintln!("Failed to record AUR maintainer: {}", e);
+                    return;
+                }
+
+                if let Some(row) = button.ancestor(ListBoxRow::static_type()) {
+                    row.set_visible(false);
+                }
+            });
+            row_box.append(&acknowledge_btn);
+        }
+    }
+
+    return row_box;
+}
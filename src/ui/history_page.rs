@@ -0,0 +1,437 @@
+use glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, ButtonsType, Label, ListBox, MessageDialog,
+    MessageType, Orientation, ResponseType, ScrolledWindow, SelectionMode, TextView, Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::helpers::checkpoint::{
+    MAX_CHECKPOINTS, load_package_checkpoint, package_checkpoint_exists, plan_checkpoint_rollback,
+};
+use crate::helpers::history::{list_install_sessions, read_transcript};
+use crate::models::checkpoint_rollback::CheckpointRollback;
+use crate::models::install_session::InstallSession;
+use crate::ui::dialogs::show_error_dialog;
+use crate::ui::main_window::return_to_package_list;
+use crate::ui::terminal_page::run_in_terminal_page;
+
+pub fn create_history_page() -> GtkBox {
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let title_box = GtkBox::new(Orientation::Vertical, 6);
+    title_box.set_hexpand(true);
+
+    let title_label = Label::new(Some("Update History"));
+    title_label.add_css_class("title-2");
+    title_label.set_halign(gtk4::Align::Start);
+
+    let subtitle_label = Label::new(Some(
+        "Previous installations made with this application. Select one to view its log.",
+    ));
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.set_halign(gtk4::Align::Start);
+
+    title_box.append(&title_label);
+    title_box.append(&subtitle_label);
+    header_box.append(&title_box);
+
+    let back_btn = Button::with_label("Back");
+    back_btn.set_valign(gtk4::Align::Center);
+    header_box.append(&back_btn);
+
+    main_box.append(&header_box);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.set_activate_on_single_click(true);
+    list_box.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some("No installations have been recorded yet."));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&list_box)
+        .build();
+
+    main_box.append(&scrolled);
+
+    let sessions: Rc<RefCell<Vec<InstallSession>>> = Rc::new(RefCell::new(Vec::new()));
+
+    list_box.connect_row_activated(clone!(
+        #[strong]
+        sessions,
+        move |list_box, row| {
+            let Ok(index) = usize::try_from(row.index()) else {
+                return;
+            };
+            let Some(session) = sessions.borrow().get(index).cloned() else {
+                return;
+            };
+            if let Some(window) = list_box.root().and_downcast::<Window>() {
+                show_transcript_dialog(&window, &session);
+            }
+        }
+    ));
+
+    main_box.connect_map(clone!(
+        #[weak]
+        list_box,
+        #[strong]
+        sessions,
+        move |_| {
+            populate_history_list(&list_box, &sessions);
+        }
+    ));
+
+    back_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        move |_| {
+            return_to_package_list(&main_box);
+        }
+    ));
+
+    return main_box;
+}
+
+fn populate_history_list(list_box: &ListBox, sessions: &Rc<RefCell<Vec<InstallSession>>>) {
+    while let Some(row) = list_box.row_at_index(0) {
+        list_box.remove(&row);
+    }
+
+    let loaded = match list_install_sessions() {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("Failed to load update history: {}", e);
+            Vec::new()
+        }
+    };
+
+    for session in &loaded {
+        list_box.append(&create_session_row(session));
+    }
+
+    sessions.replace(loaded);
+}
+
+fn create_session_row(session: &InstallSession) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Vertical, 4);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(8);
+    row_box.set_margin_bottom(8);
+
+    let status = match session.exit_code {
+        Some(0) => "Completed".to_string(),
+        Some(code) => format!("Failed (exit code {})", code),
+        None => "Interrupted".to_string(),
+    };
+
+    let title_label = Label::new(None);
+    title_label.set_xalign(0.0);
+    title_label.set_markup(&format!(
+        "<b>{}</b>  {} packages  ·  {}",
+        session.started_at.format("%Y-%m-%d %H:%M"),
+        session.packages.len(),
+        glib::markup_escape_text(&status)
+    ));
+    row_box.append(&title_label);
+
+    let package_names: Vec<&str> = session.packages.iter().map(|p| p.name.as_str()).collect();
+    let packages_label = Label::new(Some(&package_names.join(", ")));
+    packages_label.set_xalign(0.0);
+    packages_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    packages_label.add_css_class("dim-label");
+    row_box.append(&packages_label);
+
+    if let Some(snapshot) = &session.snapshot {
+        let snapshot_label = Label::new(Some(&format!("Snapshot: {}", snapshot)));
+        snapshot_label.set_xalign(0.0);
+        snapshot_label.add_css_class("dim-label");
+        snapshot_label.add_css_class("caption");
+        row_box.append(&snapshot_label);
+    }
+
+    if let Some(checkpoint) = &session.checkpoint {
+        let checkpoint_text = if package_checkpoint_exists(checkpoint) {
+            format!("Checkpoint: {}", checkpoint)
+        } else {
+            format!("Checkpoint: {} (removed)", checkpoint)
+        };
+        let checkpoint_label = Label::new(Some(&checkpoint_text));
+        checkpoint_label.set_xalign(0.0);
+        checkpoint_label.add_css_class("dim-label");
+        checkpoint_label.add_css_class("caption");
+        row_box.append(&checkpoint_label);
+    }
+
+    return row_box;
+}
+
+fn show_transcript_dialog(parent: &Window, session: &InstallSession) {
+    let dialog = Window::builder()
+        .title(format!(
+            "Installation Log — {}",
+            session.started_at.format("%Y-%m-%d %H:%M")
+        ))
+        .transient_for(parent)
+        .modal(true)
+        .default_width(800)
+        .default_height(560)
+        .build();
+
+    let content_box = GtkBox::new(Orientation::Vertical, 12);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+    content_box.set_margin_top(12);
+    content_box.set_margin_bottom(12);
+
+    let mut summary = String::new();
+    for package in &session.packages {
+        summary.push_str(&format!(
+            "{} ({}) {} -> {}\n",
+            package.name, package.repository, package.old_version, package.new_version
+        ));
+    }
+    if let Some(finished_at) = session.finished_at {
+        summary.push_str(&format!(
+            "\nFinished: {}",
+            finished_at.format("%Y-%m-%d %H:%M:%S")
+        ));
+    }
+    if let Some(code) = session.exit_code {
+        summary.push_str(&format!("\nExit code: {}", code));
+    }
+
+    let summary_label = Label::new(Some(summary.trim_end()));
+    summary_label.set_xalign(0.0);
+    summary_label.set_selectable(true);
+    content_box.append(&summary_label);
+
+    let transcript = match read_transcript(session) {
+        Ok(transcript) => transcript,
+        Err(e) => format!(
+            "The transcript for this installation is not available: {}",
+            e
+        ),
+    };
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+    text_view.buffer().set_text(&transcript);
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&text_view)
+        .build();
+
+    let transcript_frame = gtk4::Frame::new(Some("Transcript"));
+    transcript_frame.set_child(Some(&scrolled));
+    content_box.append(&transcript_frame);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    if let Some(checkpoint) = session
+        .checkpoint
+        .as_ref()
+        .filter(|checkpoint| !package_checkpoint_exists(checkpoint))
+    {
+        let removed_label = Label::new(Some(&format!(
+            "Checkpoint {} was removed, only the {} newest checkpoints are kept",
+            checkpoint, MAX_CHECKPOINTS
+        )));
+        removed_label.add_css_class("dim-label");
+        button_box.append(&removed_label);
+    } else if let (Some(checkpoint), Some(window)) = (
+        session.checkpoint.clone(),
+        parent.downcast_ref::<ApplicationWindow>().cloned(),
+    ) {
+        let rollback_btn = Button::with_label("Roll Back to Checkpoint…");
+        rollback_btn.set_tooltip_text(Some(
+            "Reinstall the package versions that were installed before this update",
+        ));
+        rollback_btn.connect_clicked(clone!(
+            #[weak]
+            dialog,
+            move |_| {
+                prepare_checkpoint_rollback(&window, &dialog, &checkpoint);
+            }
+        ));
+        button_box.append(&rollback_btn);
+    }
+
+    let spacer = GtkBox::new(Orientation::Horizontal, 0);
+    spacer.set_hexpand(true);
+    button_box.append(&spacer);
+
+    let close_btn = Button::with_label("Close");
+    close_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        move |_| {
+            dialog.close();
+        }
+    ));
+    button_box.append(&close_btn);
+    content_box.append(&button_box);
+
+    dialog.set_child(Some(&content_box));
+    dialog.present();
+}
+
+fn prepare_checkpoint_rollback(window: &ApplicationWindow, dialog: &Window, checkpoint_id: &str) {
+    let checkpoint_id = checkpoint_id.to_string();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        window,
+        #[weak]
+        dialog,
+        async move {
+            let id = checkpoint_id.clone();
+            let result = gio::spawn_blocking(move || {
+                let checkpoint = load_package_checkpoint(&id)?;
+                plan_checkpoint_rollback(&checkpoint)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(rollback))
+                    if rollback.files.is_empty() && rollback.unavailable.is_empty() =>
+                {
+                    show_error_dialog(
+                        dialog.upcast_ref::<Window>(),
+                        "Nothing to Roll Back",
+                        "The installed packages already match this checkpoint.",
+                    );
+                }
+                Ok(Ok(rollback)) => {
+                    confirm_checkpoint_rollback(&window, &dialog, &checkpoint_id, rollback);
+                }
+                Ok(Err(e)) => {
+                    show_error_dialog(
+                        dialog.upcast_ref::<Window>(),
+                        "Rollback Error",
+                        &format!("Failed to prepare the rollback: {}", e),
+                    );
+                }
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                }
+            }
+        }
+    ));
+}
+
+fn confirm_checkpoint_rollback(
+    window: &ApplicationWindow,
+    dialog: &Window,
+    checkpoint_id: &str,
+    rollback: CheckpointRollback,
+) {
+    let mut message = format!(
+        "{} packages will be reinstalled at the versions recorded in the checkpoint.",
+        rollback.files.len()
+    );
+
+    if !rollback.unavailable.is_empty() {
+        message.push_str(&format!(
+            "\n\nThese versions are no longer in the package cache and cannot be restored:\n{}",
+            summarize_names(&rollback.unavailable)
+        ));
+    }
+
+    if !rollback.installed_since.is_empty() {
+        message.push_str(&format!(
+            "\n\nPackages installed after the checkpoint are left in place:\n{}",
+            summarize_names(&rollback.installed_since)
+        ));
+    }
+
+    let confirm = MessageDialog::builder()
+        .transient_for(dialog)
+        .modal(true)
+        .message_type(MessageType::Warning)
+        .buttons(ButtonsType::None)
+        .text(format!("Roll back to checkpoint {}?", checkpoint_id))
+        .secondary_text(message)
+        .build();
+
+    confirm.add_button("Cancel", ResponseType::Cancel);
+    if !rollback.files.is_empty() {
+        confirm.add_button("Roll Back", ResponseType::Accept);
+        if let Some(rollback_btn) = confirm.widget_for_response(ResponseType::Accept) {
+            rollback_btn.add_css_class("destructive-action");
+        }
+    }
+
+    let checkpoint_id = checkpoint_id.to_string();
+    confirm.connect_response(clone!(
+        #[weak]
+        window,
+        #[weak]
+        dialog,
+        move |confirm, response| {
+            confirm.close();
+
+            if response != ResponseType::Accept {
+                return;
+            }
+
+            dialog.close();
+
+            let mut args = vec!["sudo", "pacman", "-U"];
+            args.extend(rollback.files.iter().map(|file| file.as_str()));
+
+            if let Err(e) = run_in_terminal_page(
+                &window,
+                &format!("Rolling Back to Checkpoint {}", checkpoint_id),
+                "Rollback",
+                args,
+                |_| {},
+            ) {
+                show_error_dialog(
+                    window.upcast_ref::<Window>(),
+                    "Rollback Error",
+                    &format!("Failed to start the rollback: {}", e),
+                );
+            }
+        }
+    ));
+
+    confirm.show();
+}
+
+fn summarize_names(names: &[String]) -> String {
+    const LIMIT: usize = 10;
+
+    let mut summary = names
+        .iter()
+        .take(LIMIT)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if names.len() > LIMIT {
+        summary.push_str(&format!(" and {} more", names.len() - LIMIT));
+    }
+
+    return summary;
+}
@@ -1,7 +1,10 @@
+use chrono::{DateTime, Local};
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Label, Orientation, Separator};
+use gtk4::{Box as GtkBox, Button, Label, Orientation, Separator};
 
-pub fn create_info_panel() -> (GtkBox, Label) {
+use crate::models::package_update::PackageUpdate;
+
+pub fn create_info_panel() -> (GtkBox, Label, Button) {
     let info_box = GtkBox::new(Orientation::Vertical, 6);
     info_box.set_margin_start(12);
     info_box.set_margin_end(12);
@@ -31,5 +34,63 @@ pub fn create_info_panel() -> (GtkBox, Label) {
 
     info_box.append(&scrolled_window);
 
-    return (info_box, info_text);
+    let actions_box = GtkBox::new(Orientation::Horizontal, 6);
+    actions_box.set_halign(gtk4::Align::End);
+
+    let downgrade_btn = Button::with_label("Downgrade…");
+    downgrade_btn.set_tooltip_text(Some("Install an older version of the selected package"));
+    downgrade_btn.set_sensitive(false);
+    actions_box.append(&downgrade_btn);
+
+    info_box.append(&actions_box);
+
+    return (info_box, info_text, downgrade_btn);
+}
+
+pub fn format_package_info(package: &PackageUpdate) -> String {
+    let Some(aur) = &package.aur_info else {
+        return package.description.clone();
+    };
+
+    let mut lines = vec![package.description.clone(), String::new()];
+
+    if let Some(reason) = &package.rebuild_reason {
+        lines.push(format!("Needs a rebuild: {}", reason));
+        lines.push(format!(
+            "Only offered while updating: {}",
+            package.rebuild_for.join(", ")
+        ));
+    }
+
+    lines.push(format!(
+        "Maintainer: {}",
+        aur.maintainer.as_deref().unwrap_or("none (orphaned)")
+    ));
+    lines.push(format!(
+        "Votes: {}  ·  Popularity: {:.2}",
+        aur.num_votes, aur.popularity
+    ));
+    lines.push(format!(
+        "Last modified: {}",
+        format_timestamp(aur.last_modified)
+    ));
+
+    if let Some(flagged_at) = aur.out_of_date {
+        lines.push(format!(
+            "Flagged out of date since {}",
+            format_timestamp(flagged_at)
+        ));
+    }
+
+    return lines.join("\n");
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    return DateTime::from_timestamp(timestamp, 0)
+        .map(|date| {
+            date.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "unknown".to_string());
 }
@@ -1,21 +1,36 @@
+use crate::constants::AUR_NAME;
+use crate::helpers::aur_review::scan_all_aur_build_scripts;
+use crate::helpers::get_navigation_stack::get_navigation_stack;
 use crate::helpers::package_updates::get_package_updates;
 use crate::helpers::settings::load_settings;
+use crate::helpers::transaction_inhibitor::{end_transaction_inhibit, is_transaction_inhibited};
 use crate::models::package_object::PackageUpdateObject;
+use crate::ui::config_files_page::create_config_files_page;
+use crate::ui::devel_packages_page::create_devel_packages_page;
 use crate::ui::dialogs::show_error_dialog;
+use crate::ui::downgrade_dialog::show_downgrade_dialog;
 use crate::ui::error_page::{create_error_page, update_error_page_message};
-use crate::ui::info_panel::create_info_panel;
+use crate::ui::foreign_packages_page::create_foreign_packages_page;
+use crate::ui::history_page::create_history_page;
+use crate::ui::info_panel::{create_info_panel, format_package_info};
 use crate::ui::loading::create_loading_page;
+use crate::ui::maintenance_page::create_maintenance_page;
 use crate::ui::no_updates::create_no_updates_page;
+use crate::ui::orphans_page::create_orphans_page;
 use crate::ui::package_list::{create_package_list, update_statusbar};
 use crate::ui::settings_dialog::show_settings_dialog;
+use crate::ui::snapshots_page::create_snapshots_page;
 use crate::ui::terminal_page::create_terminal_page;
+use crate::ui::timeline_page::create_timeline_page;
 use crate::ui::toolbar::create_toolbar;
+use crate::ui::update_warnings::{create_update_warnings_bar, fill_update_warnings};
 use gio::ListStore;
 use glib::clone;
 use gtk4::prelude::*;
 use gtk4::{
-    Application, ApplicationWindow, Box as GtkBox, Button, ColumnView, HeaderBar, Orientation,
-    Paned, ScrolledWindow, Separator, SingleSelection, Stack, Statusbar,
+    Application, ApplicationWindow, Box as GtkBox, Button, ColumnView, HeaderBar, MenuButton,
+    MessageDialog, MessageType, Orientation, Paned, Popover, ResponseType, ScrolledWindow,
+    Separator, SingleSelection, Stack, Statusbar,
 };
 
 pub fn build_ui(app: &Application) {
@@ -47,6 +62,9 @@ pub fn build_ui(app: &Application) {
     let stack = Stack::new();
     stack.set_vexpand(true);
 
+    let navigation_menu = create_navigation_menu(&stack);
+    header_bar.pack_end(&navigation_menu);
+
     let loading_box = create_loading_page();
     stack.add_named(&loading_box, Some("loading"));
 
@@ -59,6 +77,30 @@ pub fn build_ui(app: &Application) {
     let terminal_box = create_terminal_page();
     stack.add_named(&terminal_box, Some("terminal"));
 
+    let history_box = create_history_page();
+    stack.add_named(&history_box, Some("history"));
+
+    let timeline_box = create_timeline_page();
+    stack.add_named(&timeline_box, Some("timeline"));
+
+    let snapshots_box = create_snapshots_page();
+    stack.add_named(&snapshots_box, Some("snapshots"));
+
+    let maintenance_box = create_maintenance_page();
+    stack.add_named(&maintenance_box, Some("maintenance"));
+
+    let orphans_box = create_orphans_page();
+    stack.add_named(&orphans_box, Some("orphans"));
+
+    let config_files_box = create_config_files_page();
+    stack.add_named(&config_files_box, Some("config-files"));
+
+    let foreign_packages_box = create_foreign_packages_page();
+    stack.add_named(&foreign_packages_box, Some("foreign-packages"));
+
+    let devel_packages_box = create_devel_packages_page();
+    stack.add_named(&devel_packages_box, Some("devel-packages"));
+
     let content_box = create_main_content();
     stack.add_named(&content_box, Some("content"));
 
@@ -66,6 +108,15 @@ pub fn build_ui(app: &Application) {
 
     window.set_child(Some(&main_box));
 
+    window.connect_close_request(|window| {
+        if !is_transaction_inhibited() {
+            return glib::Propagation::Proceed;
+        }
+
+        confirm_close_during_transaction(window);
+        return glib::Propagation::Stop;
+    });
+
     stack.set_visible_child_name("loading");
 
     window.present();
@@ -78,6 +129,118 @@ pub fn build_ui(app: &Application) {
     });
 }
 
+fn confirm_close_during_transaction(window: &ApplicationWindow) {
+    let dialog = MessageDialog::builder()
+        .transient_for(window)
+        .modal(true)
+        .message_type(MessageType::Warning)
+        .text("Update Still Running")
+        .secondary_text(
+            "Packages are still being installed. Closing the window now interrupts the transaction and can leave the system unable to boot.",
+        )
+        .build();
+
+    dialog.add_button("Keep Running", ResponseType::Cancel);
+    let close_button = dialog.add_button("Close Anyway", ResponseType::Accept);
+    close_button.add_css_class("destructive-action");
+
+    dialog.connect_response(clone!(
+        #[weak]
+        window,
+        move |dialog, response| {
+            dialog.close();
+
+            if response == ResponseType::Accept {
+                end_transaction_inhibit();
+                window.destroy();
+            }
+        }
+    ));
+
+    dialog.show();
+}
+
+fn create_navigation_menu(stack: &Stack) -> MenuButton {
+    let menu_button = MenuButton::new();
+    menu_button.set_icon_name("open-menu-symbolic");
+    menu_button.set_tooltip_text(Some("Menu"));
+
+    let popover = Popover::new();
+    let menu_box = GtkBox::new(Orientation::Vertical, 2);
+    menu_box.set_margin_start(4);
+    menu_box.set_margin_end(4);
+    menu_box.set_margin_top(4);
+    menu_box.set_margin_bottom(4);
+
+    add_navigation_entry(&menu_box, &popover, stack, "Update History", "history");
+    add_navigation_entry(&menu_box, &popover, stack, "Package Log", "timeline");
+    add_navigation_entry(&menu_box, &popover, stack, "System Snapshots", "snapshots");
+    add_navigation_entry(&menu_box, &popover, stack, "Maintenance", "maintenance");
+    add_navigation_entry(&menu_box, &popover, stack, "Orphaned Packages", "orphans");
+    add_navigation_entry(
+        &menu_box,
+        &popover,
+        stack,
+        "Configuration Files",
+        "config-files",
+    );
+    add_navigation_entry(
+        &menu_box,
+        &popover,
+        stack,
+        "Foreign Packages",
+        "foreign-packages",
+    );
+    add_navigation_entry(
+        &menu_box,
+        &popover,
+        stack,
+        "Devel Packages",
+        "devel-packages",
+    );
+
+    popover.set_child(Some(&menu_box));
+    menu_button.set_popover(Some(&popover));
+
+    return menu_button;
+}
+
+fn add_navigation_entry(
+    menu_box: &GtkBox,
+    popover: &Popover,
+    stack: &Stack,
+    label: &str,
+    page_name: &'static str,
+) {
+    let button = Button::with_label(label);
+    button.add_css_class("flat");
+    if let Some(child) = button.child() {
+        child.set_halign(gtk4::Align::Start);
+    }
+
+    button.connect_clicked(clone!(
+        #[weak]
+        popover,
+        #[weak]
+        stack,
+        move |_| {
+            popover.popdown();
+            stack.set_visible_child_name(page_name);
+        }
+    ));
+
+    menu_box.append(&button);
+}
+
+pub fn return_to_package_list(widget: &impl IsA<gtk4::Widget>) {
+    let Some((stack, content_box, window)) = get_navigation_stack(widget.as_ref()) else {
+        return;
+    };
+
+    stack.set_visible_child_name("loading");
+    load_packages(stack, content_box, window);
+}
+
 fn create_main_content() -> GtkBox {
     let content_box = GtkBox::new(Orientation::Vertical, 0);
 
@@ -88,6 +251,8 @@ fn create_main_content() -> GtkBox {
     let separator = Separator::new(Orientation::Horizontal);
     content_box.append(&separator);
 
+    content_box.append(&create_update_warnings_bar());
+
     let paned = Paned::new(Orientation::Vertical);
 
     let (list_view, store, statusbar) = create_package_list();
@@ -100,21 +265,41 @@ fn create_main_content() -> GtkBox {
 
     paned.set_start_child(Some(&scrolled));
 
-    let (info_panel, info_text) = create_info_panel();
+    let (info_panel, info_text, downgrade_btn) = create_info_panel();
     paned.set_end_child(Some(&info_panel));
 
     if let Some(selection_model) = list_view.model().and_downcast::<SingleSelection>() {
         selection_model.connect_selection_changed(clone!(
             #[weak]
             info_text,
+            #[weak]
+            downgrade_btn,
             move |model, _position, _n_items| {
                 if let Some(package_obj) =
                     model.selected_item().and_downcast::<PackageUpdateObject>()
                 {
                     let package_data = package_obj.data();
-                    info_text.set_text(package_data.description.as_str());
+                    info_text.set_text(&format_package_info(&package_data));
+                    downgrade_btn.set_sensitive(true);
                 } else {
                     info_text.set_text("Select a package to view its information.");
+                    downgrade_btn.set_sensitive(false);
+                }
+            }
+        ));
+
+        downgrade_btn.connect_clicked(clone!(
+            #[weak]
+            selection_model,
+            move |button| {
+                let Some(package_obj) = selection_model
+                    .selected_item()
+                    .and_downcast::<PackageUpdateObject>()
+                else {
+                    return;
+                };
+                if let Some(window) = button.root().and_downcast::<ApplicationWindow>() {
+                    show_downgrade_dialog(&window, &package_obj.data().name);
                 }
             }
         ));
@@ -134,8 +319,9 @@ pub fn load_packages(stack: Stack, content_box: GtkBox, window: ApplicationWindo
         let packages_result = gio::spawn_blocking(|| get_package_updates()).await;
 
         match packages_result {
-            Ok(Ok(packages)) => {
-                if packages.is_empty() {
+            Ok(Ok(check)) => {
+                // Unparsed output may hide updates, so the list is shown for its warnings.
+                if check.updates.is_empty() && check.warnings.is_empty() {
                     stack.set_visible_child_name("no-updates");
                     return;
                 }
@@ -180,15 +366,20 @@ pub fn load_packages(stack: Stack, content_box: GtkBox, window: ApplicationWindo
 
                 list_store.remove_all();
 
-                for package in packages {
+                for package in check.updates {
                     list_store.append(&PackageUpdateObject::new(package));
                 }
 
+                if let Some(warnings_box) = paned.prev_sibling().and_downcast::<GtkBox>() {
+                    fill_update_warnings(&warnings_box, &check.warnings);
+                }
+
                 if let Some(statusbar) = content_box.last_child().and_downcast::<Statusbar>() {
                     update_statusbar(&statusbar, &list_store);
                 }
 
                 stack.set_visible_child_name("content");
+                scan_aur_packages(&list_store);
             }
             Ok(Err(e)) => {
                 if let crate::models::update_error::UpdateError::SyncFailed(ref msg) = e {
@@ -213,3 +404,53 @@ pub fn load_packages(stack: Stack, content_box: GtkBox, window: ApplicationWindo
         }
     });
 }
+
+// Fetching every AUR repository takes a while, so the risk badges are filled in after the list shows.
+fn scan_aur_packages(list_store: &ListStore) {
+    let items: Vec<(PackageUpdateObject, String)> = (0..list_store.n_items())
+        .filter_map(|i| list_store.item(i).and_downcast::<PackageUpdateObject>())
+        .filter_map(|item| {
+            let data = item.data();
+            if data.repository != AUR_NAME {
+                return None;
+            }
+            let package_base = data
+                .aur_info
+                .map(|info| info.package_base)
+                .unwrap_or(data.name);
+            return Some((item, package_base));
+        })
+        .collect();
+
+    if items.is_empty() {
+        return;
+    }
+
+    let package_bases: Vec<String> = items.iter().map(|(_, base)| base.clone()).collect();
+    let list_store = list_store.clone();
+
+    glib::spawn_future_local(async move {
+        let results =
+            match gio::spawn_blocking(move || scan_all_aur_build_scripts(&package_bases)).await {
+                Ok(results) => results,
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    return;
+                }
+            };
+
+        for (item, package_base) in items {
+            let Some(findings) = results.get(&package_base) else {
+                continue;
+            };
+            item.set_pkgbuild_findings(findings.clone());
+
+            // The list may have been reloaded during the scan, so the row is looked up again.
+            let position = (0..list_store.n_items())
+                .find(|&i| list_store.item(i).as_ref() == Some(item.upcast_ref()));
+            if let Some(position) = position {
+                list_store.items_changed(position, 1, 1);
+            }
+        }
+    });
+}
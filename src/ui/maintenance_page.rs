@@ -0,0 +1,429 @@
+use glib::{clone, format_size};
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, CheckButton, Label, ListBox, Orientation,
+    ScrolledWindow, SelectionMode, SpinButton, Spinner, TextView, Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::helpers::package_cache::{
+    count_cached_versions, get_cache_cleanup_policy, get_cache_dir_usage, list_cached_packages,
+    plan_cache_cleanup, remove_cached_packages,
+};
+use crate::helpers::pacman_conf::get_cache_dirs;
+use crate::helpers::pacman_info::get_installed_packages;
+use crate::helpers::settings::{load_settings, save_settings};
+use crate::models::cached_package::CachedPackage;
+use crate::ui::dialogs::show_error_dialog;
+use crate::ui::main_window::return_to_package_list;
+
+pub fn create_maintenance_page() -> GtkBox {
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let title_box = GtkBox::new(Orientation::Vertical, 6);
+    title_box.set_hexpand(true);
+
+    let title_label = Label::new(Some("Maintenance"));
+    title_label.add_css_class("title-2");
+    title_label.set_halign(gtk4::Align::Start);
+
+    let subtitle_label = Label::new(Some(
+        "See what the package cache holds and remove old package versions.",
+    ));
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.set_halign(gtk4::Align::Start);
+
+    title_box.append(&title_label);
+    title_box.append(&subtitle_label);
+    header_box.append(&title_box);
+
+    let spinner = Spinner::new();
+    spinner.set_valign(gtk4::Align::Center);
+    header_box.append(&spinner);
+
+    let back_btn = Button::with_label("Back");
+    back_btn.set_valign(gtk4::Align::Center);
+    header_box.append(&back_btn);
+
+    main_box.append(&header_box);
+
+    let cache_heading = Label::new(Some("Package Cache"));
+    cache_heading.add_css_class("heading");
+    cache_heading.set_halign(gtk4::Align::Start);
+    main_box.append(&cache_heading);
+
+    let dirs_list = ListBox::new();
+    dirs_list.set_selection_mode(SelectionMode::None);
+    dirs_list.add_css_class("boxed-list");
+    main_box.append(&dirs_list);
+
+    let settings = load_settings();
+
+    let policy_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let keep_box = GtkBox::new(Orientation::Horizontal, 6);
+    keep_box.append(&Label::new(Some("Keep")));
+    let keep_spin = SpinButton::with_range(0.0, 20.0, 1.0);
+    keep_spin.set_value(settings.cache_keep_versions as f64);
+    keep_box.append(&keep_spin);
+    keep_box.append(&Label::new(Some("versions of each package")));
+    policy_box.append(&keep_box);
+
+    let uninstalled_check = CheckButton::with_label("Remove uninstalled packages");
+    uninstalled_check.set_active(settings.cache_remove_uninstalled);
+    policy_box.append(&uninstalled_check);
+
+    let checkpoints_label = Label::new(Some(
+        "Packages used by checkpoints are kept by this cleanup",
+    ));
+    checkpoints_label.add_css_class("dim-label");
+    checkpoints_label.set_tooltip_text(Some(
+        "Rolling back to a checkpoint reinstalls these files from the cache. paccache and pacman -Sc do not know about checkpoints and still remove them.",
+    ));
+    policy_box.append(&checkpoints_label);
+
+    let spacer = GtkBox::new(Orientation::Horizontal, 0);
+    spacer.set_hexpand(true);
+    policy_box.append(&spacer);
+
+    let preview_btn = Button::with_label("Preview Cleanup…");
+    preview_btn.add_css_class("suggested-action");
+    policy_box.append(&preview_btn);
+
+    main_box.append(&policy_box);
+
+    let versions_heading = Label::new(Some("Versions per Package"));
+    versions_heading.add_css_class("heading");
+    versions_heading.set_halign(gtk4::Align::Start);
+    main_box.append(&versions_heading);
+
+    let versions_list = ListBox::new();
+    versions_list.set_selection_mode(SelectionMode::None);
+    versions_list.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some("Every cached package has a single version."));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    versions_list.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&versions_list)
+        .build();
+    main_box.append(&scrolled);
+
+    let save_policy = clone!(
+        #[weak]
+        keep_spin,
+        #[weak]
+        uninstalled_check,
+        move || {
+            let mut settings = load_settings();
+            settings.cache_keep_versions = keep_spin.value() as u32;
+            settings.cache_remove_uninstalled = uninstalled_check.is_active();
+
+            if let Err(e) = save_settings(&settings) {
+                eprintln!("Failed to save settings: {}", e);
+            }
+        }
+    );
+
+    let on_change = save_policy.clone();
+    keep_spin.connect_value_changed(move |_| on_change());
+    uninstalled_check.connect_toggled(move |_| save_policy());
+
+    main_box.connect_map(clone!(
+        #[weak]
+        dirs_list,
+        #[weak]
+        versions_list,
+        #[weak]
+        spinner,
+        move |_| {
+            load_cache_usage(&dirs_list, &versions_list, &spinner);
+        }
+    ));
+
+    preview_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        #[weak]
+        dirs_list,
+        #[weak]
+        versions_list,
+        #[weak]
+        spinner,
+        move |_| {
+            let Some(window) = main_box.root().and_downcast::<ApplicationWindow>() else {
+                return;
+            };
+
+            show_cleanup_preview(&window, move || {
+                load_cache_usage(&dirs_list, &versions_list, &spinner);
+            });
+        }
+    ));
+
+    back_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        move |_| {
+            return_to_package_list(&main_box);
+        }
+    ));
+
+    return main_box;
+}
+
+fn load_cache_usage(dirs_list: &ListBox, versions_list: &ListBox, spinner: &Spinner) {
+    while let Some(row) = dirs_list.row_at_index(0) {
+        dirs_list.remove(&row);
+    }
+    while let Some(row) = versions_list.row_at_index(0) {
+        versions_list.remove(&row);
+    }
+
+    spinner.start();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        dirs_list,
+        #[weak]
+        versions_list,
+        #[weak]
+        spinner,
+        async move {
+            let result = gio::spawn_blocking(|| {
+                let cache_dirs = get_cache_dirs();
+                let cached = list_cached_packages();
+                (get_cache_dir_usage(&cache_dirs, &cached), cached)
+            })
+            .await;
+            spinner.stop();
+
+            let Ok((usage, cached)) = result else {
+                eprintln!("Error in background thread while reading the package cache");
+                return;
+            };
+
+            for cache_dir in &usage {
+                dirs_list.append(&create_info_row(
+                    &cache_dir.path.to_string_lossy(),
+                    &format!(
+                        "{} files  ·  {}",
+                        cache_dir.files,
+                        format_size(cache_dir.size)
+                    ),
+                ));
+            }
+
+            let mut versions: Vec<(String, usize)> = count_cached_versions(&cached)
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .collect();
+            versions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+            for (name, count) in &versions {
+                let size: u64 = cached
+                    .iter()
+                    .filter(|package| &package.name == name)
+                    .map(|package| package.size)
+                    .sum();
+                versions_list.append(&create_info_row(
+                    name,
+                    &format!("{} versions  ·  {}", count, format_size(size)),
+                ));
+            }
+        }
+    ));
+}
+
+fn create_info_row(title: &str, detail: &str) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Horizontal, 12);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(8);
+    row_box.set_margin_bottom(8);
+
+    let title_label = Label::new(None);
+    title_label.set_markup(&format!("<b>{}</b>", glib::markup_escape_text(title)));
+    title_label.set_xalign(0.0);
+    title_label.set_hexpand(true);
+    title_label.set_ellipsize(gtk4::pango::EllipsizeMode::Middle);
+    row_box.append(&title_label);
+
+    let detail_label = Label::new(Some(detail));
+    detail_label.add_css_class("dim-label");
+    row_box.append(&detail_label);
+
+    return row_box;
+}
+
+fn show_cleanup_preview(parent: &ApplicationWindow, on_removed: impl Fn() + 'static) {
+    let dialog = Window::builder()
+        .title("Package Cache Cleanup")
+        .transient_for(parent)
+        .modal(true)
+        .default_width(720)
+        .default_height(520)
+        .build();
+
+    let content_box = GtkBox::new(Orientation::Vertical, 12);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+    content_box.set_margin_top(12);
+    content_box.set_margin_bottom(12);
+
+    let summary_label = Label::new(Some("Looking for packages to remove..."));
+    summary_label.set_xalign(0.0);
+    summary_label.set_wrap(true);
+    content_box.append(&summary_label);
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&text_view)
+        .build();
+    content_box.append(&scrolled);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 6);
+    button_box.set_halign(gtk4::Align::End);
+
+    let cancel_btn = Button::with_label("Cancel");
+    button_box.append(&cancel_btn);
+
+    let remove_btn = Button::with_label("Remove");
+    remove_btn.add_css_class("destructive-action");
+    remove_btn.set_sensitive(false);
+    button_box.append(&remove_btn);
+
+    content_box.append(&button_box);
+    dialog.set_child(Some(&content_box));
+
+    cancel_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        move |_| {
+            dialog.close();
+        }
+    ));
+
+    let plan: Rc<RefCell<Vec<CachedPackage>>> = Rc::new(RefCell::new(Vec::new()));
+    let on_removed = Rc::new(on_removed);
+
+    remove_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        #[weak]
+        parent,
+        #[strong]
+        plan,
+        #[strong]
+        on_removed,
+        move |remove_btn| {
+            remove_btn.set_sensitive(false);
+            let packages = plan.borrow().clone();
+            let on_removed = on_removed.clone();
+
+            glib::spawn_future_local(clone!(
+                #[weak]
+                dialog,
+                #[weak]
+                parent,
+                async move {
+                    let result =
+                        gio::spawn_blocking(move || remove_cached_packages(&packages)).await;
+                    dialog.close();
+
+                    match result {
+                        Ok(Ok(_)) => on_removed(),
+                        Ok(Err(e)) => {
+                            show_error_dialog(
+                                parent.upcast_ref::<Window>(),
+                                "Failed to Clean Package Cache",
+                                &format!("{:#}", e),
+                            );
+                            on_removed();
+                        }
+                        Err(e) => eprintln!("Error in background thread: {:?}", e),
+                    }
+                }
+            ));
+        }
+    ));
+
+    dialog.present();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        summary_label,
+        #[weak]
+        text_view,
+        #[weak]
+        remove_btn,
+        #[strong]
+        plan,
+        async move {
+            let result = gio::spawn_blocking(|| {
+                let policy = get_cache_cleanup_policy(&load_settings())?;
+                let installed = get_installed_packages()?;
+                Ok::<_, anyhow::Error>(plan_cache_cleanup(
+                    &list_cached_packages(),
+                    &installed,
+                    &policy,
+                ))
+            })
+            .await;
+
+            let to_remove = match result {
+                Ok(Ok(to_remove)) => to_remove,
+                Ok(Err(e)) => {
+                    summary_label.set_text(&format!("Failed to plan the cleanup: {:#}", e));
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    return;
+                }
+            };
+
+            if to_remove.is_empty() {
+                summary_label.set_text("Nothing to remove with the current settings.");
+                return;
+            }
+
+            let freed: u64 = to_remove.iter().map(|package| package.size).sum();
+            summary_label.set_text(&format!(
+                "{} package files will be removed, freeing {}.",
+                to_remove.len(),
+                format_size(freed)
+            ));
+            text_view.buffer().set_text(
+                &to_remove
+                    .iter()
+                    .map(|package| package.path.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+            remove_btn.set_label(&format!("Remove {} Files", to_remove.len()));
+            remove_btn.set_sensitive(true);
+            plan.replace(to_remove);
+        }
+    ));
+}
@@ -1,12 +1,27 @@
+pub mod aur_build_progress;
+pub mod aur_review_dialog;
+pub mod config_files_page;
+pub mod devel_packages_page;
 pub mod dialogs;
+pub mod disk_space_warning;
+pub mod downgrade_dialog;
 pub mod error_page;
+pub mod foreign_packages_page;
+pub mod history_page;
 pub mod info_panel;
 pub mod loading;
 pub mod main_window;
+pub mod maintenance_page;
 pub mod no_updates;
+pub mod orphans_page;
 pub mod package_list;
+pub mod restart_summary;
+pub mod risk_badges;
 pub mod settings_dialog;
+pub mod snapshots_page;
 pub mod terminal_page;
+pub mod timeline_page;
 pub mod toolbar;
+pub mod update_warnings;
 
 pub use main_window::build_ui;
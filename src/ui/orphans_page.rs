@@ -0,0 +1,391 @@
+use glib::{clone, format_size};
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, CheckButton, Label, ListBox, MessageDialog,
+    MessageType, Orientation, ResponseType, ScrolledWindow, SelectionMode, Spinner, ToggleButton,
+    Window,
+};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use crate::helpers::orphans::list_orphan_packages;
+use crate::helpers::settings::{load_settings, save_settings};
+use crate::models::orphan_package::OrphanPackage;
+use crate::ui::dialogs::show_error_dialog;
+use crate::ui::main_window::return_to_package_list;
+use crate::ui::terminal_page::run_in_terminal_page;
+
+pub fn create_orphans_page() -> GtkBox {
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let title_box = GtkBox::new(Orientation::Vertical, 6);
+    title_box.set_hexpand(true);
+
+    let title_label = Label::new(Some("Orphaned Packages"));
+    title_label.add_css_class("title-2");
+    title_label.set_halign(gtk4::Align::Start);
+
+    let subtitle_label = Label::new(Some(
+        "Dependencies that no installed package needs anymore.",
+    ));
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.set_halign(gtk4::Align::Start);
+
+    title_box.append(&title_label);
+    title_box.append(&subtitle_label);
+    header_box.append(&title_box);
+
+    let spinner = Spinner::new();
+    spinner.set_valign(gtk4::Align::Center);
+    header_box.append(&spinner);
+
+    let back_btn = Button::with_label("Back");
+    back_btn.set_valign(gtk4::Align::Center);
+    header_box.append(&back_btn);
+
+    main_box.append(&header_box);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some("No orphaned packages found."));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&list_box)
+        .build();
+
+    main_box.append(&scrolled);
+
+    let footer_box = GtkBox::new(Orientation::Horizontal, 6);
+
+    let summary_label = Label::new(None);
+    summary_label.add_css_class("dim-label");
+    summary_label.set_hexpand(true);
+    summary_label.set_xalign(0.0);
+    footer_box.append(&summary_label);
+
+    let select_all_btn = Button::with_label("Select All");
+    footer_box.append(&select_all_btn);
+
+    let remove_btn = Button::with_label("Remove Selected…");
+    remove_btn.add_css_class("destructive-action");
+    remove_btn.set_sensitive(false);
+    footer_box.append(&remove_btn);
+
+    main_box.append(&footer_box);
+
+    let orphans: Rc<RefCell<Vec<OrphanPackage>>> = Rc::new(RefCell::new(Vec::new()));
+    let selected: Rc<RefCell<BTreeSet<String>>> = Rc::new(RefCell::new(BTreeSet::new()));
+
+    let update_summary = Rc::new(clone!(
+        #[weak]
+        summary_label,
+        #[weak]
+        remove_btn,
+        #[strong]
+        orphans,
+        #[strong]
+        selected,
+        move || {
+            let selected = selected.borrow();
+            let size: u64 = orphans
+                .borrow()
+                .iter()
+                .filter(|orphan| selected.contains(&orphan.name))
+                .map(|orphan| orphan.size)
+                .sum();
+
+            summary_label.set_text(&format!(
+                "{} of {} selected  ·  {}",
+                selected.len(),
+                orphans.borrow().len(),
+                format_size(size)
+            ));
+            remove_btn.set_sensitive(!selected.is_empty());
+        }
+    ));
+
+    main_box.connect_map(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        empty_label,
+        #[strong]
+        orphans,
+        #[strong]
+        selected,
+        #[strong]
+        update_summary,
+        move |_| {
+            load_orphans(
+                &list_box,
+                &spinner,
+                &empty_label,
+                orphans.clone(),
+                selected.clone(),
+                update_summary.clone(),
+            );
+        }
+    ));
+
+    select_all_btn.connect_clicked(clone!(
+        #[weak]
+        list_box,
+        move |_| {
+            let mut index = 0;
+            while let Some(row) = list_box.row_at_index(index) {
+                if let Some(check) = row
+                    .child()
+                    .and_then(|child| child.first_child())
+                    .and_downcast::<CheckButton>()
+                {
+                    if check.is_sensitive() {
+                        check.set_active(true);
+                    }
+                }
+                index += 1;
+            }
+        }
+    ));
+
+    remove_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        #[strong]
+        selected,
+        move |_| {
+            let Some(window) = main_box.root().and_downcast::<ApplicationWindow>() else {
+                return;
+            };
+
+            let names: Vec<String> = selected.borrow().iter().cloned().collect();
+            confirm_removal(&window, names);
+        }
+    ));
+
+    back_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        move |_| {
+            return_to_package_list(&main_box);
+        }
+    ));
+
+    return main_box;
+}
+
+fn load_orphans(
+    list_box: &ListBox,
+    spinner: &Spinner,
+    empty_label: &Label,
+    orphans: Rc<RefCell<Vec<OrphanPackage>>>,
+    selected: Rc<RefCell<BTreeSet<String>>>,
+    update_summary: Rc<dyn Fn()>,
+) {
+    while let Some(row) = list_box.row_at_index(0) {
+        list_box.remove(&row);
+    }
+    orphans.borrow_mut().clear();
+    selected.borrow_mut().clear();
+    update_summary();
+
+    spinner.start();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        empty_label,
+        async move {
+            let result = gio::spawn_blocking(list_orphan_packages).await;
+            spinner.stop();
+
+            let loaded = match result {
+                Ok(Ok(loaded)) => loaded,
+                Ok(Err(e)) => {
+                    empty_label.set_text(&format!("Failed to list orphaned packages: {}", e));
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    return;
+                }
+            };
+
+            empty_label.set_text("No orphaned packages found.");
+
+            let kept = load_settings().kept_orphans;
+            for orphan in &loaded {
+                list_box.append(&create_orphan_row(
+                    orphan,
+                    kept.contains(&orphan.name),
+                    selected.clone(),
+                    update_summary.clone(),
+                ));
+            }
+
+            orphans.replace(loaded);
+            update_summary();
+        }
+    ));
+}
+
+fn create_orphan_row(
+    orphan: &OrphanPackage,
+    kept: bool,
+    selected: Rc<RefCell<BTreeSet<String>>>,
+    update_summary: Rc<dyn Fn()>,
+) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Horizontal, 12);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(8);
+    row_box.set_margin_bottom(8);
+
+    let select_check = CheckButton::new();
+    select_check.set_valign(gtk4::Align::Center);
+    select_check.set_sensitive(!kept);
+    row_box.append(&select_check);
+
+    let text_box = GtkBox::new(Orientation::Vertical, 4);
+    text_box.set_hexpand(true);
+
+    let name_label = Label::new(None);
+    name_label.set_xalign(0.0);
+    name_label.set_markup(&format!(
+        "<b>{}</b>  {}",
+        glib::markup_escape_text(&orphan.name),
+        glib::markup_escape_text(&orphan.version)
+    ));
+    text_box.append(&name_label);
+
+    let mut details = vec![format_size(orphan.size).to_string()];
+    if !orphan.install_reason.is_empty() {
+        details.push(orphan.install_reason.clone());
+    }
+    if !orphan.optional_for.is_empty() {
+        details.push(format!("Optional for {}", orphan.optional_for.join(", ")));
+    }
+
+    let details_label = Label::new(Some(&details.join("  ·  ")));
+    details_label.set_xalign(0.0);
+    details_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    details_label.add_css_class("dim-label");
+    text_box.append(&details_label);
+
+    row_box.append(&text_box);
+
+    let keep_btn = ToggleButton::with_label("Keep");
+    keep_btn.set_tooltip_text(Some("Kept packages are never offered for removal"));
+    keep_btn.set_valign(gtk4::Align::Center);
+    keep_btn.set_active(kept);
+    row_box.append(&keep_btn);
+
+    let name = orphan.name.clone();
+    select_check.connect_toggled(clone!(
+        #[strong]
+        selected,
+        #[strong]
+        update_summary,
+        move |check| {
+            if check.is_active() {
+                selected.borrow_mut().insert(name.clone());
+            } else {
+                selected.borrow_mut().remove(&name);
+            }
+            update_summary();
+        }
+    ));
+
+    let name = orphan.name.clone();
+    keep_btn.connect_toggled(clone!(
+        #[weak]
+        select_check,
+        move |button| {
+            set_orphan_kept(&name, button.is_active());
+
+            if button.is_active() {
+                select_check.set_active(false);
+            }
+            select_check.set_sensitive(!button.is_active());
+        }
+    ));
+
+    return row_box;
+}
+
+fn set_orphan_kept(name: &str, kept: bool) {
+    let mut settings = load_settings();
+    settings.kept_orphans.retain(|kept_name| kept_name != name);
+    if kept {
+        settings.kept_orphans.push(name.to_string());
+    }
+
+    if let Err(e) = save_settings(&settings) {
+        eprintln!("Failed to save settings: {}", e);
+    }
+}
+
+fn confirm_removal(window: &ApplicationWindow, names: Vec<String>) {
+    let dialog = MessageDialog::builder()
+        .transient_for(window)
+        .modal(true)
+        .message_type(MessageType::Question)
+        .text(format!("Remove {} Orphaned Packages?", names.len()))
+        .secondary_text(format!(
+            "{}\n\nTheir own dependencies that nothing else needs are removed as well.",
+            names.join(", ")
+        ))
+        .build();
+
+    dialog.add_button("Cancel", ResponseType::Cancel);
+    let remove_button = dialog.add_button("Remove", ResponseType::Accept);
+    remove_button.add_css_class("destructive-action");
+
+    let window = window.clone();
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+
+        if response != ResponseType::Accept {
+            return;
+        }
+
+        let mut args = vec!["sudo", "pacman", "-Rns"];
+        args.extend(names.iter().map(|name| name.as_str()));
+
+        if let Err(e) = run_in_terminal_page(
+            &window,
+            "Removing Orphaned Packages",
+            "Removal",
+            args,
+            |_| {},
+        ) {
+            show_error_dialog(
+                window.upcast_ref::<Window>(),
+                "Failed to Remove Packages",
+                &e.to_string(),
+            );
+        }
+    });
+
+    dialog.show();
+}
@@ -1,4 +1,6 @@
 use crate::models::package_object::PackageUpdateObject;
+use crate::models::package_update::PackageUpdate;
+use crate::ui::risk_badges::fill_risk_badges;
 use gio::ListStore;
 use glib::{clone, format_size};
 use gtk4::prelude::*;
@@ -82,6 +84,7 @@ fn create_upgrade_column(column_view: &ColumnView, store: &ListStore, statusbar:
             let data = obj.data();
             let check = list_item.child().and_downcast::<CheckButton>().unwrap();
             check.set_active(data.selected);
+            check.set_sensitive(has_selected_trigger(&store, &data));
 
             check.connect_toggled(clone!(
                 #[weak]
@@ -91,7 +94,15 @@ fn create_upgrade_column(column_view: &ColumnView, store: &ListStore, statusbar:
                 #[weak]
                 statusbar,
                 move |check| {
+                    let data = obj.data();
+                    if data.selected == check.is_active() {
+                        return;
+                    }
+
                     obj.set_selected(check.is_active());
+                    if data.rebuild_for.is_empty() {
+                        sync_rebuild_rows(&store);
+                    }
                     update_statusbar(&statusbar, &store);
                 }
             ));
@@ -101,6 +112,36 @@ fn create_upgrade_column(column_view: &ColumnView, store: &ListStore, statusbar:
     column_view.append_column(&upgrade_column);
 }
 
+// A rebuild is only offered while one of the updates that cause it is selected.
+fn has_selected_trigger(store: &ListStore, data: &PackageUpdate) -> bool {
+    if data.rebuild_for.is_empty() {
+        return true;
+    }
+
+    return (0..store.n_items())
+        .filter_map(|i| store.item(i).and_downcast::<PackageUpdateObject>())
+        .map(|item| item.data())
+        .any(|other| other.selected && data.rebuild_for.contains(&other.name));
+}
+
+// Deselects rebuilds whose updates were all deselected and refreshes their checkboxes.
+fn sync_rebuild_rows(store: &ListStore) {
+    for i in 0..store.n_items() {
+        let Some(item) = store.item(i).and_downcast::<PackageUpdateObject>() else {
+            continue;
+        };
+        let data = item.data();
+        if data.rebuild_for.is_empty() {
+            continue;
+        }
+
+        if data.selected && !has_selected_trigger(store, &data) {
+            item.set_selected(false);
+        }
+        store.items_changed(i, 1, 1);
+    }
+}
+
 fn create_name_column(column_view: &ColumnView) {
     let name_factory = gtk4::SignalListItemFactory::new();
     name_factory.connect_setup(move |_factory, item| {
@@ -112,8 +153,10 @@ fn create_name_column(column_view: &ColumnView) {
         desc_label.set_xalign(0.0);
         desc_label.set_css_classes(&["package-desc"]);
         desc_label.add_css_class("dim-label");
+        let badge_box = GtkBox::new(Orientation::Horizontal, 8);
         vbox.append(&name_label);
         vbox.append(&desc_label);
+        vbox.append(&badge_box);
         item.downcast_ref::<gtk4::ListItem>()
             .unwrap()
             .set_child(Some(&vbox));
@@ -128,10 +171,12 @@ fn create_name_column(column_view: &ColumnView) {
         let vbox = list_item.child().and_downcast::<GtkBox>().unwrap();
         let name_label = vbox.first_child().and_downcast::<Label>().unwrap();
         let desc_label = name_label.next_sibling().and_downcast::<Label>().unwrap();
+        let badge_box = desc_label.next_sibling().and_downcast::<GtkBox>().unwrap();
         desc_label.set_wrap(true);
 
         name_label.set_text(&data.name);
         desc_label.set_text(&data.description);
+        fill_risk_badges(&badge_box, &data.pkgbuild_findings);
     });
     let name_column = ColumnViewColumn::new(Some("Name"), Some(name_factory));
     name_column.set_expand(true);
@@ -165,7 +210,11 @@ fn create_version_column(column_view: &ColumnView) {
         let new_label = old_label.next_sibling().and_downcast::<Label>().unwrap();
 
         old_label.set_text(&data.current_version);
-        new_label.set_text(&data.new_version);
+        if data.rebuild_reason.is_some() {
+            new_label.set_text("rebuild");
+        } else {
+            new_label.set_text(&data.new_version);
+        }
     });
     let version_column = ColumnViewColumn::new(Some("Version"), Some(version_factory));
     column_view.append_column(&version_column);
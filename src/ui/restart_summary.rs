@@ -0,0 +1,170 @@
+use gtk4::prelude::*;
+use gtk4::{ApplicationWindow, MessageDialog, MessageType, ResponseType, Window};
+use std::path::Path;
+
+use crate::helpers::logind::reboot;
+use crate::helpers::restart_check::{build_restart_command, check_restart_needed, is_restartable};
+use crate::models::restart_report::RestartReport;
+use crate::ui::dialogs::show_error_dialog;
+use crate::ui::terminal_page::run_in_terminal_page;
+
+pub fn show_restart_summary_when_needed(window: &ApplicationWindow) {
+    let window = window.clone();
+
+    glib::spawn_future_local(async move {
+        let report = match gio::spawn_blocking(|| check_restart_needed(Path::new("/"))).await {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("Error in background thread: {:?}", e);
+                return;
+            }
+        };
+
+        if !report.kernel_missing && report.processes.is_empty() {
+            return;
+        }
+
+        show_restart_summary(&window, report);
+    });
+}
+
+fn show_restart_summary(window: &ApplicationWindow, report: RestartReport) {
+    let restart_command = build_restart_command(&report.processes);
+
+    let dialog = MessageDialog::builder()
+        .transient_for(window)
+        .modal(true)
+        .message_type(if report.kernel_missing {
+            MessageType::Warning
+        } else {
+            MessageType::Question
+        })
+        .text(if report.kernel_missing {
+            "Reboot Required"
+        } else {
+            "Some Programs Use Outdated Libraries"
+        })
+        .secondary_text(describe_restart_report(&report))
+        .build();
+
+    dialog.add_button("Later", ResponseType::Cancel);
+    if restart_command.is_some() && !report.kernel_missing {
+        dialog.add_button("Restart Services", ResponseType::Apply);
+    }
+    dialog.add_button("Reboot Now", ResponseType::Accept);
+
+    let window = window.clone();
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+
+        match response {
+            ResponseType::Accept => {
+                if let Err(e) = reboot() {
+                    show_error_dialog(
+                        window.upcast_ref::<Window>(),
+                        "Failed to Reboot",
+                        &format!("{:#}", e),
+                    );
+                }
+            }
+            ResponseType::Apply => {
+                let Some(command) = &restart_command else {
+                    return;
+                };
+
+                if let Err(e) = run_in_terminal_page(
+                    &window,
+                    "Restarting Services",
+                    "Service Restart",
+                    vec!["bash", "-lc", command],
+                    |_| {},
+                ) {
+                    show_error_dialog(
+                        window.upcast_ref::<Window>(),
+                        "Failed to Restart Services",
+                        &e.to_string(),
+                    );
+                }
+            }
+            _ => {}
+        }
+    });
+
+    dialog.show();
+}
+
+fn describe_restart_report(report: &RestartReport) -> String {
+    const LIMIT: usize = 10;
+
+    let mut sections = Vec::new();
+
+    if report.kernel_missing {
+        sections.push(format!(
+            "The running kernel ({}) was replaced and its modules are gone. Devices that need a module which is not loaded yet will stop working until you reboot.",
+            report.running_kernel
+        ));
+    }
+
+    let mut services: Vec<String> = report
+        .processes
+        .iter()
+        .filter(|process| is_restartable(process))
+        .filter_map(|process| match (&process.unit, process.user_id) {
+            (Some(unit), Some(user_id)) => Some(format!("{} (user {})", unit, user_id)),
+            (Some(unit), None) => Some(unit.clone()),
+            _ => None,
+        })
+        .collect();
+    services.sort();
+    services.dedup();
+
+    let mut others: Vec<String> = report
+        .processes
+        .iter()
+        .filter(|process| !is_restartable(process))
+        .map(|process| {
+            let library = process
+                .libraries
+                .first()
+                .and_then(|library| Path::new(library).file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            format!(
+                "{} ({}) uses an old {}",
+                process.command, process.pid, library
+            )
+        })
+        .collect();
+    others.sort();
+    others.dedup();
+
+    if !services.is_empty() {
+        sections.push(format!(
+            "These services still use libraries that were replaced by the update:\n{}",
+            summarize_lines(&services, LIMIT)
+        ));
+    }
+
+    if !others.is_empty() {
+        sections.push(format!(
+            "These programs are not managed by a service that can be restarted safely. Restart them yourself, log out or reboot:\n{}",
+            summarize_lines(&others, LIMIT)
+        ));
+    }
+
+    return sections.join("\n\n");
+}
+
+fn summarize_lines(lines: &[String], limit: usize) -> String {
+    let mut summary = lines
+        .iter()
+        .take(limit)
+        .map(|line| format!("• {}", line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.len() > limit {
+        summary.push_str(&format!("\n…and {} more", lines.len() - limit));
+    }
+
+    return summary;
+}
@@ -0,0 +1,41 @@
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Label};
+
+use crate::models::pkgbuild_finding::PkgbuildFinding;
+
+pub fn fill_risk_badges(badge_box: &GtkBox, findings: &[PkgbuildFinding]) {
+    while let Some(child) = badge_box.first_child() {
+        badge_box.remove(&child);
+    }
+
+    let mut sorted: Vec<&PkgbuildFinding> = findings.iter().collect();
+    sorted.sort_by_key(|finding| std::cmp::Reverse(finding.level));
+
+    let mut shown: Vec<&str> = Vec::new();
+    for finding in &sorted {
+        if shown.contains(&finding.title.as_str()) {
+            continue;
+        }
+        shown.push(&finding.title);
+
+        let details: Vec<String> = sorted
+            .iter()
+            .filter(|other| other.title == finding.title)
+            .map(|other| match other.line {
+                Some(line) => format!("Line {}: {}", line, other.detail),
+                None => other.detail.clone(),
+            })
+            .collect();
+
+        let badge = Label::new(None);
+        badge.set_markup(&format!(
+            "<small><b>{}</b></small>",
+            glib::markup_escape_text(&finding.title)
+        ));
+        badge.add_css_class(finding.level.css_class());
+        badge.set_tooltip_text(Some(&details.join("\n")));
+        badge_box.append(&badge);
+    }
+
+    badge_box.set_visible(!shown.is_empty());
+}
@@ -1,9 +1,20 @@
+use chrono::Local;
+use glib::clone;
 use gtk4::{ApplicationWindow, prelude::*};
 use std::rc::Rc;
 
 use crate::{
-    helpers::settings::{get_available_aur_helpers, load_settings, save_settings},
-    models::{app_settings::AppSettings, snapshot_retention_period::SnapshotRetentionPeriod},
+    constants::{AUR_RPC_URL, BUILTIN_AUR_BUILDER, TIMESHIFT_COMMENT},
+    helpers::{
+        aur_helpers::is_custom_aur_helper,
+        settings::{get_available_aur_helpers, load_settings, save_settings},
+        snapshot_provider::{get_snapshot_provider, list_managed_snapshots},
+        snapshot_retention::{get_retention_policy, plan_snapshot_retention},
+    },
+    models::{
+        app_settings::AppSettings, snapshot_provider_kind::SnapshotProviderKind,
+        system_snapshot::SystemSnapshot,
+    },
 };
 
 pub fn show_settings_dialog(parent: &ApplicationWindow, settings: &AppSettings) {
@@ -23,18 +34,31 @@ pub fn show_settings_dialog(parent: &ApplicationWindow, settings: &AppSettings)
     main_container.set_margin_top(24);
     main_container.set_margin_bottom(24);
 
-    let (aur_enable_check, aur_combo) = create_aur_group(settings, &main_container);
-    let (timeshift_check, retention_count_spin, retention_period_combo) =
-        create_timeshift_group(settings, &main_container);
+    let (aur_enable_check, aur_combo, aur_rpc_entry, aur_chroot_check, devel_check) =
+        create_aur_group(settings, &main_container);
+    let (
+        provider_combo,
+        timeshift_check,
+        retention_count_spin,
+        retention_days_spin,
+        retention_weeks_spin,
+    ) = create_timeshift_group(settings, &main_container);
+    let archive_url_entry = create_downgrade_group(settings, &main_container);
 
     content_area.append(&main_container);
 
     let save_all = {
         let aur_enable_check = aur_enable_check.clone();
         let aur_combo = aur_combo.clone();
+        let aur_rpc_entry = aur_rpc_entry.clone();
+        let aur_chroot_check = aur_chroot_check.clone();
+        let devel_check = devel_check.clone();
+        let provider_combo = provider_combo.clone();
         let timeshift_check = timeshift_check.clone();
         let retention_count_spin = retention_count_spin.clone();
-        let retention_period_combo = retention_period_combo.clone();
+        let retention_days_spin = retention_days_spin.clone();
+        let retention_weeks_spin = retention_weeks_spin.clone();
+        let archive_url_entry = archive_url_entry.clone();
 
         Rc::new(move || {
             let mut new_settings = load_settings();
@@ -49,18 +73,33 @@ pub fn show_settings_dialog(parent: &ApplicationWindow, settings: &AppSettings)
                 };
             }
 
+            let aur_rpc_url = aur_rpc_entry.text().trim().to_string();
+            new_settings.aur_rpc_url = if aur_rpc_url.is_empty() {
+                None
+            } else {
+                Some(aur_rpc_url)
+            };
+            new_settings.aur_clean_chroot = aur_chroot_check.is_active();
+            new_settings.check_devel_updates = devel_check.is_active();
+
+            if let Some(provider) = provider_combo
+                .active_id()
+                .and_then(|id| SnapshotProviderKind::from_id(&id))
+            {
+                new_settings.snapshot_provider = provider;
+            }
+
             new_settings.create_timeshift_snapshot = timeshift_check.is_active();
             new_settings.snapshot_retention_count = retention_count_spin.value() as u32;
+            new_settings.snapshot_retention_days = retention_days_spin.value() as u32;
+            new_settings.snapshot_retention_weeks = retention_weeks_spin.value() as u32;
 
-            if let Some(active_id) = retention_period_combo.active_id() {
-                new_settings.snapshot_retention_period = match active_id.as_str() {
-                    "day" => SnapshotRetentionPeriod::Day,
-                    "week" => SnapshotRetentionPeriod::Week,
-                    "month" => SnapshotRetentionPeriod::Month,
-                    "year" => SnapshotRetentionPeriod::Year,
-                    _ => SnapshotRetentionPeriod::Forever,
-                };
-            }
+            let archive_url = archive_url_entry.text().trim().to_string();
+            new_settings.downgrade_archive_url = if archive_url.is_empty() {
+                None
+            } else {
+                Some(archive_url)
+            };
 
             if let Err(e) = save_settings(&new_settings) {
                 eprintln!("Failed to save settings: {}", e);
@@ -69,9 +108,27 @@ pub fn show_settings_dialog(parent: &ApplicationWindow, settings: &AppSettings)
     };
 
     let aur_combo_weak = aur_combo.clone();
+    let aur_rpc_entry_weak = aur_rpc_entry.clone();
+    let aur_chroot_check_weak = aur_chroot_check.clone();
+    let devel_check_weak = devel_check.clone();
     let save_all_clone = save_all.clone();
     aur_enable_check.connect_toggled(move |check| {
         aur_combo_weak.set_sensitive(check.is_active());
+        aur_rpc_entry_weak.set_sensitive(check.is_active());
+        aur_chroot_check_weak.set_sensitive(check.is_active());
+        devel_check_weak.set_sensitive(check.is_active());
+        save_all_clone();
+    });
+
+    for check in [&aur_chroot_check, &devel_check] {
+        let save_all_clone = save_all.clone();
+        check.connect_toggled(move |_| {
+            save_all_clone();
+        });
+    }
+
+    let save_all_clone = save_all.clone();
+    aur_rpc_entry.connect_changed(move |_| {
         save_all_clone();
     });
 
@@ -80,32 +137,43 @@ pub fn show_settings_dialog(parent: &ApplicationWindow, settings: &AppSettings)
         save_all_clone();
     });
 
-    let retention_count_spin_weak = retention_count_spin.clone();
-    let retention_period_combo_weak = retention_period_combo.clone();
+    let save_all_clone = save_all.clone();
+    provider_combo.connect_changed(move |_| {
+        save_all_clone();
+    });
+
+    let retention_spins = [
+        retention_count_spin.clone(),
+        retention_days_spin.clone(),
+        retention_weeks_spin.clone(),
+    ];
     let save_all_clone = save_all.clone();
     timeshift_check.connect_toggled(move |check| {
         let is_active = check.is_active();
 
-        if let Some(parent) = retention_count_spin_weak.parent() {
-            if let Ok(box_widget) = parent.downcast::<gtk4::Box>() {
-                box_widget.set_sensitive(is_active);
-            }
-        }
-        if let Some(parent) = retention_period_combo_weak.parent() {
-            if let Ok(box_widget) = parent.downcast::<gtk4::Box>() {
-                box_widget.set_sensitive(is_active);
+        for spin in &retention_spins {
+            if let Some(parent) = spin.parent() {
+                if let Ok(box_widget) = parent.downcast::<gtk4::Box>() {
+                    box_widget.set_sensitive(is_active);
+                }
             }
         }
 
         save_all_clone();
     });
 
-    let save_all_clone = save_all.clone();
-    retention_count_spin.connect_value_changed(move |_| {
-        save_all_clone();
-    });
+    for spin in [
+        &retention_count_spin,
+        &retention_days_spin,
+        &retention_weeks_spin,
+    ] {
+        let save_all_clone = save_all.clone();
+        spin.connect_value_changed(move |_| {
+            save_all_clone();
+        });
+    }
 
-    retention_period_combo.connect_changed(move |_| {
+    archive_url_entry.connect_changed(move |_| {
         save_all();
     });
 
@@ -115,7 +183,13 @@ pub fn show_settings_dialog(parent: &ApplicationWindow, settings: &AppSettings)
 fn create_aur_group(
     settings: &AppSettings,
     main_container: &gtk4::Box,
-) -> (gtk4::CheckButton, gtk4::ComboBoxText) {
+) -> (
+    gtk4::CheckButton,
+    gtk4::ComboBoxText,
+    gtk4::Entry,
+    gtk4::CheckButton,
+    gtk4::CheckButton,
+) {
     let aur_section = create_preference_group(
         "AUR Package Manager",
         "Enable support for installing packages from the Arch User Repository (AUR).",
@@ -126,15 +200,20 @@ fn create_aur_group(
     aur_enable_check.set_active(settings.enable_aur_support);
     aur_section.append(&aur_enable_check);
 
-    let available_helpers = get_available_aur_helpers();
+    let available_helpers = get_available_aur_helpers(settings);
     let aur_combo = gtk4::ComboBoxText::new();
     aur_combo.add_css_class("settings-combo");
     aur_combo.set_margin_top(8);
 
     aur_combo.append(Some("auto"), "Auto-detect (recommended)");
     for helper in &available_helpers {
-        aur_combo.append(Some(helper), helper);
+        if is_custom_aur_helper(settings, &helper.id) {
+            aur_combo.append(Some(&helper.id), &format!("{} (custom)", helper.id));
+        } else {
+            aur_combo.append(Some(&helper.id), &helper.id);
+        }
     }
+    aur_combo.append(Some(BUILTIN_AUR_BUILDER), "Built-in (makepkg)");
 
     if let Some(preferred) = &settings.preferred_aur_helper {
         aur_combo.set_active_id(Some(preferred));
@@ -145,76 +224,123 @@ fn create_aur_group(
     aur_combo.set_sensitive(settings.enable_aur_support);
 
     aur_section.append(&aur_combo);
+
+    let aur_rpc_entry = gtk4::Entry::new();
+    aur_rpc_entry.set_placeholder_text(Some(AUR_RPC_URL));
+    aur_rpc_entry.set_tooltip_text(Some(
+        "AUR RPC endpoint used to check for updates. Leave empty for the official AUR.",
+    ));
+    aur_rpc_entry.set_text(settings.aur_rpc_url.as_deref().unwrap_or(""));
+    aur_rpc_entry.set_margin_top(8);
+    aur_rpc_entry.set_sensitive(settings.enable_aur_support);
+    aur_section.append(&aur_rpc_entry);
+
+    let aur_chroot_check =
+        gtk4::CheckButton::with_label("Build in a clean chroot when devtools is installed");
+    aur_chroot_check.add_css_class("settings-check");
+    aur_chroot_check.set_tooltip_text(Some(
+        "Only used by the built-in builder. Without devtools, packages are built with makepkg.",
+    ));
+    aur_chroot_check.set_active(settings.aur_clean_chroot);
+    aur_chroot_check.set_margin_top(8);
+    aur_chroot_check.set_sensitive(settings.enable_aur_support);
+    aur_section.append(&aur_chroot_check);
+
+    let devel_check =
+        gtk4::CheckButton::with_label("Check devel (-git) packages for new upstream commits");
+    devel_check.add_css_class("settings-check");
+    devel_check.set_tooltip_text(Some(
+        "Runs git ls-remote for every devel package. Individual packages can be changed on the Devel Packages page.",
+    ));
+    devel_check.set_active(settings.check_devel_updates);
+    devel_check.set_sensitive(settings.enable_aur_support);
+    aur_section.append(&devel_check);
+
     main_container.append(&aur_section);
 
-    return (aur_enable_check, aur_combo);
+    return (
+        aur_enable_check,
+        aur_combo,
+        aur_rpc_entry,
+        aur_chroot_check,
+        devel_check,
+    );
 }
 
 fn create_timeshift_group(
     settings: &AppSettings,
     main_container: &gtk4::Box,
-) -> (gtk4::CheckButton, gtk4::SpinButton, gtk4::ComboBoxText) {
+) -> (
+    gtk4::ComboBoxText,
+    gtk4::CheckButton,
+    gtk4::SpinButton,
+    gtk4::SpinButton,
+    gtk4::SpinButton,
+) {
     let timeshift_section = create_preference_group(
         "System Snapshots",
         "Automatically create system snapshots before installing updates for easy rollback if needed.",
     );
 
-    let timeshift_check =
-        gtk4::CheckButton::with_label("Create Timeshift snapshot before the update");
+    let provider_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
+    provider_box.set_margin_bottom(8);
+    provider_box.set_hexpand(true);
+
+    let provider_label = gtk4::Label::new(Some("Snapshot tool"));
+    provider_label.set_halign(gtk4::Align::Start);
+    provider_label.set_hexpand(true);
+    provider_box.append(&provider_label);
+
+    let provider_combo = gtk4::ComboBoxText::new();
+    provider_combo.add_css_class("settings-combo");
+    for kind in SnapshotProviderKind::all() {
+        let provider = get_snapshot_provider(&kind);
+        if provider.is_available() {
+            provider_combo.append(Some(kind.id()), provider.name());
+        } else {
+            provider_combo.append(
+                Some(kind.id()),
+                &format!("{} (not available)", provider.name()),
+            );
+        }
+    }
+    provider_combo.set_active_id(Some(settings.snapshot_provider.id()));
+    provider_combo.set_halign(gtk4::Align::End);
+    provider_box.append(&provider_combo);
+
+    timeshift_section.append(&provider_box);
+
+    let timeshift_check = gtk4::CheckButton::with_label("Create a snapshot before the update");
     timeshift_check.add_css_class("settings-check");
     timeshift_check.set_active(settings.create_timeshift_snapshot);
 
     timeshift_section.append(&timeshift_check);
 
-    let retention_count_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
+    let (retention_count_box, retention_count_spin) = create_retention_row(
+        "Always keep the most recent snapshots",
+        1.0,
+        settings.snapshot_retention_count,
+    );
     retention_count_box.set_margin_top(12);
-    retention_count_box.set_hexpand(true);
-
-    let retention_count_label = gtk4::Label::new(Some("Number of snapshots to keep"));
-    retention_count_label.set_halign(gtk4::Align::Start);
-    retention_count_label.set_hexpand(true);
-    retention_count_box.append(&retention_count_label);
-
-    let retention_count_spin = gtk4::SpinButton::with_range(1.0, 10.0, 1.0);
-    retention_count_spin.set_value(settings.snapshot_retention_count as f64);
-    retention_count_spin.add_css_class("settings-spin");
-    retention_count_spin.set_halign(gtk4::Align::End);
-    retention_count_box.append(&retention_count_spin);
-
     timeshift_section.append(&retention_count_box);
 
-    let retention_period_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
-    retention_period_box.set_margin_top(8);
-    retention_period_box.set_hexpand(true);
-
-    let retention_period_label = gtk4::Label::new(Some("Keep snapshots for"));
-    retention_period_label.set_halign(gtk4::Align::Start);
-    retention_period_label.set_hexpand(true);
-    retention_period_box.append(&retention_period_label);
-
-    let retention_period_combo = gtk4::ComboBoxText::new();
-    retention_period_combo.add_css_class("settings-combo");
-    retention_period_combo.append(Some("forever"), "Forever");
-    retention_period_combo.append(Some("day"), "1 Day");
-    retention_period_combo.append(Some("week"), "1 Week");
-    retention_period_combo.append(Some("month"), "1 Month");
-    retention_period_combo.append(Some("year"), "1 Year");
-
-    let active_id = match settings.snapshot_retention_period {
-        SnapshotRetentionPeriod::Forever => "forever",
-        SnapshotRetentionPeriod::Day => "day",
-        SnapshotRetentionPeriod::Week => "week",
-        SnapshotRetentionPeriod::Month => "month",
-        SnapshotRetentionPeriod::Year => "year",
-    };
-    retention_period_combo.set_active_id(Some(active_id));
-    retention_period_combo.set_halign(gtk4::Align::End);
-    retention_period_box.append(&retention_period_combo);
+    let (retention_days_box, retention_days_spin) = create_retention_row(
+        "Keep one snapshot per day for (days)",
+        0.0,
+        settings.snapshot_retention_days,
+    );
+    timeshift_section.append(&retention_days_box);
 
-    timeshift_section.append(&retention_period_box);
+    let (retention_weeks_box, retention_weeks_spin) = create_retention_row(
+        "Keep one snapshot per week for (weeks)",
+        0.0,
+        settings.snapshot_retention_weeks,
+    );
+    timeshift_section.append(&retention_weeks_box);
 
-    let deletion_info_label =
-        gtk4::Label::new(Some("Old snapshots are only deleted when updating."));
+    let deletion_info_label = gtk4::Label::new(Some(
+        "Old snapshots are only deleted when updating. Pinned snapshots are always kept.",
+    ));
     deletion_info_label.set_wrap(true);
     deletion_info_label.set_xalign(0.0);
     deletion_info_label.set_margin_top(8);
@@ -222,19 +348,160 @@ fn create_timeshift_group(
     deletion_info_label.add_css_class("caption");
     timeshift_section.append(&deletion_info_label);
 
+    let preview_btn = gtk4::Button::with_label("Preview Cleanup…");
+    preview_btn.set_halign(gtk4::Align::Start);
+    preview_btn.connect_clicked(|button| {
+        if let Some(window) = button.root().and_downcast::<gtk4::Window>() {
+            show_retention_preview(&window);
+        }
+    });
+    timeshift_section.append(&preview_btn);
+
     let is_active = settings.create_timeshift_snapshot;
     retention_count_box.set_sensitive(is_active);
-    retention_period_box.set_sensitive(is_active);
+    retention_days_box.set_sensitive(is_active);
+    retention_weeks_box.set_sensitive(is_active);
 
     main_container.append(&timeshift_section);
 
     return (
+        provider_combo,
         timeshift_check,
         retention_count_spin,
-        retention_period_combo,
+        retention_days_spin,
+        retention_weeks_spin,
     );
 }
 
+fn create_retention_row(label: &str, min: f64, value: u32) -> (gtk4::Box, gtk4::SpinButton) {
+    let row_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
+    row_box.set_margin_top(8);
+    row_box.set_hexpand(true);
+
+    let row_label = gtk4::Label::new(Some(label));
+    row_label.set_halign(gtk4::Align::Start);
+    row_label.set_hexpand(true);
+    row_box.append(&row_label);
+
+    let spin = gtk4::SpinButton::with_range(min, 52.0, 1.0);
+    spin.set_value(value as f64);
+    spin.add_css_class("settings-spin");
+    spin.set_halign(gtk4::Align::End);
+    row_box.append(&spin);
+
+    return (row_box, spin);
+}
+
+fn show_retention_preview(parent: &gtk4::Window) {
+    let dialog = gtk4::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .message_type(gtk4::MessageType::Info)
+        .buttons(gtk4::ButtonsType::Close)
+        .text("Snapshot Cleanup Preview")
+        .secondary_text("Looking for snapshots...")
+        .build();
+    dialog.connect_response(|dialog, _| {
+        dialog.close();
+    });
+    dialog.show();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        dialog,
+        async move {
+            let settings = load_settings();
+            let result = gio::spawn_blocking(move || {
+                let provider = get_snapshot_provider(&settings.snapshot_provider);
+                let snapshots = list_managed_snapshots(provider.as_ref(), TIMESHIFT_COMMENT)?;
+                let to_delete = plan_snapshot_retention(
+                    &snapshots,
+                    &get_retention_policy(&settings),
+                    Local::now(),
+                );
+                Ok::<_, anyhow::Error>((snapshots, to_delete))
+            })
+            .await;
+
+            let text = match result {
+                Ok(Ok((snapshots, to_delete))) => describe_retention_plan(&snapshots, &to_delete),
+                Ok(Err(e)) => format!("Failed to list snapshots: {}", e),
+                Err(_) => "Failed to list snapshots.".to_string(),
+            };
+            dialog.set_secondary_text(Some(&text));
+        }
+    ));
+}
+
+fn describe_retention_plan(snapshots: &[SystemSnapshot], to_delete: &[SystemSnapshot]) -> String {
+    if snapshots.is_empty() {
+        return "No snapshots created by this application were found.".to_string();
+    }
+
+    let mut text = String::new();
+    let mut kept = Vec::new();
+    let mut deleted = Vec::new();
+
+    let pinned = load_settings().pinned_snapshots;
+
+    for snapshot in snapshots {
+        let mut line = match snapshot.created_at {
+            Some(created_at) => {
+                format!("{}  ({})", snapshot.id, created_at.format("%Y-%m-%d %H:%M"))
+            }
+            None => snapshot.id.clone(),
+        };
+        if pinned.contains(&snapshot.id) {
+            line.push_str("  pinned");
+        }
+
+        if to_delete.iter().any(|s| s.id == snapshot.id) {
+            deleted.push(line);
+        } else {
+            kept.push(line);
+        }
+    }
+
+    text.push_str(&format!(
+        "On the next update {} of {} snapshots would be deleted.\n",
+        deleted.len(),
+        snapshots.len()
+    ));
+
+    if !deleted.is_empty() {
+        text.push_str("\nDelete:\n");
+        for line in &deleted {
+            text.push_str(&format!("  {}\n", line));
+        }
+    }
+
+    if !kept.is_empty() {
+        text.push_str("\nKeep:\n");
+        for line in &kept {
+            text.push_str(&format!("  {}\n", line));
+        }
+    }
+
+    return text;
+}
+
+fn create_downgrade_group(settings: &AppSettings, main_container: &gtk4::Box) -> gtk4::Entry {
+    let downgrade_section = create_preference_group(
+        "Downgrades",
+        "Older package versions are taken from the pacman cache. Optionally, an archive mirror such as https://archive.archlinux.org can be searched as well.",
+    );
+
+    let archive_url_entry = gtk4::Entry::new();
+    archive_url_entry.set_placeholder_text(Some("Archive mirror URL (optional)"));
+    archive_url_entry.set_text(settings.downgrade_archive_url.as_deref().unwrap_or(""));
+    archive_url_entry.set_hexpand(true);
+    downgrade_section.append(&archive_url_entry);
+
+    main_container.append(&downgrade_section);
+
+    return archive_url_entry;
+}
+
 fn create_preference_group(title: &str, description: &str) -> gtk4::Box {
     let group = gtk4::Box::new(gtk4::Orientation::Vertical, 8);
     group.add_css_class("preference-group");
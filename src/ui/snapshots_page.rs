@@ -0,0 +1,461 @@
+use glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, ButtonsType, Label, ListBox, MessageDialog,
+    MessageType, Orientation, ResponseType, ScrolledWindow, SelectionMode, Spinner, TextView,
+    ToggleButton, Window,
+};
+
+use crate::helpers::settings::{load_settings, save_settings};
+use crate::helpers::snapshot_metadata::{diff_against_installed, load_snapshot_metadata};
+use crate::helpers::snapshot_provider::{get_configured_snapshot_provider, get_snapshot_provider};
+use crate::models::snapshot_metadata::SnapshotMetadata;
+use crate::models::system_snapshot::SystemSnapshot;
+use crate::ui::dialogs::{offer_reboot, show_error_dialog};
+use crate::ui::main_window::return_to_package_list;
+use crate::ui::terminal_page::run_in_terminal_page;
+
+pub fn create_snapshots_page() -> GtkBox {
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let title_box = GtkBox::new(Orientation::Vertical, 6);
+    title_box.set_hexpand(true);
+
+    let title_label = Label::new(Some("System Snapshots"));
+    title_label.add_css_class("title-2");
+    title_label.set_halign(gtk4::Align::Start);
+
+    let subtitle_label = Label::new(Some(
+        "Restore the whole system to the state it was in when a snapshot was taken.",
+    ));
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.set_halign(gtk4::Align::Start);
+
+    title_box.append(&title_label);
+    title_box.append(&subtitle_label);
+    header_box.append(&title_box);
+
+    let spinner = Spinner::new();
+    spinner.set_valign(gtk4::Align::Center);
+    header_box.append(&spinner);
+
+    let back_btn = Button::with_label("Back");
+    back_btn.set_valign(gtk4::Align::Center);
+    header_box.append(&back_btn);
+
+    main_box.append(&header_box);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some("No snapshots found."));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&list_box)
+        .build();
+
+    main_box.append(&scrolled);
+
+    main_box.connect_map(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        empty_label,
+        move |_| {
+            load_snapshots(&list_box, &spinner, &empty_label);
+        }
+    ));
+
+    back_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        move |_| {
+            return_to_package_list(&main_box);
+        }
+    ));
+
+    return main_box;
+}
+
+fn load_snapshots(list_box: &ListBox, spinner: &Spinner, empty_label: &Label) {
+    while let Some(row) = list_box.row_at_index(0) {
+        list_box.remove(&row);
+    }
+
+    let provider = get_configured_snapshot_provider();
+    if !provider.is_available() {
+        empty_label.set_text(&format!(
+            "{} is not installed or not configured on this system.",
+            provider.name()
+        ));
+        return;
+    }
+
+    spinner.start();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[weak]
+        empty_label,
+        async move {
+            let result = gio::spawn_blocking(|| {
+                let provider_kind = load_settings().snapshot_provider;
+                let snapshots = get_snapshot_provider(&provider_kind).list()?;
+
+                Ok::<_, anyhow::Error>(
+                    snapshots
+                        .into_iter()
+                        .map(|snapshot| {
+                            let metadata = load_snapshot_metadata(&provider_kind, &snapshot.id);
+                            (snapshot, metadata)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .await;
+            spinner.stop();
+
+            let mut snapshots = match result {
+                Ok(Ok(snapshots)) => snapshots,
+                Ok(Err(e)) => {
+                    empty_label.set_text(&format!("Failed to list snapshots: {}", e));
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    return;
+                }
+            };
+
+            empty_label.set_text("No snapshots found.");
+            snapshots
+                .sort_by(|(a, _), (b, _)| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+
+            for (snapshot, metadata) in &snapshots {
+                list_box.append(&create_snapshot_row(snapshot, metadata.as_ref()));
+            }
+        }
+    ));
+}
+
+fn create_snapshot_row(snapshot: &SystemSnapshot, metadata: Option<&SnapshotMetadata>) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Horizontal, 12);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(8);
+    row_box.set_margin_bottom(8);
+
+    let text_box = GtkBox::new(Orientation::Vertical, 4);
+    text_box.set_hexpand(true);
+
+    let name_label = Label::new(None);
+    name_label.set_xalign(0.0);
+    let title = match snapshot.created_at {
+        Some(created_at) => format!(
+            "{}  ·  {}",
+            snapshot.id,
+            created_at.format("%Y-%m-%d %H:%M")
+        ),
+        None => snapshot.id.clone(),
+    };
+    name_label.set_markup(&format!(
+        "<b>{}</b>  {}",
+        glib::markup_escape_text(&title),
+        glib::markup_escape_text(&snapshot.labels.join(", "))
+    ));
+    text_box.append(&name_label);
+
+    let comment_label = Label::new(Some(if snapshot.description.is_empty() {
+        "No description"
+    } else {
+        &snapshot.description
+    }));
+    comment_label.set_xalign(0.0);
+    comment_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    comment_label.add_css_class("dim-label");
+    text_box.append(&comment_label);
+
+    if let Some(metadata) = metadata {
+        let metadata_label = Label::new(Some(&summarize_metadata(metadata)));
+        metadata_label.set_xalign(0.0);
+        metadata_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        metadata_label.add_css_class("dim-label");
+        metadata_label.add_css_class("caption");
+        text_box.append(&metadata_label);
+    }
+
+    row_box.append(&text_box);
+
+    if let Some(metadata) = metadata {
+        let changes_btn = Button::with_label("Changes…");
+        changes_btn.set_tooltip_text(Some("What changed since this snapshot was taken"));
+        changes_btn.set_valign(gtk4::Align::Center);
+
+        let metadata = metadata.clone();
+        changes_btn.connect_clicked(move |button| {
+            if let Some(window) = button.root().and_downcast::<ApplicationWindow>() {
+                show_changes_dialog(&window, &metadata);
+            }
+        });
+        row_box.append(&changes_btn);
+    }
+
+    let pin_btn = ToggleButton::with_label("Pinned");
+    pin_btn.set_tooltip_text(Some("Pinned snapshots are never deleted automatically"));
+    pin_btn.set_valign(gtk4::Align::Center);
+    pin_btn.set_active(load_settings().pinned_snapshots.contains(&snapshot.id));
+
+    let snapshot_id = snapshot.id.clone();
+    pin_btn.connect_toggled(move |button| {
+        set_snapshot_pinned(&snapshot_id, button.is_active());
+    });
+    row_box.append(&pin_btn);
+
+    let restore_btn = Button::with_label("Restore…");
+    restore_btn.add_css_class("destructive-action");
+    restore_btn.set_valign(gtk4::Align::Center);
+
+    let snapshot = snapshot.clone();
+    restore_btn.connect_clicked(move |button| {
+        if let Some(window) = button.root().and_downcast::<ApplicationWindow>() {
+            confirm_restore(&window, &snapshot);
+        }
+    });
+    row_box.append(&restore_btn);
+
+    return row_box;
+}
+
+fn summarize_metadata(metadata: &SnapshotMetadata) -> String {
+    let mut parts = vec![format!(
+        "Before updating {} packages",
+        metadata.packages.len()
+    )];
+    if !metadata.aur_packages.is_empty() {
+        parts[0].push_str(&format!(" ({} from AUR)", metadata.aur_packages.len()));
+    }
+    if !metadata.kernel.is_empty() {
+        parts.push(format!("kernel {}", metadata.kernel));
+    }
+    if !metadata.host.is_empty() {
+        parts.push(metadata.host.clone());
+    }
+
+    return parts.join("  ·  ");
+}
+
+fn show_changes_dialog(parent: &ApplicationWindow, metadata: &SnapshotMetadata) {
+    let dialog = Window::builder()
+        .title(format!("Changes Since Snapshot {}", metadata.snapshot))
+        .transient_for(parent)
+        .modal(true)
+        .default_width(720)
+        .default_height(520)
+        .build();
+
+    let content_box = GtkBox::new(Orientation::Vertical, 12);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+    content_box.set_margin_top(12);
+    content_box.set_margin_bottom(12);
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+    text_view
+        .buffer()
+        .set_text("Comparing with the local package database...");
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&text_view)
+        .build();
+    content_box.append(&scrolled);
+
+    let close_btn = Button::with_label("Close");
+    close_btn.set_halign(gtk4::Align::End);
+    close_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        move |_| {
+            dialog.close();
+        }
+    ));
+    content_box.append(&close_btn);
+
+    dialog.set_child(Some(&content_box));
+    dialog.present();
+
+    let metadata = metadata.clone();
+    glib::spawn_future_local(clone!(
+        #[weak]
+        text_view,
+        async move {
+            let recorded = metadata.clone();
+            let result = gio::spawn_blocking(move || diff_against_installed(&recorded)).await;
+
+            let mut text = format!(
+                "Snapshot {} taken {} on {} (kernel {})\n",
+                metadata.snapshot,
+                metadata.created_at.format("%Y-%m-%d %H:%M"),
+                metadata.host,
+                metadata.kernel
+            );
+
+            text.push_str("\nUpdate it was taken for:\n");
+            for package in &metadata.packages {
+                text.push_str(&format!(
+                    "  {} {} -> {}{}\n",
+                    package.name,
+                    package.old_version,
+                    package.new_version,
+                    if metadata.aur_packages.contains(&package.name) {
+                        "  (AUR)"
+                    } else {
+                        ""
+                    }
+                ));
+            }
+
+            text.push_str("\nChanged since the snapshot:\n");
+            match result {
+                Ok(Ok(changes)) if changes.is_empty() => {
+                    text.push_str("  Nothing, the installed packages are identical.\n");
+                }
+                Ok(Ok(changes)) => {
+                    for change in &changes {
+                        let versions = match (&change.old_version, &change.new_version) {
+                            (Some(old), Some(new)) => format!("{} -> {}", old, new),
+                            (Some(old), None) => old.clone(),
+                            (None, Some(new)) => new.clone(),
+                            (None, None) => String::new(),
+                        };
+                        text.push_str(&format!(
+                            "  {} {} ({})\n",
+                            change.kind, change.package, versions
+                        ));
+                    }
+                }
+                Ok(Err(e)) => {
+                    text.push_str(&format!("  Failed to read installed packages: {}\n", e))
+                }
+                Err(_) => text.push_str("  Failed to read installed packages.\n"),
+            }
+
+            text_view.buffer().set_text(&text);
+        }
+    ));
+}
+
+fn set_snapshot_pinned(snapshot_id: &str, pinned: bool) {
+    let mut settings = load_settings();
+    settings.pinned_snapshots.retain(|id| id != snapshot_id);
+    if pinned {
+        settings.pinned_snapshots.push(snapshot_id.to_string());
+    }
+
+    if let Err(e) = save_settings(&settings) {
+        eprintln!("Failed to save settings: {}", e);
+    }
+}
+
+fn confirm_restore(window: &ApplicationWindow, snapshot: &SystemSnapshot) {
+    let restore_command = match get_configured_snapshot_provider().restore_command(snapshot) {
+        Ok(command) => command,
+        Err(e) => {
+            show_error_dialog(
+                window.upcast_ref::<Window>(),
+                "Restore Not Available",
+                &e.to_string(),
+            );
+            return;
+        }
+    };
+
+    let dialog = MessageDialog::builder()
+        .transient_for(window)
+        .modal(true)
+        .message_type(MessageType::Warning)
+        .buttons(ButtonsType::None)
+        .text(format!("Restore snapshot {}?", snapshot.id))
+        .secondary_text(
+            "Restoring a snapshot OVERWRITES the current system with the contents of the snapshot. \
+             Every package, update and system file change made since it was taken will be lost. \
+             This cannot be undone from within this application.\n\n\
+             Save your work and close other applications before continuing. \
+             The system must be rebooted once the restore has finished.",
+        )
+        .build();
+
+    dialog.add_button("Cancel", ResponseType::Cancel);
+    dialog.add_button("Restore and Overwrite System", ResponseType::Accept);
+
+    if let Some(restore_btn) = dialog.widget_for_response(ResponseType::Accept) {
+        restore_btn.add_css_class("destructive-action");
+    }
+
+    let snapshot_id = snapshot.id.clone();
+    dialog.connect_response(clone!(
+        #[weak]
+        window,
+        move |dialog, response| {
+            dialog.close();
+
+            if response == ResponseType::Accept {
+                start_restore(&window, &snapshot_id, &restore_command);
+            }
+        }
+    ));
+
+    dialog.show();
+}
+
+fn start_restore(window: &ApplicationWindow, snapshot_id: &str, restore_command: &[String]) {
+    let window_clone = window.clone();
+
+    let result = run_in_terminal_page(
+        window,
+        &format!("Restoring Snapshot {}", snapshot_id),
+        "Restore",
+        restore_command.iter().map(|arg| arg.as_str()).collect(),
+        move |exit_code| {
+            if exit_code == 0 {
+                offer_reboot(
+                    &window_clone,
+                    "Snapshot Restored",
+                    "The system has to be rebooted to finish restoring the snapshot.",
+                );
+            }
+        },
+    );
+
+    if let Err(e) = result {
+        show_error_dialog(
+            window.upcast_ref::<Window>(),
+            "Restore Error",
+            &format!("Failed to start the restore: {}", e),
+        );
+    }
+}
@@ -1,10 +1,23 @@
 use glib::clone;
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, Label, Orientation};
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, FlowBox, Frame, Label, Orientation, SelectionMode,
+    Stack,
+};
+use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
 use vte4::{Terminal, TerminalExt};
 
 use crate::helpers::get_navigation_stack::get_navigation_stack;
+use crate::helpers::terminal::{exit_status_code, spawn_terminal};
+use crate::helpers::transaction_inhibitor::{begin_transaction_inhibit, end_transaction_inhibit};
+use crate::models::aur_build_status::AurBuildStatus;
+
+type FinishedCallback = Box<dyn FnOnce(i32)>;
+
+thread_local! {
+    static ACTIVE_TASK: RefCell<Option<(String, FinishedCallback)>> = const { RefCell::new(None) };
+}
 
 pub fn create_terminal_page() -> GtkBox {
     let main_box = GtkBox::new(Orientation::Vertical, 12);
@@ -25,8 +38,15 @@ pub fn create_terminal_page() -> GtkBox {
     subtitle_label.add_css_class("dim-label");
     subtitle_label.set_halign(gtk4::Align::Start);
 
+    let progress_box = FlowBox::new();
+    progress_box.set_selection_mode(SelectionMode::None);
+    progress_box.set_column_spacing(12);
+    progress_box.set_max_children_per_line(8);
+    progress_box.set_visible(false);
+
     header_box.append(&title_label);
     header_box.append(&subtitle_label);
+    header_box.append(&progress_box);
 
     main_box.append(&header_box);
 
@@ -64,9 +84,7 @@ pub fn create_terminal_page() -> GtkBox {
 
     terminal.connect_child_exited(clone!(
         #[weak]
-        button_box,
-        #[weak]
-        title_label,
+        main_box,
         move |_terminal, exit_status| {
             let mut finished = command_finished.lock().unwrap();
             *finished = true;
@@ -74,13 +92,7 @@ pub fn create_terminal_page() -> GtkBox {
             let mut code = exit_code.lock().unwrap();
             *code = Some(exit_status);
 
-            if exit_status == 0 {
-                title_label.set_text("Installation Completed Successfully");
-            } else {
-                title_label.set_text("Installation Failed");
-            }
-
-            button_box.set_visible(true);
+            complete_terminal_task(&main_box, exit_status_code(exit_status));
         }
     ));
 
@@ -95,6 +107,152 @@ pub fn create_terminal_page() -> GtkBox {
     return main_box;
 }
 
+pub fn is_terminal_task_running() -> bool {
+    return ACTIVE_TASK.with(|task| task.borrow().is_some());
+}
+
+pub fn run_in_terminal_page(
+    window: &ApplicationWindow,
+    title: &str,
+    task_name: &str,
+    args: Vec<&str>,
+    on_finished: impl FnOnce(i32) + 'static,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if is_terminal_task_running() {
+        return Err("Another operation is still running in the terminal".into());
+    }
+
+    let Some(main_box) = window.child().and_downcast::<GtkBox>() else {
+        return Err("Could not find main box".into());
+    };
+    let Some(stack) = main_box.first_child().and_downcast::<Stack>() else {
+        return Err("Could not find stack".into());
+    };
+    let Some(terminal_box) = stack.child_by_name("terminal").and_downcast::<GtkBox>() else {
+        return Err("Could not find terminal box".into());
+    };
+    let Some(terminal_frame) = find_terminal_in_box(&terminal_box) else {
+        return Err("Could not find terminal frame".into());
+    };
+    let Some(terminal) = terminal_frame.child().and_downcast::<Terminal>() else {
+        return Err("Could not find terminal widget".into());
+    };
+
+    if let Some(title_label) = find_title_label(&terminal_box) {
+        title_label.set_text(title);
+    }
+    if let Some(button_box) = terminal_box.last_child() {
+        button_box.set_visible(false);
+    }
+    if let Some(progress_box) = find_progress_box(&terminal_box) {
+        clear_progress_box(&progress_box);
+        progress_box.set_visible(false);
+    }
+
+    terminal.reset(true, true);
+
+    ACTIVE_TASK.with(|task| {
+        task.replace(Some((task_name.to_string(), Box::new(on_finished))));
+    });
+
+    stack.set_visible_child_name("terminal");
+
+    // Tasks run here change the system, so they must not be cut off by a suspend, shutdown
+    // or the window closing.
+    begin_transaction_inhibit(window, title);
+
+    spawn_terminal(&terminal, args, move |_| {
+        complete_terminal_task(&terminal_box, 127);
+    });
+
+    return Ok(());
+}
+
+pub fn show_terminal_progress(window: &ApplicationWindow, items: &[(String, AurBuildStatus)]) {
+    let Some(progress_box) = window
+        .child()
+        .and_downcast::<GtkBox>()
+        .and_then(|main_box| main_box.first_child())
+        .and_downcast::<Stack>()
+        .and_then(|stack| stack.child_by_name("terminal"))
+        .and_downcast::<GtkBox>()
+        .and_then(|terminal_box| find_progress_box(&terminal_box))
+    else {
+        return;
+    };
+
+    clear_progress_box(&progress_box);
+
+    for (name, status) in items {
+        let label = Label::new(Some(&format!("{} · {}", name, status)));
+        label.add_css_class(status.css_class());
+        progress_box.insert(&label, -1);
+    }
+
+    progress_box.set_visible(!items.is_empty());
+}
+
+fn complete_terminal_task(main_box: &GtkBox, exit_code: i32) {
+    let Some((task_name, on_finished)) = ACTIVE_TASK.with(|task| task.take()) else {
+        return;
+    };
+
+    end_transaction_inhibit();
+
+    if let Some(title_label) = find_title_label(main_box) {
+        if exit_code == 0 {
+            title_label.set_text(&format!("{} Completed Successfully", task_name));
+        } else {
+            title_label.set_text(&format!("{} Failed", task_name));
+        }
+    }
+
+    if let Some(button_box) = main_box.last_child() {
+        button_box.set_visible(true);
+    }
+
+    on_finished(exit_code);
+}
+
+fn find_title_label(main_box: &GtkBox) -> Option<Label> {
+    return main_box
+        .first_child()
+        .and_then(|header_box| header_box.first_child())
+        .and_downcast::<Label>();
+}
+
+fn find_progress_box(main_box: &GtkBox) -> Option<FlowBox> {
+    return main_box
+        .first_child()
+        .and_then(|header_box| header_box.last_child())
+        .and_downcast::<FlowBox>();
+}
+
+fn clear_progress_box(progress_box: &FlowBox) {
+    while let Some(child) = progress_box.child_at_index(0) {
+        progress_box.remove(&child);
+    }
+}
+
+fn find_terminal_in_box(container: &GtkBox) -> Option<Frame> {
+    let mut child = container.first_child();
+    while let Some(widget) = child {
+        if let Some(frame) = widget.downcast_ref::<Frame>() {
+            if frame.label().is_some_and(|label| label == "Terminal") {
+                return Some(frame.clone());
+            }
+        }
+
+        if let Some(child_box) = widget.downcast_ref::<GtkBox>() {
+            if let Some(found) = find_terminal_in_box(child_box) {
+                return Some(found);
+            }
+        }
+        child = widget.next_sibling();
+    }
+    return None;
+}
+
 fn refresh_package_list(main_box: &GtkBox) {
     let Some((stack, content_box, window)) = get_navigation_stack(main_box) else {
         return;
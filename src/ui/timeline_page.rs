@@ -0,0 +1,422 @@
+use glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Button, ComboBoxText, Label, ListBox, Orientation,
+    ScrolledWindow, SearchEntry, SelectionMode, Spinner, TextView, Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::helpers::pacman_log::load_pacman_timeline;
+use crate::models::pacman_action_kind::PacmanActionKind;
+use crate::models::pacman_transaction::PacmanTransaction;
+use crate::ui::downgrade_dialog::show_downgrade_dialog;
+use crate::ui::main_window::return_to_package_list;
+
+pub fn create_timeline_page() -> GtkBox {
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let title_box = GtkBox::new(Orientation::Vertical, 6);
+    title_box.set_hexpand(true);
+
+    let title_label = Label::new(Some("Package Log"));
+    title_label.add_css_class("title-2");
+    title_label.set_halign(gtk4::Align::Start);
+
+    let subtitle_label = Label::new(Some(
+        "Every transaction recorded in the pacman log, including those run from the command line.",
+    ));
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.set_halign(gtk4::Align::Start);
+
+    title_box.append(&title_label);
+    title_box.append(&subtitle_label);
+    header_box.append(&title_box);
+
+    let spinner = Spinner::new();
+    spinner.set_valign(gtk4::Align::Center);
+    header_box.append(&spinner);
+
+    let back_btn = Button::with_label("Back");
+    back_btn.set_valign(gtk4::Align::Center);
+    header_box.append(&back_btn);
+
+    main_box.append(&header_box);
+
+    let search_entry = SearchEntry::new();
+    search_entry.set_placeholder_text(Some("Search packages, commands or warnings"));
+    main_box.append(&search_entry);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.set_activate_on_single_click(true);
+    list_box.add_css_class("boxed-list");
+
+    let empty_label = Label::new(Some("No transactions found."));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(24);
+    empty_label.set_margin_bottom(24);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&list_box)
+        .build();
+
+    main_box.append(&scrolled);
+
+    let transactions: Rc<RefCell<Vec<PacmanTransaction>>> = Rc::new(RefCell::new(Vec::new()));
+
+    list_box.set_filter_func(clone!(
+        #[strong]
+        transactions,
+        #[weak]
+        search_entry,
+        #[upgrade_or]
+        true,
+        move |row| {
+            let query = search_entry.text().to_lowercase();
+            if query.is_empty() {
+                return true;
+            }
+
+            let Ok(index) = usize::try_from(row.index()) else {
+                return true;
+            };
+
+            return transactions
+                .borrow()
+                .get(index)
+                .is_some_and(|transaction| transaction_matches(transaction, &query));
+        }
+    ));
+
+    search_entry.connect_search_changed(clone!(
+        #[weak]
+        list_box,
+        move |_| {
+            list_box.invalidate_filter();
+        }
+    ));
+
+    list_box.connect_row_activated(clone!(
+        #[strong]
+        transactions,
+        move |list_box, row| {
+            let Ok(index) = usize::try_from(row.index()) else {
+                return;
+            };
+            let Some(transaction) = transactions.borrow().get(index).cloned() else {
+                return;
+            };
+            if let Some(window) = list_box.root().and_downcast::<ApplicationWindow>() {
+                show_transaction_dialog(&window, &transaction);
+            }
+        }
+    ));
+
+    main_box.connect_map(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[strong]
+        transactions,
+        move |_| {
+            load_timeline(&list_box, &spinner, &transactions);
+        }
+    ));
+
+    back_btn.connect_clicked(clone!(
+        #[weak]
+        main_box,
+        move |_| {
+            return_to_package_list(&main_box);
+        }
+    ));
+
+    return main_box;
+}
+
+fn load_timeline(
+    list_box: &ListBox,
+    spinner: &Spinner,
+    transactions: &Rc<RefCell<Vec<PacmanTransaction>>>,
+) {
+    spinner.start();
+
+    glib::spawn_future_local(clone!(
+        #[weak]
+        list_box,
+        #[weak]
+        spinner,
+        #[strong]
+        transactions,
+        async move {
+            let result = gio::spawn_blocking(load_pacman_timeline).await;
+            spinner.stop();
+
+            let mut loaded = match result {
+                Ok(Ok(loaded)) => loaded,
+                Ok(Err(e)) => {
+                    eprintln!("Failed to load pacman log: {}", e);
+                    Vec::new()
+                }
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    Vec::new()
+                }
+            };
+            loaded.reverse();
+
+            while let Some(row) = list_box.row_at_index(0) {
+                list_box.remove(&row);
+            }
+
+            transactions.replace(loaded);
+
+            for transaction in transactions.borrow().iter() {
+                list_box.append(&create_transaction_row(transaction));
+            }
+        }
+    ));
+}
+
+fn transaction_matches(transaction: &PacmanTransaction, query: &str) -> bool {
+    return transaction
+        .actions
+        .iter()
+        .any(|action| action.package.to_lowercase().contains(query))
+        || transaction
+            .command
+            .as_ref()
+            .is_some_and(|command| command.to_lowercase().contains(query))
+        || transaction
+            .warnings
+            .iter()
+            .any(|warning| warning.to_lowercase().contains(query));
+}
+
+fn summarize_actions(transaction: &PacmanTransaction) -> String {
+    let kinds = [
+        PacmanActionKind::Upgraded,
+        PacmanActionKind::Installed,
+        PacmanActionKind::Removed,
+        PacmanActionKind::Downgraded,
+        PacmanActionKind::Reinstalled,
+    ];
+
+    let mut parts = Vec::new();
+    for kind in kinds {
+        let count = transaction
+            .actions
+            .iter()
+            .filter(|action| action.kind == kind)
+            .count();
+        if count > 0 {
+            parts.push(format!("{} {}", count, kind));
+        }
+    }
+
+    return parts.join(", ");
+}
+
+fn create_transaction_row(transaction: &PacmanTransaction) -> GtkBox {
+    let row_box = GtkBox::new(Orientation::Vertical, 4);
+    row_box.set_margin_start(12);
+    row_box.set_margin_end(12);
+    row_box.set_margin_top(8);
+    row_box.set_margin_bottom(8);
+
+    let mut title = format!(
+        "<b>{}</b>  {}",
+        transaction.started_at.format("%Y-%m-%d %H:%M"),
+        glib::markup_escape_text(&summarize_actions(transaction))
+    );
+    if !transaction.completed {
+        title.push_str("  ·  <span foreground=\"red\">incomplete</span>");
+    }
+    if !transaction.warnings.is_empty() {
+        title.push_str(&format!("  ·  {} warnings", transaction.warnings.len()));
+    }
+
+    let title_label = Label::new(None);
+    title_label.set_xalign(0.0);
+    title_label.set_markup(&title);
+    row_box.append(&title_label);
+
+    let package_names: Vec<&str> = transaction
+        .actions
+        .iter()
+        .map(|action| action.package.as_str())
+        .collect();
+    let packages_label = Label::new(Some(&package_names.join(", ")));
+    packages_label.set_xalign(0.0);
+    packages_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    packages_label.add_css_class("dim-label");
+    row_box.append(&packages_label);
+
+    let mut details = Vec::new();
+    if let Some(command) = &transaction.command {
+        details.push(command.clone());
+    }
+    if let Some(snapshot) = &transaction.snapshot_before {
+        details.push(format!("Snapshot before: {}", snapshot));
+    }
+
+    if !details.is_empty() {
+        let details_label = Label::new(Some(&details.join("  ·  ")));
+        details_label.set_xalign(0.0);
+        details_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        details_label.add_css_class("dim-label");
+        details_label.add_css_class("caption");
+        row_box.append(&details_label);
+    }
+
+    return row_box;
+}
+
+fn show_transaction_dialog(parent: &ApplicationWindow, transaction: &PacmanTransaction) {
+    let dialog = Window::builder()
+        .title(format!(
+            "Transaction — {}",
+            transaction.started_at.format("%Y-%m-%d %H:%M")
+        ))
+        .transient_for(parent)
+        .modal(true)
+        .default_width(760)
+        .default_height(540)
+        .build();
+
+    let content_box = GtkBox::new(Orientation::Vertical, 12);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+    content_box.set_margin_top(12);
+    content_box.set_margin_bottom(12);
+
+    let mut text = String::new();
+
+    if let Some(command) = &transaction.command {
+        text.push_str(&format!("Command: {}\n", command));
+    }
+    text.push_str(&format!(
+        "Started: {}\n",
+        transaction.started_at.format("%Y-%m-%d %H:%M:%S")
+    ));
+    if let Some(finished_at) = transaction.finished_at {
+        text.push_str(&format!(
+            "Finished: {}\n",
+            finished_at.format("%Y-%m-%d %H:%M:%S")
+        ));
+    }
+    if let Some(snapshot) = &transaction.snapshot_before {
+        text.push_str(&format!("Snapshot before: {}\n", snapshot));
+    }
+
+    text.push_str("\nPackages:\n");
+    for action in &transaction.actions {
+        let versions = match (&action.old_version, &action.new_version) {
+            (Some(old), Some(new)) => format!("{} -> {}", old, new),
+            (Some(old), None) => old.clone(),
+            (None, Some(new)) => new.clone(),
+            (None, None) => String::new(),
+        };
+        text.push_str(&format!(
+            "  {} {} ({})\n",
+            action.kind, action.package, versions
+        ));
+    }
+
+    if !transaction.warnings.is_empty() {
+        text.push_str("\nWarnings:\n");
+        for warning in &transaction.warnings {
+            text.push_str(&format!("  {}\n", warning));
+        }
+    }
+
+    if !transaction.hooks.is_empty() {
+        text.push_str("\nHooks:\n");
+        for hook in &transaction.hooks {
+            text.push_str(&format!("  {}\n", hook));
+        }
+    }
+
+    if !transaction.output.is_empty() {
+        text.push_str("\nOutput:\n");
+        for line in &transaction.output {
+            text.push_str(&format!("  {}\n", line));
+        }
+    }
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+    text_view.buffer().set_text(&text);
+
+    let scrolled = ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vscrollbar_policy(gtk4::PolicyType::Automatic)
+        .vexpand(true)
+        .child(&text_view)
+        .build();
+
+    content_box.append(&scrolled);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 12);
+
+    let package_combo = ComboBoxText::new();
+    for action in &transaction.actions {
+        if action.kind != PacmanActionKind::Removed {
+            package_combo.append(Some(&action.package), &action.package);
+        }
+    }
+    package_combo.set_active(Some(0));
+    button_box.append(&package_combo);
+
+    let downgrade_btn = Button::with_label("Downgrade…");
+    downgrade_btn.set_sensitive(package_combo.active_id().is_some());
+    downgrade_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        #[weak]
+        parent,
+        #[weak]
+        package_combo,
+        move |_| {
+            let Some(package) = package_combo.active_id() else {
+                return;
+            };
+            dialog.close();
+            show_downgrade_dialog(&parent, &package);
+        }
+    ));
+    button_box.append(&downgrade_btn);
+
+    let spacer = GtkBox::new(Orientation::Horizontal, 0);
+    spacer.set_hexpand(true);
+    button_box.append(&spacer);
+
+    let close_btn = Button::with_label("Close");
+    close_btn.connect_clicked(clone!(
+        #[weak]
+        dialog,
+        move |_| {
+            dialog.close();
+        }
+    ));
+    button_box.append(&close_btn);
+
+    content_box.append(&button_box);
+
+    dialog.set_child(Some(&content_box));
+    dialog.present();
+}
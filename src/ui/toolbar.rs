@@ -1,21 +1,42 @@
 use crate::constants::{AUR_NAME, TIMESHIFT_COMMENT};
-use crate::helpers::aur::install_aur_packages;
+use crate::helpers::aur::{install_aur_packages, uses_builtin_aur_builder};
+use crate::helpers::aur_builder::prepare_aur_build;
+use crate::helpers::boot_checks::{check_boot_partition, is_boot_related_package};
+use crate::helpers::checkpoint::create_package_checkpoint;
+use crate::helpers::devel_packages::{is_devel_package, record_devel_revisions};
+use crate::helpers::foreign_packages::record_aur_maintainers;
 use crate::helpers::get_navigation_stack::get_navigation_stack;
+use crate::helpers::history::{
+    begin_install_session, discard_install_session, finish_install_session,
+};
 use crate::helpers::settings::load_settings;
-use crate::helpers::terminal::spawn_terminal;
-use crate::helpers::timeshift::{cleanup_timeshift_snapshots, create_timeshift_snapshot};
+use crate::helpers::snapshot_metadata::record_snapshot_metadata;
+use crate::helpers::snapshot_provider::{cleanup_snapshots, get_snapshot_provider};
+use crate::helpers::transaction_inhibitor::{begin_transaction_inhibit, end_transaction_inhibit};
+use crate::models::app_settings::AppSettings;
 use crate::models::package_object::PackageUpdateObject;
+use crate::models::package_update::PackageUpdate;
+use crate::models::session_package::SessionPackage;
+use crate::models::system_snapshot::SystemSnapshot;
+use crate::ui::aur_build_progress::watch_aur_build;
+use crate::ui::aur_review_dialog::review_aur_changes;
+use crate::ui::config_files_page::offer_config_file_review;
 use crate::ui::dialogs::{create_progress_dialog, show_confirm_dialog, show_error_dialog};
+use crate::ui::disk_space_warning::ensure_disk_space;
 use crate::ui::package_list::update_statusbar;
+use crate::ui::restart_summary::show_restart_summary_when_needed;
+use crate::ui::terminal_page::{is_terminal_task_running, run_in_terminal_page};
 use gio::ListStore;
 use glib::clone;
 use gtk4::prelude::*;
 use gtk4::{
-    ApplicationWindow, Box as GtkBox, Button, ColumnView, Frame, Image, Orientation, Paned,
-    ScrolledWindow, Separator, SingleSelection, Stack, Statusbar,
+    ApplicationWindow, Box as GtkBox, Button, ColumnView, Image, MessageDialog, Orientation, Paned,
+    ScrolledWindow, Separator, SingleSelection, Statusbar,
 };
 use shlex::try_quote as quote;
-use std::sync::mpsc;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
 use std::thread;
 use std::time::Duration;
 
@@ -92,34 +113,37 @@ pub fn create_toolbar() -> GtkBox {
         #[weak]
         toolbar,
         move |_| {
-            if let Some((store, _statusbar)) = find_store_and_statusbar(&toolbar) {
+            if let Some((store, statusbar)) = find_store_and_statusbar(&toolbar) {
                 if let Some(window) = toolbar.root().and_downcast::<ApplicationWindow>() {
-                    let settings = load_settings();
-                    let create_snapshot = settings.create_timeshift_snapshot;
+                    let selected_packages = get_selected_packages(&store);
+                    if selected_packages.is_empty() {
+                        return;
+                    }
 
-                    let confirm_dialog = show_confirm_dialog(
+                    ensure_disk_space(
                         &window,
-                        "Confirm Installation",
-                        &format!(
-                            "Install selected updates?{}",
-                            if create_snapshot {
-                                "\nA Timeshift snapshot will be created."
-                            } else {
-                                ""
+                        selected_packages.clone(),
+                        clone!(
+                            #[strong]
+                            window,
+                            move || {
+                                review_aur_changes(
+                                    &window,
+                                    &selected_packages,
+                                    clone!(
+                                        #[strong]
+                                        window,
+                                        move |include_aur| {
+                                            if !include_aur {
+                                                deselect_aur_packages(&store, &statusbar);
+                                            }
+                                            confirm_installation(&store, &window);
+                                        }
+                                    ),
+                                );
                             }
                         ),
                     );
-
-                    confirm_dialog.connect_response(move |dialog, response| {
-                        if response == gtk4::ResponseType::Accept {
-                            if let Err(e) =
-                                install_selected_packages_ui(&store, &window, create_snapshot)
-                            {
-                                eprintln!("Failed to install packages: {}", e);
-                            }
-                        }
-                        dialog.close();
-                    });
                 }
             }
         }
@@ -209,121 +233,320 @@ fn select_all_packages(store: &ListStore, statusbar: &Statusbar) {
     update_statusbar(statusbar, store);
 }
 
-fn install_selected_packages_ui(
-    store: &ListStore,
-    window: &ApplicationWindow,
-    create_snapshot: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut official_packages = Vec::new();
-    let mut aur_packages = Vec::new();
+fn deselect_aur_packages(store: &ListStore, statusbar: &Statusbar) {
+    let n_items = store.n_items();
+    for i in 0..n_items {
+        if let Some(item) = store.item(i).and_downcast::<PackageUpdateObject>() {
+            if item.data().repository == AUR_NAME {
+                item.set_selected(false);
+            }
+        }
+    }
+    let items: Vec<PackageUpdateObject> = (0..n_items)
+        .filter_map(|i| store.item(i).and_downcast::<PackageUpdateObject>())
+        .collect();
+
+    store.remove_all();
+    for item in items {
+        store.append(&item);
+    }
+
+    update_statusbar(statusbar, store);
+}
+
+fn confirm_installation(store: &ListStore, window: &ApplicationWindow) {
+    let settings = load_settings();
+    let provider = get_snapshot_provider(&settings.snapshot_provider);
+    let create_snapshot = settings.create_timeshift_snapshot && provider.is_available();
+
+    let confirm_dialog = show_confirm_dialog(
+        window,
+        "Confirm Installation",
+        &format!(
+            "Install selected updates?{}",
+            if create_snapshot {
+                format!("\nA {} snapshot will be created.", provider.name())
+            } else {
+                "\nA package checkpoint will be saved so the update can be rolled back.".to_string()
+            }
+        ),
+    );
+
+    let store = store.clone();
+    let window = window.clone();
+    confirm_dialog.connect_response(move |dialog, response| {
+        if response == gtk4::ResponseType::Accept {
+            if let Err(e) = install_selected_packages_ui(&store, &window, create_snapshot) {
+                eprintln!("Failed to install packages: {}", e);
+            }
+        }
+        dialog.close();
+    });
+}
+
+fn get_selected_packages(store: &ListStore) -> Vec<PackageUpdate> {
+    let mut selected_packages = Vec::new();
     let n_items = store.n_items();
 
     for i in 0..n_items {
         if let Some(item) = store.item(i).and_downcast::<PackageUpdateObject>() {
             let data = item.data();
             if data.selected {
-                if data.repository == AUR_NAME {
-                    aur_packages.push(data.name);
-                } else {
-                    official_packages.push(data.name);
-                }
+                selected_packages.push(data);
             }
         }
     }
 
-    if official_packages.is_empty() && aur_packages.is_empty() {
+    return selected_packages;
+}
+
+fn install_selected_packages_ui(
+    store: &ListStore,
+    window: &ApplicationWindow,
+    create_snapshot: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let selected_packages = get_selected_packages(store);
+
+    if selected_packages.is_empty() {
         return Ok(());
     }
 
-    if create_snapshot {
-        let progress_dialog = create_progress_dialog(
-            &window.upcast_ref::<gtk4::Window>(),
-            "Creating System Snapshot",
-            "Creating Timeshift snapshot and deleting old snapshots...\n\nPlease wait, this may take a few minutes.",
-        );
-        progress_dialog.show();
+    glib::spawn_future_local(clone!(
+        #[weak]
+        window,
+        async move {
+            let names: Vec<String> = selected_packages.iter().map(|p| p.name.clone()).collect();
+            let result = gio::spawn_blocking(move || {
+                let root = Path::new("/");
+                let boot_packages: Vec<String> = names
+                    .into_iter()
+                    .filter(|name| is_boot_related_package(root, name))
+                    .collect();
+                if boot_packages.is_empty() {
+                    return (boot_packages, Vec::new());
+                }
 
-        execute_timeshift_operations_async(
-            official_packages.clone(),
-            aur_packages.clone(),
-            window.clone(),
-            progress_dialog,
-        );
+                let problems = check_boot_partition(root, &boot_packages);
+                return (boot_packages, problems);
+            })
+            .await;
 
-        return Ok(());
-    }
+            match result {
+                Ok((boot_packages, problems)) if !problems.is_empty() => {
+                    show_error_dialog(
+                        window.upcast_ref::<gtk4::Window>(),
+                        "Kernel Update Blocked",
+                        &format!(
+                            "The update includes {} but the boot setup does not look ready:\n\n{}",
+                            boot_packages.join(", "),
+                            problems.join("\n\n")
+                        ),
+                    );
+                }
+                Ok(_) => start_install(selected_packages, &window, create_snapshot),
+                Err(e) => eprintln!("Error in background thread: {:?}", e),
+            }
+        }
+    ));
 
-    if let Err(e) = navigate_to_terminal_and_install(window, official_packages, aur_packages) {
+    return Ok(());
+}
+
+fn start_install(
+    selected_packages: Vec<PackageUpdate>,
+    window: &ApplicationWindow,
+    create_snapshot: bool,
+) {
+    // The running task owns the inhibitor, so don't take it over only to release it on error.
+    if is_terminal_task_running() {
         show_error_dialog(
-            &window.upcast_ref::<gtk4::Window>(),
+            window.upcast_ref::<gtk4::Window>(),
             "Installation Error",
-            &format!("Failed to start installation: {}", e),
+            "Another operation is still running in the terminal",
         );
+        return;
     }
-    return Ok(());
-}
 
-fn execute_timeshift_operations_async(
-    official_packages: Vec<String>,
-    aur_packages: Vec<String>,
-    window: ApplicationWindow,
-    progress_dialog: gtk4::Dialog,
-) {
-    let (tx, rx) = mpsc::channel();
-    let official_packages_clone = official_packages.clone();
-    let aur_packages_clone = aur_packages.clone();
-    let settings = load_settings();
+    begin_transaction_inhibit(window, "Installing package updates");
 
-    thread::spawn(move || match create_timeshift_snapshot(TIMESHIFT_COMMENT) {
-        Ok(newest) => match cleanup_timeshift_snapshots(TIMESHIFT_COMMENT, &settings, &newest) {
-            Ok(()) => {
-                let _ = tx.send(("success", "Package installation starting".to_string()));
-            }
-            Err(e) => {
-                let _ = tx.send(("error", format!("Failed to clean up old snapshots: {}", e)));
-            }
-        },
-        Err(e) => {
-            let _ = tx.send(("error", format!("Failed to create system snapshot: {}", e)));
-        }
-    });
+    if create_snapshot {
+        let progress_dialog = create_progress_dialog(
+            &window.upcast_ref::<gtk4::Window>(),
+            "Creating System Snapshot",
+            "Creating a system snapshot and deleting old snapshots...\n\nPlease wait, this may take a few minutes.",
+        );
+        execute_snapshot_operations_async(selected_packages, window.clone(), progress_dialog);
 
-    glib::timeout_add_local(Duration::from_millis(50), move || match rx.try_recv() {
-        Ok(("success", _)) => {
-            progress_dialog.close();
+        return;
+    }
 
-            if let Err(e) = navigate_to_terminal_and_install(
-                &window,
-                official_packages_clone.clone(),
-                aur_packages_clone.clone(),
-            ) {
+    glib::spawn_future_local(clone!(
+        #[weak]
+        window,
+        async move {
+            let checkpoint = match gio::spawn_blocking(create_package_checkpoint).await {
+                Ok(Ok(checkpoint)) => Some(checkpoint.id),
+                Ok(Err(e)) => {
+                    eprintln!("Failed to save package checkpoint: {}", e);
+                    None
+                }
+                Err(e) => {
+                    eprintln!("Error in background thread: {:?}", e);
+                    None
+                }
+            };
+
+            if let Err(e) =
+                navigate_to_terminal_and_install(&window, selected_packages, None, checkpoint)
+            {
+                end_transaction_inhibit();
                 show_error_dialog(
                     &window.upcast_ref::<gtk4::Window>(),
                     "Installation Error",
                     &format!("Failed to start installation: {}", e),
                 );
             }
-
-            glib::ControlFlow::Break
         }
-        Ok(("error", message)) => {
-            progress_dialog.close();
-            show_error_dialog(
-                &window.upcast_ref::<gtk4::Window>(),
-                "Timeshift Error",
-                &message,
-            );
-            glib::ControlFlow::Break
+    ));
+}
+
+enum SnapshotEvent {
+    Progress(String),
+    Finished(Result<SystemSnapshot, String>),
+}
+
+fn execute_snapshot_operations_async(
+    selected_packages: Vec<PackageUpdate>,
+    window: ApplicationWindow,
+    progress_dialog: MessageDialog,
+) {
+    let (tx, rx) = mpsc::channel();
+    let settings = load_settings();
+    let packages = selected_packages.clone();
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    progress_dialog.connect_response(clone!(
+        #[strong]
+        cancelled,
+        move |_, _| {
+            cancelled.store(true, Ordering::SeqCst);
         }
-        Err(mpsc::TryRecvError::Empty) => glib::ControlFlow::Continue,
-        Err(mpsc::TryRecvError::Disconnected) => {
-            progress_dialog.close();
-            glib::ControlFlow::Break
+    ));
+
+    let thread_cancelled = cancelled.clone();
+    thread::spawn(move || {
+        let result = create_snapshot_before_install(&settings, packages, &tx, &thread_cancelled);
+        let _ = tx.send(SnapshotEvent::Finished(result));
+    });
+
+    glib::timeout_add_local(Duration::from_millis(50), move || {
+        loop {
+            match rx.try_recv() {
+                Ok(SnapshotEvent::Progress(line)) => {
+                    progress_dialog.set_secondary_text(Some(&line));
+                }
+                Ok(SnapshotEvent::Finished(result)) => {
+                    progress_dialog.close();
+
+                    if cancelled.load(Ordering::SeqCst) {
+                        end_transaction_inhibit();
+                        if let Some((stack, _, _)) = get_navigation_stack(&window) {
+                            stack.set_visible_child_name("content");
+                        }
+                        return glib::ControlFlow::Break;
+                    }
+
+                    match result {
+                        Ok(snapshot) => {
+                            if let Err(e) = navigate_to_terminal_and_install(
+                                &window,
+                                selected_packages.clone(),
+                                Some(snapshot),
+                                None,
+                            ) {
+                                end_transaction_inhibit();
+                                show_error_dialog(
+                                    &window.upcast_ref::<gtk4::Window>(),
+                                    "Installation Error",
+                                    &format!("Failed to start installation: {}", e),
+                                );
+                            }
+                        }
+                        Err(message) => {
+                            end_transaction_inhibit();
+                            show_error_dialog(
+                                &window.upcast_ref::<gtk4::Window>(),
+                                "Snapshot Error",
+                                &message,
+                            );
+                        }
+                    }
+
+                    return glib::ControlFlow::Break;
+                }
+                Err(mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    end_transaction_inhibit();
+                    progress_dialog.close();
+                    return glib::ControlFlow::Break;
+                }
+            }
         }
-        _ => glib::ControlFlow::Continue,
     });
 }
 
+fn create_snapshot_before_install(
+    settings: &AppSettings,
+    packages: Vec<PackageUpdate>,
+    tx: &mpsc::Sender<SnapshotEvent>,
+    cancelled: &AtomicBool,
+) -> Result<SystemSnapshot, String> {
+    let provider = get_snapshot_provider(&settings.snapshot_provider);
+    if !provider.is_available() {
+        return Err(format!(
+            "{} is not installed or not configured on this system.",
+            provider.name()
+        ));
+    }
+
+    let _ = tx.send(SnapshotEvent::Progress(format!(
+        "Checking that {} can take a snapshot...",
+        provider.name()
+    )));
+    if let Err(e) = provider.preflight() {
+        return Err(format!("Cannot create a system snapshot: {}", e));
+    }
+
+    let mut on_output = |line: &str| {
+        let _ = tx.send(SnapshotEvent::Progress(line.to_string()));
+    };
+    let newest = provider
+        .create(TIMESHIFT_COMMENT, &mut on_output, cancelled)
+        .map_err(|e| format!("Failed to create system snapshot: {}", e))?;
+
+    let aur_packages = packages
+        .iter()
+        .filter(|p| p.repository == AUR_NAME)
+        .map(|p| p.name.clone())
+        .collect();
+    if let Err(e) = record_snapshot_metadata(
+        &settings.snapshot_provider,
+        &newest,
+        to_session_packages(packages),
+        aur_packages,
+    ) {
+        eprintln!("Failed to record snapshot metadata: {}", e);
+    }
+
+    let _ = tx.send(SnapshotEvent::Progress(
+        "Deleting old snapshots...".to_string(),
+    ));
+    cleanup_snapshots(provider.as_ref(), TIMESHIFT_COMMENT, settings, &newest)
+        .map_err(|e| format!("Failed to clean up old snapshots: {}", e))?;
+
+    return Ok(newest);
+}
+
 fn create_button_content(icon_name: &str, label_text: &str) -> GtkBox {
     let button_box = GtkBox::new(Orientation::Horizontal, 6);
     button_box.set_halign(gtk4::Align::Center);
@@ -337,30 +560,23 @@ fn create_button_content(icon_name: &str, label_text: &str) -> GtkBox {
     return button_box;
 }
 
-fn find_terminal_in_box(container: &GtkBox) -> Option<Frame> {
-    let mut child = container.first_child();
-    while let Some(widget) = child {
-        if let Some(frame) = widget.downcast_ref::<Frame>() {
-            if frame.label().is_some_and(|label| label == "Terminal") {
-                return Some(frame.clone());
-            }
-        }
-
-        if let Some(child_box) = widget.downcast_ref::<GtkBox>() {
-            if let Some(found) = find_terminal_in_box(child_box) {
-                return Some(found);
-            }
-        }
-        child = widget.next_sibling();
-    }
-    return None;
-}
-
 fn start_installation_in_terminal(
-    terminal: &vte4::Terminal,
-    official_packages: Vec<String>,
-    aur_packages: Vec<String>,
+    window: &ApplicationWindow,
+    selected_packages: Vec<PackageUpdate>,
+    snapshot: Option<SystemSnapshot>,
+    checkpoint: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let official_packages: Vec<String> = selected_packages
+        .iter()
+        .filter(|p| p.repository != AUR_NAME)
+        .map(|p| p.name.clone())
+        .collect();
+    let aur_packages: Vec<String> = selected_packages
+        .iter()
+        .filter(|p| p.repository == AUR_NAME)
+        .map(|p| p.name.clone())
+        .collect();
+
     let pacman_cmd = if !official_packages.is_empty() {
         let pkgs = official_packages
             .clone()
@@ -373,7 +589,13 @@ fn start_installation_in_terminal(
         None
     };
 
-    let aur_cmd = if !aur_packages.is_empty() {
+    let mut aur_build = None;
+    let aur_cmd = if !aur_packages.is_empty() && uses_builtin_aur_builder() {
+        let build = prepare_aur_build(&aur_packages)?;
+        let line = format!("sudo bash {}", quote(&build.script.to_string_lossy())?);
+        aur_build = Some(build);
+        Some(line)
+    } else if !aur_packages.is_empty() {
         let parts = install_aur_packages(aur_packages)?;
         let line = parts
             .iter()
@@ -395,35 +617,129 @@ fn start_installation_in_terminal(
         (None, None) => return Ok(()),
     };
 
-    spawn_terminal(terminal, vec!["bash", "-lc", &joined]);
+    let aur_maintainers: Vec<(String, Option<String>)> = selected_packages
+        .iter()
+        .filter_map(|p| {
+            let aur_info = p.aur_info.as_ref()?;
+            Some((p.name.clone(), aur_info.maintainer.clone()))
+        })
+        .collect();
+    let devel_packages: Vec<(String, String)> = selected_packages
+        .iter()
+        .filter(|p| p.repository == AUR_NAME && is_devel_package(&p.name))
+        .map(|p| {
+            let package_base = p.aur_info.as_ref().map(|info| &info.package_base);
+            (p.name.clone(), package_base.unwrap_or(&p.name).clone())
+        })
+        .collect();
+
+    let session_packages = to_session_packages(selected_packages);
+
+    let snapshot_id = snapshot.as_ref().map(|s| s.id.clone());
+    let finished_window = window.clone();
+    let on_finished = move |exit_code| {
+        finish_install_session(exit_code);
+
+        if exit_code == 0 {
+            if let Err(e) = record_aur_maintainers(&aur_maintainers) {
+                eprintln!("Failed to record AUR maintainers: {}", e);
+            }
+            if !devel_packages.is_empty() {
+                record_devel_revisions_async(devel_packages);
+            }
+            show_restart_summary_when_needed(&finished_window);
+            offer_config_file_review(&finished_window);
+        }
+
+        if let Some(snapshot) = snapshot {
+            complete_snapshot(snapshot);
+        }
+    };
+
+    match begin_install_session(session_packages, snapshot_id, checkpoint) {
+        Ok(transcript) => {
+            let transcript = transcript.to_string_lossy().to_string();
+            let command = format!("bash -lc {}", quote(&joined)?);
+            if let Err(e) = run_in_terminal_page(
+                window,
+                "Installing Packages",
+                "Installation",
+                vec![
+                    "script",
+                    "--quiet",
+                    "--return",
+                    "--flush",
+                    "--command",
+                    &command,
+                    &transcript,
+                ],
+                on_finished,
+            ) {
+                discard_install_session();
+                return Err(e);
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to record install session: {}", e);
+            run_in_terminal_page(
+                window,
+                "Installing Packages",
+                "Installation",
+                vec!["bash", "-lc", &joined],
+                on_finished,
+            )?;
+        }
+    }
+
+    if let Some(build) = aur_build {
+        watch_aur_build(window, build);
+    }
 
     return Ok(());
 }
 
+fn to_session_packages(packages: Vec<PackageUpdate>) -> Vec<SessionPackage> {
+    return packages
+        .into_iter()
+        .map(|p| SessionPackage {
+            name: p.name,
+            repository: p.repository,
+            old_version: p.current_version,
+            new_version: p.new_version,
+        })
+        .collect();
+}
+
+fn record_devel_revisions_async(packages: Vec<(String, String)>) {
+    thread::spawn(move || {
+        if let Err(e) = record_devel_revisions(&packages) {
+            eprintln!("Failed to record devel package revisions: {}", e);
+        }
+    });
+}
+
+fn complete_snapshot(snapshot: SystemSnapshot) {
+    let settings = load_settings();
+
+    thread::spawn(move || {
+        let provider = get_snapshot_provider(&settings.snapshot_provider);
+        if let Err(e) = provider.complete(&snapshot, TIMESHIFT_COMMENT) {
+            eprintln!("Failed to complete snapshot {}: {}", snapshot.id, e);
+        }
+    });
+}
+
 fn navigate_to_terminal_and_install(
     window: &ApplicationWindow,
-    official_packages: Vec<String>,
-    aur_packages: Vec<String>,
+    selected_packages: Vec<PackageUpdate>,
+    snapshot: Option<SystemSnapshot>,
+    checkpoint: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let Some(main_box) = window.child().and_downcast::<GtkBox>() else {
-        return Err("Could not find main box".into());
-    };
-    let Some(stack) = main_box.first_child().and_downcast::<Stack>() else {
-        return Err("Could not find stack".into());
-    };
-    let Some(terminal_box) = stack.child_by_name("terminal").and_downcast::<GtkBox>() else {
-        return Err("Could not find terminal box".into());
-    };
-    let Some(terminal_frame) = find_terminal_in_box(&terminal_box) else {
-        return Err("Could not find terminal frame".into());
-    };
-    let Some(terminal) = terminal_frame.child().and_downcast::<vte4::Terminal>() else {
-        return Err("Could not find terminal widget".into());
-    };
-
-    stack.set_visible_child_name("terminal");
+    if is_terminal_task_running() {
+        return Err("Another operation is still running in the terminal".into());
+    }
 
-    start_installation_in_terminal(&terminal, official_packages, aur_packages)?;
+    start_installation_in_terminal(window, selected_packages, snapshot, checkpoint)?;
 
     return Ok(());
 }
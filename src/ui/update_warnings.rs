@@ -0,0 +1,60 @@
+use gtk4::prelude::*;
+use gtk4::{ApplicationWindow, Box as GtkBox, Button, Label, Orientation, Window};
+
+use crate::ui::dialogs::show_error_dialog;
+
+const MAX_SHOWN_WARNINGS: usize = 50;
+
+pub fn create_update_warnings_bar() -> GtkBox {
+    let warnings_box = GtkBox::new(Orientation::Horizontal, 12);
+    warnings_box.set_margin_start(12);
+    warnings_box.set_margin_end(12);
+    warnings_box.set_margin_top(6);
+    warnings_box.set_margin_bottom(6);
+    warnings_box.set_visible(false);
+
+    return warnings_box;
+}
+
+pub fn fill_update_warnings(warnings_box: &GtkBox, warnings: &[String]) {
+    while let Some(child) = warnings_box.first_child() {
+        warnings_box.remove(&child);
+    }
+
+    warnings_box.set_visible(!warnings.is_empty());
+    if warnings.is_empty() {
+        return;
+    }
+
+    let summary_label = Label::new(Some(&if warnings.len() == 1 {
+        warnings[0].clone()
+    } else {
+        format!(
+            "{} problems while checking for updates. Some updates may be missing.",
+            warnings.len()
+        )
+    }));
+    summary_label.set_xalign(0.0);
+    summary_label.set_hexpand(true);
+    summary_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    summary_label.add_css_class("warning");
+    warnings_box.append(&summary_label);
+
+    let details_btn = Button::with_label("Details");
+    let mut details: Vec<String> = warnings.iter().take(MAX_SHOWN_WARNINGS).cloned().collect();
+    if warnings.len() > MAX_SHOWN_WARNINGS {
+        details.push(format!("…and {} more", warnings.len() - MAX_SHOWN_WARNINGS));
+    }
+    details_btn.connect_clicked(move |button| {
+        let Some(window) = button.root().and_downcast::<ApplicationWindow>() else {
+            return;
+        };
+
+        show_error_dialog(
+            window.upcast_ref::<Window>(),
+            "Problems While Checking for Updates",
+            &details.join("\n"),
+        );
+    });
+    warnings_box.append(&details_btn);
+}